@@ -51,8 +51,9 @@ history-action-edit-last = Edit then build
 history-action-refill = Re-enter parameters
 select-parameter-source = Select parameter source
 parameter-source-preset = preset
-parameter-source-last-build = Last build parameters
+parameter-source-last-build = Recent build parameters
 parameter-source-reenter = Re-enter parameters
+parameter-source-build-history = Browse build history
 parameter-source-manage-presets = Manage presets
 parameter-preset = Parameter preset
 parameter-preset-name = Parameter preset name
@@ -97,3 +98,181 @@ history-cleanup = Auto removed invalid history ({ $count }): { $names }
 history-cleanup-error = History cleanup failed: { $error }
 preset-cleanup = Auto removed invalid parameter presets ({ $count }): { $names }
 preset-cleanup-error = Parameter preset cleanup failed: { $error }
+
+# Test report
+test-report-title = Tests
+test-report-passed = passed
+test-report-failed = failed
+test-report-skipped = skipped
+test-report-more-failures = ... and { $count } more failed tests
+test-report-not-found = No test report found for this build
+test-report-saved = JUnit report saved: { $path }
+test-report-save-failed = Failed to save JUnit report: { $error }
+get-test-report-failed = Failed to get test report: { $error }
+
+# Artifacts
+artifacts-title = Artifacts
+artifacts-none = This build has no artifacts
+artifacts-no-match = No artifacts match: { $pattern }
+artifacts-download-prompt = Download { $count } artifact(s)?
+artifacts-dest-prompt = Download directory
+artifacts-downloaded = Downloaded { $count } artifact(s)
+artifact-invalid-path = Invalid artifact path: { $path }
+get-artifacts-failed = Failed to get build artifacts: { $error }
+download-artifacts-failed = Failed to download artifacts: { $error }
+
+# Build history
+select-build = Select a build
+no-builds = This job has no builds yet
+build-no-parameters = This build has no parameters
+build-action-view-log = View log
+build-action-view-params = View parameters
+build-action-rebuild = Rebuild with the same parameters
+build-action-diff-defaults = Diff parameters against current defaults
+build-params-match-defaults = Build parameters match the current defaults
+build-params-diff-title = Parameters of #{ $number } (current default -> build value)
+get-builds-failed = Failed to get build history: { $error }
+get-build-log-failed = Failed to get build log: { $error }
+rebuild-params-title = Parameters of build #{ $number }
+rebuild-obsolete-params = These parameters no longer exist and will be dropped: { $names }
+rebuild-action-edit = Edit parameters before building
+rebuild-requires-job-url = --rebuild requires -U/--url to point to a specific Jenkins Job URL
+get-build-failed = Failed to get build #{ $number }: { $error }
+
+# Batch
+batch-triggering = triggering...
+batch-building = building
+batch-confirm = Trigger { $count } job(s)?
+batch-unknown-params = Ignored parameters not defined by this job: { $names }
+batch-summary-title = Batch results
+
+# Pipeline
+pipeline-confirm = Run { $count } stage(s)?
+pipeline-stage-skipped = ==> { $name } (skipped)
+pipeline-summary-title = Pipeline results
+pipeline-column-stage = STAGE
+pipeline-column-job = JOB
+pipeline-column-result = RESULT
+pipeline-column-duration = DURATION
+pipeline-column-url = URL
+pipeline-captured = captured { $name } = { $value }
+pipeline-capture-failed = { $name }: failed to capture values: { $error }
+
+# Notifications
+notify-failed = Failed to send the build notification: { $error }
+
+# Hooks
+running-hook = Running hook: { $command }
+pre-build-hook-failed = Pre-build hook failed, build not triggered: { $error }
+post-build-hook-failed = Post-build hook failed: { $error }
+
+# Git branch check
+git-branch-not-on-remote = Branch { $branch } does not exist on { $remote }
+git-branch-head-mismatch = Local { $branch } ({ $local_sha }) differs from { $remote }/{ $branch } ({ $remote_sha }), the build uses the remote commit
+git-uncommitted-changes = There are uncommitted changes on { $branch }, they are not part of the build
+git-check-prompt = What do you want to do?
+git-check-action-push = Push { $branch } to { $remote } and continue
+git-check-action-continue = Continue anyway
+git-check-action-abort = Abort
+git-push-failed = git push failed: { $error }
+git-check-non-interactive = Build not triggered: push the branches first, or disable check_git_push
+build-aborted = Build aborted
+
+# Protected jobs
+protected-label = [protected]
+protected-job-title = PROTECTED JOB on { $service }
+protected-no-last-build = No previous build, parameters:
+protected-diff-title = Changes since build #{ $number }:
+protected-no-changes = Same parameters as the last build
+protected-type-name = Type the job name ({ $name }) to trigger
+protected-name-mismatch = The name does not match
+protected-requires-flag = Protected job { $name } needs --yes-i-mean-prod to be triggered without confirmation
+
+# Freeze windows
+freeze-warning = Deployment freeze { $name } is active for { $job }
+freeze-blocked = { $job } is blocked by the deployment freeze { $name }
+freeze-invalid = Invalid freeze window: { $error }
+
+# Parameter validation
+rule-required = is required
+rule-pattern = must match { $pattern }
+rule-not-allowed = must be one of: { $values }
+rule-not-git-tag = must be an existing git tag
+rule-invalid-regex = invalid rule regex { $pattern }
+params-invalid = Parameters break the validation rules, build not triggered:
+params-invalid-reprompt = Parameters break the validation rules, please correct them:
+
+# Dry run
+dry-run-title = Dry run, the build is not triggered:
+dry-run-vs-defaults = Changes from the job defaults:
+dry-run-vs-last-build = Changes from the last build in history:
+dry-run-no-last-build = No previous build in history
+dry-run-no-changes = none
+dry-run-command = Equivalent command:
+dry-run-curl-env = Export { $names } before running it
+
+# History runs
+select-history-run = Select a recent build
+
+# Log book
+log-book-empty = No builds in the log book match
+log-book-more = ... {$count} more, use --limit to list them
+log-book-stats-title = Statistics:
+log-book-runs = Builds: {$count}
+log-book-success-rate = Success rate: {$rate}% ({$succeeded}/{$finished} finished)
+log-book-median-duration = Median duration: {$duration}
+log-book-top-presets = Most used presets: {$presets}
+log-book-runs-kept = Only the last {$count} builds of each job are kept, see history_runs
+unknown-service = Unknown service: {$name}
+log-book-load-failed = Failed to load history: {$error}
+
+# Preset sharing
+preset-load-failed = Failed to load parameter presets: {$error}
+preset-save-failed = Failed to save parameter presets: {$error}
+presets-exported = Exported {$count} parameter presets
+preset-import-conflict = Parameter preset "{$name}" of {$job} exists with other values:
+preset-import-action = Imported preset
+preset-import-skip = Keep the local preset
+preset-import-overwrite = Overwrite the local preset
+preset-import-rename = Import under a new name
+preset-import-aborted = Import cancelled, no presets were changed
+presets-imported = Imported parameter presets: {$added} added, {$overwritten} overwritten, {$renamed} renamed, {$skipped} skipped, {$unchanged} unchanged
+parameter-source-shared-preset = shared: {$source}
+shared-preset-read-only = Shared preset from {$source}, read-only: save it as a new preset to change it
+preset-source-failed = Skipped preset source {$source}: {$error}
+
+# Preset management
+manage-preset-show = Show parameters
+manage-preset-edit-param = Edit a parameter value
+manage-preset-description = Edit description
+manage-preset-tags = Edit tags
+manage-preset-duplicate = Duplicate preset
+select-preset-to-manage = Select preset
+select-preset-param = Select parameter
+preset-description = Description
+preset-tags = Tags (comma separated)
+parameter-preset-duplicated = Duplicated parameter preset: {$name} -> {$new}
+preset-param-obsolete = obsolete
+preset-param-new = new
+preset-column-name = Parameter
+preset-column-value = Preset
+preset-column-default = Job default
+preset-no-params = The preset and the job have no parameters
+remove-obsolete-param-confirm = Remove "{$name}", no longer a parameter of the job?
+
+# Parameter templates
+parameter-source-template = template
+template-read-only = Parameter template of the configuration, read-only: save it as a new preset to change it
+
+# Dynamic values
+dynamic-value-failed = Failed to resolve parameter value: {$error}
+
+# Migrations
+migration-newer-version = {$file} was written by a newer jenkins-cli (version {$version}, this version supports {$supported}), please upgrade jenkins-cli
+migration-applied = Migrated {$file} from version {$from} to {$to}, backup: {$backup}
+
+# Data files
+store-corrupted = {$file} could not be parsed and was moved to {$backup}, starting with an empty file
+
+# Job list cache
+job-list-refreshed = The job list was refreshed
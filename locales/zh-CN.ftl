@@ -51,8 +51,9 @@ history-action-edit-last = 修改上次参数后发布
 history-action-refill = 重新填写参数
 select-parameter-source = 请选择参数来源
 parameter-source-preset = 预设
-parameter-source-last-build = 上次构建参数
+parameter-source-last-build = 最近的构建参数
 parameter-source-reenter = 重新填写参数
+parameter-source-build-history = 浏览构建历史
 parameter-source-manage-presets = 管理预设
 parameter-preset = 参数预设
 parameter-preset-name = 参数预设名称
@@ -97,3 +98,181 @@ history-cleanup = 自动清理失效历史({ $count }): { $names }
 history-cleanup-error = 清理历史记录失败: { $error }
 preset-cleanup = 自动清理失效参数预设({ $count }): { $names }
 preset-cleanup-error = 清理参数预设失败: { $error }
+
+# Test report
+test-report-title = 测试
+test-report-passed = 通过
+test-report-failed = 失败
+test-report-skipped = 跳过
+test-report-more-failures = ... 另有 { $count } 个失败用例
+test-report-not-found = 此构建没有测试报告
+test-report-saved = JUnit 报告已保存: { $path }
+test-report-save-failed = 保存 JUnit 报告失败: { $error }
+get-test-report-failed = 获取测试报告失败: { $error }
+
+# Artifacts
+artifacts-title = 构建产物
+artifacts-none = 此构建没有构建产物
+artifacts-no-match = 没有匹配的构建产物: { $pattern }
+artifacts-download-prompt = 是否下载 { $count } 个构建产物?
+artifacts-dest-prompt = 下载目录
+artifacts-downloaded = 已下载 { $count } 个构建产物
+artifact-invalid-path = 无效的构建产物路径: { $path }
+get-artifacts-failed = 获取构建产物失败: { $error }
+download-artifacts-failed = 下载构建产物失败: { $error }
+
+# Build history
+select-build = 请选择构建
+no-builds = 此 Job 暂无构建记录
+build-no-parameters = 此构建没有参数
+build-action-view-log = 查看日志
+build-action-view-params = 查看参数
+build-action-rebuild = 使用相同参数重新构建
+build-action-diff-defaults = 与当前默认参数对比
+build-params-match-defaults = 构建参数与当前默认值一致
+build-params-diff-title = #{ $number } 的参数 (当前默认值 -> 构建值)
+get-builds-failed = 获取构建历史失败: { $error }
+get-build-log-failed = 获取构建日志失败: { $error }
+rebuild-params-title = 构建 #{ $number } 的参数
+rebuild-obsolete-params = 以下参数已不存在, 将被忽略: { $names }
+rebuild-action-edit = 修改参数后发布
+rebuild-requires-job-url = --rebuild 需要 -U/--url 指向具体 Jenkins Job 地址
+get-build-failed = 获取构建 #{ $number } 失败: { $error }
+
+# Batch
+batch-triggering = 正在触发...
+batch-building = 构建中
+batch-confirm = 是否触发 { $count } 个 Job?
+batch-unknown-params = 已忽略此 Job 未定义的参数: { $names }
+batch-summary-title = 批量构建结果
+
+# Pipeline
+pipeline-confirm = 是否运行 { $count } 个阶段?
+pipeline-stage-skipped = ==> { $name } (已跳过)
+pipeline-summary-title = 流水线结果
+pipeline-column-stage = 阶段
+pipeline-column-job = JOB
+pipeline-column-result = 结果
+pipeline-column-duration = 耗时
+pipeline-column-url = URL
+pipeline-captured = 已捕获 { $name } = { $value }
+pipeline-capture-failed = { $name }: 捕获值失败: { $error }
+
+# Notifications
+notify-failed = 发送构建通知失败: { $error }
+
+# Hooks
+running-hook = 执行钩子: { $command }
+pre-build-hook-failed = 构建前钩子执行失败, 未触发构建: { $error }
+post-build-hook-failed = 构建后钩子执行失败: { $error }
+
+# Git branch check
+git-branch-not-on-remote = 分支 { $branch } 在 { $remote } 上不存在
+git-branch-head-mismatch = 本地 { $branch } ({ $local_sha }) 与 { $remote }/{ $branch } ({ $remote_sha }) 不一致, 构建将使用远程提交
+git-uncommitted-changes = { $branch } 上有未提交的修改, 不会包含在构建中
+git-check-prompt = 请选择操作
+git-check-action-push = 推送 { $branch } 到 { $remote } 并继续
+git-check-action-continue = 仍然继续
+git-check-action-abort = 取消
+git-push-failed = git push 失败: { $error }
+git-check-non-interactive = 未触发构建: 请先推送分支, 或关闭 check_git_push
+build-aborted = 已取消构建
+
+# Protected jobs
+protected-label = [受保护]
+protected-job-title = 受保护的任务 ({ $service })
+protected-no-last-build = 没有历史构建, 参数:
+protected-diff-title = 与构建 #{ $number } 相比的变更:
+protected-no-changes = 参数与上次构建相同
+protected-type-name = 输入任务名称 ({ $name }) 以触发构建
+protected-name-mismatch = 名称不匹配
+protected-requires-flag = 受保护的任务 { $name } 需要 --yes-i-mean-prod 才能跳过确认触发
+
+# Freeze windows
+freeze-warning = 封版时段 { $name } 生效中: { $job }
+freeze-blocked = { $job } 处于封版时段 { $name }, 禁止触发构建
+freeze-invalid = 封版时段配置无效: { $error }
+
+# Parameter validation
+rule-required = 不能为空
+rule-pattern = 需匹配 { $pattern }
+rule-not-allowed = 只能为以下值之一: { $values }
+rule-not-git-tag = 需为已存在的 git 标签
+rule-invalid-regex = 校验规则中的正则无效: { $pattern }
+params-invalid = 参数未通过校验规则, 未触发构建:
+params-invalid-reprompt = 参数未通过校验规则, 请修改:
+
+# Dry run
+dry-run-title = 预览模式, 不会触发构建:
+dry-run-vs-defaults = 与 Job 默认值相比的变更:
+dry-run-vs-last-build = 与历史记录中上次构建相比的变更:
+dry-run-no-last-build = 历史记录中没有上次构建
+dry-run-no-changes = 无
+dry-run-command = 等效命令:
+dry-run-curl-env = 运行前请先导出 { $names }
+
+# History runs
+select-history-run = 选择最近的构建
+
+# Log book
+log-book-empty = 没有匹配的构建记录
+log-book-more = ... 还有 {$count} 条, 使用 --limit 显示更多
+log-book-stats-title = 统计:
+log-book-runs = 构建次数: {$count}
+log-book-success-rate = 成功率: {$rate}% ({$succeeded}/{$finished} 已完成)
+log-book-median-duration = 耗时中位数: {$duration}
+log-book-top-presets = 最常用的预设: {$presets}
+log-book-runs-kept = 每个 Job 只保留最近 {$count} 次构建, 见 history_runs
+unknown-service = 未知的服务: {$name}
+log-book-load-failed = 加载历史记录失败: {$error}
+
+# Preset sharing
+preset-load-failed = 加载参数预设失败: {$error}
+preset-save-failed = 保存参数预设失败: {$error}
+presets-exported = 已导出 {$count} 个参数预设
+preset-import-conflict = {$job} 已存在参数不同的预设 "{$name}":
+preset-import-action = 导入的预设
+preset-import-skip = 保留本地预设
+preset-import-overwrite = 覆盖本地预设
+preset-import-rename = 以新名称导入
+preset-import-aborted = 已取消导入, 预设未修改
+presets-imported = 参数预设已导入: 新增 {$added}, 覆盖 {$overwritten}, 重命名 {$renamed}, 跳过 {$skipped}, 未变化 {$unchanged}
+parameter-source-shared-preset = 共享: {$source}
+shared-preset-read-only = 来自 {$source} 的共享预设为只读, 如需修改请另存为新预设
+preset-source-failed = 已跳过预设来源 {$source}: {$error}
+
+# Preset management
+manage-preset-show = 查看参数
+manage-preset-edit-param = 修改参数值
+manage-preset-description = 修改描述
+manage-preset-tags = 修改标签
+manage-preset-duplicate = 复制预设
+select-preset-to-manage = 请选择预设
+select-preset-param = 请选择参数
+preset-description = 描述
+preset-tags = 标签 (逗号分隔)
+parameter-preset-duplicated = 已复制参数预设: {$name} -> {$new}
+preset-param-obsolete = 已废弃
+preset-param-new = 新增
+preset-column-name = 参数
+preset-column-value = 预设值
+preset-column-default = Job 默认值
+preset-no-params = 预设和 Job 都没有参数
+remove-obsolete-param-confirm = "{$name}" 已不是 Job 的参数, 是否删除?
+
+# Parameter templates
+parameter-source-template = 模板
+template-read-only = 配置中的参数模板为只读, 如需修改请另存为新预设
+
+# Dynamic values
+dynamic-value-failed = 解析参数值失败: {$error}
+
+# Migrations
+migration-newer-version = {$file} 由更新版本的 jenkins-cli 写入 (版本 {$version}, 当前版本支持 {$supported}), 请升级 jenkins-cli
+migration-applied = 已将 {$file} 从版本 {$from} 迁移到 {$to}, 备份: {$backup}
+
+# Data files
+store-corrupted = {$file} 无法解析, 已移动到 {$backup}, 将使用空文件
+
+# Job list cache
+job-list-refreshed = Job 列表已刷新
@@ -0,0 +1,566 @@
+//! Subcommand handlers (`jenkins <subcommand>`), each returning the process exit code.
+
+use clap::ArgMatches;
+use colored::*;
+use dialoguer::{theme::ColorfulTheme, Confirm, FuzzySelect};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::config::CONFIG;
+use crate::i18n::macros::t;
+use crate::jenkins::{
+    artifacts,
+    batch::{self, BatchJob, PreparedJob},
+    client::JenkinsClient,
+    history::{History, HistoryEntry},
+    log_book::{self, LogBookFilter},
+    pipeline::{self, PreparedStage, StageStatus},
+    policy,
+    preset_share::{self, ConflictAction, ExportFilter},
+    presets::{self, JobPresetIdentity, PresetStore},
+    template,
+    validation::{self, ParamValidator},
+};
+use crate::models::{GlobalConfig, JenkinsConfig};
+use crate::prompt;
+use crate::utils::format_url;
+
+/// The global configuration and the service selected by `initialize_config`.
+/// Prints the configuration hint when no service is configured.
+async fn service_config() -> Option<(Option<GlobalConfig>, JenkinsConfig)> {
+    let config = CONFIG.lock().await;
+    let Some(jenkins_config) = config.jenkins.clone() else {
+        eprintln!("{}", t!("fill-required-config").yellow());
+        println!("{}", t!("jenkins-login-instruction"));
+        return None;
+    };
+    Some((config.global.clone(), jenkins_config))
+}
+
+/// Create a client for the service selected by `initialize_config`.
+async fn service_client() -> Option<JenkinsClient> {
+    let (global_config, jenkins_config) = service_config().await?;
+    Some(JenkinsClient::from_service(&jenkins_config, global_config.as_ref()))
+}
+
+/// Clients of the services the jobs of a manifest run on, created on first use.
+struct ServiceClients {
+    global_config: Option<GlobalConfig>,
+    selected: JenkinsConfig,
+    services: Vec<JenkinsConfig>,
+    // service url -> (client resolving jobs, client shared by the triggered jobs)
+    clients: HashMap<String, (JenkinsClient, Arc<JenkinsClient>)>,
+}
+
+impl ServiceClients {
+    async fn load() -> Option<Self> {
+        let (global_config, selected) = service_config().await?;
+        let services = CONFIG.lock().await.services.clone();
+        Some(Self {
+            global_config,
+            selected,
+            services,
+            clients: HashMap::new(),
+        })
+    }
+
+    /// The service of a job URL and its clients.
+    fn for_job(&mut self, job_url: &str) -> (JenkinsConfig, &mut JenkinsClient, Arc<JenkinsClient>) {
+        let service = batch::service_for_job(&self.selected, &self.services, job_url);
+        let global_config = self.global_config.as_ref();
+        let (client, shared) = self.clients.entry(service.url.clone()).or_insert_with(|| {
+            (
+                JenkinsClient::from_service(&service, global_config),
+                Arc::new(JenkinsClient::from_service(&service, global_config)),
+            )
+        });
+        let shared = Arc::clone(shared);
+        (service, client, shared)
+    }
+}
+
+/// Personal presets and the shared `preset_sources` of the configuration.
+/// Prints the error when the presets cannot be loaded.
+async fn preset_store() -> Option<PresetStore> {
+    let config = CONFIG.lock().await;
+    let mut store = match PresetStore::new() {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("{}", t!("preset-load-failed", "error" => e.to_string()).red());
+            return None;
+        }
+    };
+    if let Some(global) = &config.global {
+        store.shared = preset_share::load_shared_presets(&global.preset_sources, &config.services);
+    }
+    Some(store)
+}
+
+/// Check the freeze windows of all jobs before triggering the first one.
+fn check_freeze(global_config: Option<&GlobalConfig>, jobs: &[&PreparedJob]) -> bool {
+    // Check every job so all active windows are reported
+    let blocked = jobs
+        .iter()
+        .filter(|job| !policy::check_freeze(global_config, &job.service, &job.job_name, &job.name))
+        .count();
+    blocked == 0
+}
+
+/// `jenkins artifacts <build-url> [--download <glob>] [--dest <dir>]`
+pub async fn artifacts(matches: &ArgMatches) -> i32 {
+    let build_url = matches.get_one::<String>("build_url").expect("required argument");
+    let Some(client) = service_client().await else {
+        return 1;
+    };
+
+    let build_artifacts = match client.get_build_artifacts(build_url).await {
+        Ok(build_artifacts) => build_artifacts,
+        Err(e) => {
+            eprintln!("{}", t!("get-artifacts-failed", "error" => e.to_string()));
+            return 1;
+        }
+    };
+
+    let Some(pattern) = matches.get_one::<String>("download") else {
+        artifacts::print_artifacts(&build_artifacts);
+        return 0;
+    };
+
+    let selected = artifacts::filter_artifacts(&build_artifacts, pattern);
+    if selected.is_empty() {
+        eprintln!("{}", t!("artifacts-no-match", "pattern" => pattern.as_str()).yellow());
+        return 1;
+    }
+    let dest = matches
+        .get_one::<String>("dest")
+        .map(|dest| dest.as_str())
+        .unwrap_or(".");
+    match artifacts::download_artifacts(&client, build_url, &selected, Path::new(dest)).await {
+        Ok(count) => {
+            println!("{}", t!("artifacts-downloaded", "count" => count.to_string()));
+            0
+        }
+        Err(e) => {
+            eprintln!("{}", t!("download-artifacts-failed", "error" => e.to_string()));
+            1
+        }
+    }
+}
+
+/// The configured service named by `--service`.
+fn find_service<'a>(
+    matches: &ArgMatches,
+    jenkins_configs: &'a [JenkinsConfig],
+) -> anyhow::Result<Option<&'a JenkinsConfig>> {
+    let Some(name) = matches.get_one::<String>("service") else {
+        return Ok(None);
+    };
+    jenkins_configs
+        .iter()
+        .find(|config| &config.name == name)
+        .map(Some)
+        .ok_or_else(|| anyhow::anyhow!(t!("unknown-service", "name" => name.as_str())))
+}
+
+/// Build the log book filter from the command line arguments.
+fn log_book_filter(matches: &ArgMatches, jenkins_configs: &[JenkinsConfig]) -> anyhow::Result<LogBookFilter> {
+    let service_url = find_service(matches, jenkins_configs)?.map(|config| config.url.clone());
+    let job = matches
+        .get_one::<String>("job")
+        .map(|pattern| regex::Regex::new(pattern))
+        .transpose()?;
+    let since = matches
+        .get_one::<String>("since")
+        .map(|date| log_book::parse_date(date))
+        .transpose()?;
+    let until = matches
+        .get_one::<String>("until")
+        .map(|date| log_book::parse_date(date))
+        .transpose()?;
+    let params = matches
+        .get_many::<String>("param")
+        .unwrap_or_default()
+        .map(|filter| log_book::parse_param_filter(filter))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(LogBookFilter {
+        service_url,
+        job,
+        since,
+        until,
+        result: matches.get_one::<String>("result").cloned(),
+        params,
+    })
+}
+
+/// `jenkins log-book [--service <name>] [--job <regex>] [--since/--until <date>] [--result <result>] [--param NAME=GLOB]`
+pub fn log_book(matches: &ArgMatches, global_config: &GlobalConfig, jenkins_configs: &[JenkinsConfig]) -> i32 {
+    let filter = match log_book_filter(matches, jenkins_configs) {
+        Ok(filter) => filter,
+        Err(e) => {
+            eprintln!("{}", e.to_string().red());
+            return 1;
+        }
+    };
+    let mut history = match History::new() {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("{}", t!("log-book-load-failed", "error" => e.to_string()).red());
+            return 1;
+        }
+    };
+    history.max_runs = global_config.history_runs;
+    let limit = *matches.get_one::<usize>("limit").expect("default value");
+    log_book::print_log_book(&log_book::find_runs(&history, &filter), limit, history.max_runs());
+    0
+}
+
+fn export_filter(matches: &ArgMatches, jenkins_configs: &[JenkinsConfig]) -> anyhow::Result<ExportFilter> {
+    Ok(ExportFilter {
+        service_url: find_service(matches, jenkins_configs)?.map(|config| config.url.clone()),
+        job: matches
+            .get_one::<String>("job")
+            .map(|pattern| regex::Regex::new(pattern))
+            .transpose()?,
+    })
+}
+
+/// `jenkins presets export [--service <name>] [--job <regex>]`, the TOML is printed to stdout.
+pub fn presets_export(matches: &ArgMatches, jenkins_configs: &[JenkinsConfig]) -> i32 {
+    let filter = match export_filter(matches, jenkins_configs) {
+        Ok(filter) => filter,
+        Err(e) => {
+            eprintln!("{}", e.to_string().red());
+            return 1;
+        }
+    };
+    let store = match PresetStore::new() {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("{}", t!("preset-load-failed", "error" => e.to_string()).red());
+            return 1;
+        }
+    };
+    let export = preset_share::export_presets(&store, jenkins_configs, &filter);
+    let count: usize = export.jobs.iter().map(|job| job.presets.len()).sum();
+    match toml::to_string(&export) {
+        Ok(content) => {
+            print!("{}", content);
+            eprintln!("{}", t!("presets-exported", "count" => count.to_string()));
+            0
+        }
+        Err(e) => {
+            eprintln!("{}", e.to_string().red());
+            1
+        }
+    }
+}
+
+/// Ask what to do with an imported preset whose name exists with other values.
+fn select_conflict_action(
+    store: &PresetStore,
+    identity: &JobPresetIdentity,
+    preset: &presets::ParameterPreset,
+) -> Option<ConflictAction> {
+    println!();
+    println!(
+        "{}",
+        t!("preset-import-conflict", "name" => preset.name.as_str(), "job" => identity.job_name.as_str()).yellow()
+    );
+    if let Some(existing) = store.find_preset(identity, &preset.name) {
+        presets::print_param_diff(&presets::diff_params(&existing.params, &preset.params));
+    }
+    let actions = [
+        (t!("preset-import-skip"), ConflictAction::Skip),
+        (t!("preset-import-overwrite"), ConflictAction::Overwrite),
+        (t!("preset-import-rename"), ConflictAction::Rename),
+    ];
+    let labels: Vec<&str> = actions.iter().map(|(label, _)| label.as_str()).collect();
+    let selection = prompt::handle_selection_opt(prompt::with_prompt_kind(prompt::PromptKind::FuzzySelectVim, || {
+        FuzzySelect::with_theme(&ColorfulTheme::default())
+            .with_prompt(t!("preset-import-action"))
+            .items(&labels)
+            .default(0)
+            .interact_opt()
+    }))?;
+    Some(actions[selection].1)
+}
+
+/// `jenkins presets import <file> [--service <name>] [--on-conflict skip|overwrite|rename]`
+pub fn presets_import(matches: &ArgMatches, jenkins_configs: &[JenkinsConfig]) -> i32 {
+    let path = matches.get_one::<String>("file").expect("required argument");
+    let target = match find_service(matches, jenkins_configs) {
+        Ok(target) => target,
+        Err(e) => {
+            eprintln!("{}", e.to_string().red());
+            return 1;
+        }
+    };
+    let export = match preset_share::load_export(Path::new(path)) {
+        Ok(export) => export,
+        Err(e) => {
+            eprintln!("{}", format!("{:#}", e).red());
+            return 1;
+        }
+    };
+    let mut store = match PresetStore::new() {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("{}", t!("preset-load-failed", "error" => e.to_string()).red());
+            return 1;
+        }
+    };
+    let conflict_action = matches
+        .get_one::<String>("on_conflict")
+        .map(|action| action.parse::<ConflictAction>().expect("validated by clap"));
+
+    // The prompt reads the store while the import changes it, so compare against a copy
+    let local_store = PresetStore {
+        jobs: store.jobs.clone(),
+        ..Default::default()
+    };
+    let result = preset_share::import_presets(&mut store, &export, jenkins_configs, target, |identity, preset| {
+        conflict_action.or_else(|| select_conflict_action(&local_store, identity, preset))
+    });
+    let summary = match result {
+        Ok(Some(summary)) => summary,
+        Ok(None) => {
+            eprintln!("{}", t!("preset-import-aborted").yellow());
+            return 1;
+        }
+        Err(e) => {
+            eprintln!("{}", e.to_string().red());
+            return 1;
+        }
+    };
+    if let Err(e) = store.save_presets() {
+        eprintln!("{}", t!("preset-save-failed", "error" => e.to_string()).red());
+        return 1;
+    }
+    println!(
+        "{}",
+        t!(
+            "presets-imported",
+            "added" => summary.added.to_string(),
+            "overwritten" => summary.overwritten.to_string(),
+            "renamed" => summary.renamed.to_string(),
+            "skipped" => summary.skipped.to_string(),
+            "unchanged" => summary.unchanged.to_string()
+        )
+        .green()
+    );
+    0
+}
+
+/// Resolve the parameters of manifest jobs and print them.
+/// Returns `None` (after printing the error) if a job or preset cannot be resolved.
+async fn prepare_jobs(
+    clients: &mut ServiceClients,
+    preset_store: &PresetStore,
+    jobs: &[BatchJob],
+    shared_params: &HashMap<String, toml::Value>,
+) -> Option<Vec<PreparedJob>> {
+    let mut prepared = Vec::new();
+    for job in jobs {
+        let job_url = format_url(&job.url);
+        let (service, client, shared_client) = clients.for_job(&job_url);
+        let project = match client.get_project(&job_url).await {
+            Ok(project) => project,
+            Err(e) => {
+                eprintln!("{}: {} ({})", t!("get-project-failed"), e, job_url);
+                return None;
+            }
+        };
+        let definitions = match client.get_job_parameters(&job_url).await {
+            Ok(definitions) => definitions,
+            Err(e) => {
+                eprintln!("{}: {} ({})", t!("get-job-parameters-failed"), e, job_url);
+                return None;
+            }
+        };
+        let base_params = match job.preset.as_deref() {
+            Some(preset_name) => {
+                let identity = JobPresetIdentity {
+                    service_url: client.base_url.clone(),
+                    job_url: job_url.clone(),
+                    job_name: project.name.clone(),
+                    display_name: Some(project.display_name.clone()),
+                };
+                match preset_store.find_preset(&identity, preset_name) {
+                    Some(preset) => presets::merge_preset_parameters(&preset, &definitions),
+                    None => {
+                        eprintln!("{} ({})", t!("preset-not-found", "name" => preset_name), job_url);
+                        return None;
+                    }
+                }
+            }
+            None => History::merge_parameters(&HistoryEntry::default(), &definitions),
+        };
+        let (params, unknown) = batch::apply_manifest_params(base_params, &definitions, shared_params, &job.params);
+        let params = match template::render_dynamic_params(&params, template::prompt_param_value) {
+            Ok(Some(params)) => params,
+            Ok(None) => return None, // Ctrl+C pressed
+            Err(e) => {
+                eprintln!(
+                    "{} ({})",
+                    t!("dynamic-value-failed", "error" => e.to_string()).red(),
+                    job_url
+                );
+                return None;
+            }
+        };
+        let validator = ParamValidator::new(validation::resolve_rules(
+            &service,
+            &project.name,
+            &project.display_name,
+        ));
+        // Values filled from captures are checked when their stage runs
+        let violations: Vec<_> = validator
+            .check_all(&params)
+            .into_iter()
+            .filter(|(name, _)| {
+                !params
+                    .get(name)
+                    .is_some_and(|param| template::has_captures(&param.value))
+            })
+            .collect();
+        if !violations.is_empty() {
+            eprintln!("{} ({})", t!("params-invalid").red(), job_url);
+            validation::print_violations(&violations);
+            return None;
+        }
+
+        let protected = policy::is_protected(&service, &project.name, &project.display_name);
+        if protected {
+            println!("{}", t!("protected-label").red().bold());
+        }
+        println!("{} {}", project.display_name.bold(), job_url.underline().blue());
+        presets::print_params(&params);
+        if !unknown.is_empty() {
+            println!("{}", t!("batch-unknown-params", "names" => unknown.join(", ")).yellow());
+        }
+        println!();
+        prepared.push(PreparedJob {
+            name: project.display_name,
+            job_url,
+            service,
+            client: shared_client,
+            params,
+            validator,
+            captures: job.captures.clone(),
+            job_name: project.name.clone(),
+            protected: protected.then_some(project.name),
+        });
+    }
+    Some(prepared)
+}
+
+/// Ask before triggering unless `--yes` is given.
+/// Protected jobs also need their names typed, or `--yes-i-mean-prod` together with `--yes`.
+fn confirm_trigger(matches: &ArgMatches, prompt_text: String, protected_jobs: &[&str]) -> bool {
+    if matches.get_flag("yes") {
+        if protected_jobs.is_empty() || matches.get_flag("yes_i_mean_prod") {
+            return true;
+        }
+        eprintln!(
+            "{}",
+            t!("protected-requires-flag", "name" => protected_jobs.join(", ")).red()
+        );
+        return false;
+    }
+    let confirmed = prompt::handle_confirm_opt(prompt::with_prompt_kind(prompt::PromptKind::Confirm, || {
+        Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt_text.clone())
+            .default(true)
+            .show_default(true)
+            .wait_for_newline(false)
+            .interact_opt()
+    }));
+    if confirmed != Some(true) {
+        return false;
+    }
+    protected_jobs.iter().all(|job_name| policy::confirm_job_name(job_name))
+}
+
+/// `jenkins batch <manifest.toml> [--yes]`
+pub async fn batch(matches: &ArgMatches, manifest: &batch::BatchManifest) -> i32 {
+    let Some(mut clients) = ServiceClients::load().await else {
+        return 1;
+    };
+    let Some(preset_store) = preset_store().await else {
+        return 1;
+    };
+
+    // Resolve all parameters before triggering anything
+    let Some(jobs) = prepare_jobs(&mut clients, &preset_store, &manifest.jobs, &manifest.params).await else {
+        return 1;
+    };
+    if !check_freeze(clients.global_config.as_ref(), &jobs.iter().collect::<Vec<_>>()) {
+        return 1;
+    }
+    let protected_jobs: Vec<&str> = jobs.iter().filter_map(|job| job.protected.as_deref()).collect();
+    if !confirm_trigger(
+        matches,
+        t!("batch-confirm", "count" => jobs.len().to_string()),
+        &protected_jobs,
+    ) {
+        return 1;
+    }
+
+    let job_clients: Vec<Arc<JenkinsClient>> = jobs.iter().map(|job| Arc::clone(&job.client)).collect();
+    let outcomes = batch::run_batch(jobs).await;
+    batch::print_batch_summary(&job_clients, &outcomes).await;
+    if outcomes.iter().all(|outcome| outcome.is_success()) {
+        0
+    } else {
+        1
+    }
+}
+
+/// `jenkins run <pipeline.toml> [--yes]`
+pub async fn run(matches: &ArgMatches, manifest: &pipeline::PipelineManifest) -> i32 {
+    let Some(mut clients) = ServiceClients::load().await else {
+        return 1;
+    };
+    let Some(preset_store) = preset_store().await else {
+        return 1;
+    };
+
+    // Resolve the jobs of every stage before running the first one
+    let mut stages = Vec::new();
+    for stage in &manifest.stages {
+        println!("{}", format!("[{}]", stage.name).bold().cyan());
+        let Some(jobs) = prepare_jobs(&mut clients, &preset_store, &stage.jobs, &manifest.params).await else {
+            return 1;
+        };
+        stages.push(PreparedStage {
+            name: stage.name.clone(),
+            when: stage.when,
+            jobs,
+        });
+    }
+    let all_jobs: Vec<&PreparedJob> = stages.iter().flat_map(|stage| &stage.jobs).collect();
+    if !check_freeze(clients.global_config.as_ref(), &all_jobs) {
+        return 1;
+    }
+    let protected_jobs: Vec<&str> = all_jobs.iter().filter_map(|job| job.protected.as_deref()).collect();
+    if !confirm_trigger(
+        matches,
+        t!("pipeline-confirm", "count" => stages.len().to_string()),
+        &protected_jobs,
+    ) {
+        return 1;
+    }
+
+    let reports = pipeline::run_pipeline(stages).await;
+    pipeline::print_pipeline_summary(manifest.name.as_deref(), &reports);
+    // Skipped stages are expected (e.g. `when = "failure"` after a green run)
+    if reports
+        .iter()
+        .all(|report| matches!(report.status, StageStatus::Success | StageStatus::Skipped))
+    {
+        0
+    } else {
+        1
+    }
+}
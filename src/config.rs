@@ -12,7 +12,7 @@ use toml_edit::{value, DocumentMut};
 
 use crate::i18n::macros::t;
 use crate::i18n::I18n;
-use crate::migrations::migrate_config_yaml_to_toml;
+use crate::migrations::{migrate_config, migrate_config_yaml_to_toml, NewerVersionError};
 use crate::models::{Config, GlobalConfig, JenkinsConfig};
 use crate::prompt;
 
@@ -41,9 +41,8 @@ pub static DATA_DIR: Lazy<PathBuf> = Lazy::new(|| {
     data_dir
 });
 
-pub async fn initialize_config(matches: &clap::ArgMatches) -> Result<(GlobalConfig, bool)> {
-    let _ = DATA_DIR.as_path(); // auto create data dir
-
+/// Read the `[config]` and `[[jenkins]]` sections of the config file and apply the global settings.
+pub fn load_file_config() -> (GlobalConfig, Vec<JenkinsConfig>) {
     let file_config = load_config().expect(&t!("load-config-failed"));
     let global_config = file_config["config"]
         .as_object()
@@ -53,15 +52,24 @@ pub async fn initialize_config(matches: &clap::ArgMatches) -> Result<(GlobalConf
         serde_json::from_value(file_config["jenkins"].clone()).unwrap_or_default();
 
     apply_global_settings(&global_config);
+    (global_config, jenkins_configs)
+}
+
+/// Load the config file and select the Jenkins service.
+/// `target_url` is a Jenkins URL given to a subcommand (e.g. a build URL); it is used like `--url` when
+/// `--url` is not set, so the matching service credentials apply.
+pub async fn initialize_config(matches: &clap::ArgMatches, target_url: Option<&str>) -> Result<(GlobalConfig, bool)> {
+    let _ = DATA_DIR.as_path(); // auto create data dir
+
+    let (global_config, jenkins_configs) = load_file_config();
 
     // println!("arg len: {}", std::env::args().len());
-    let url_arg = matches.get_one::<String>("url");
-    let cli_config = ["url", "user", "token", "cookie"]
+    let url_arg = matches.get_one::<String>("url").map(|url| url.as_str()).or(target_url);
+    let cli_config = ["user", "token", "cookie"]
         .iter()
         .fold(JenkinsConfig::default(), |mut config, &field| {
             if let Some(value) = matches.get_one::<String>(field) {
                 match field {
-                    "url" => config.url = value.to_string(),
                     "user" => config.user = value.to_string(),
                     "token" => config.token = value.to_string(),
                     "cookie" => config.cookie = value.to_string(),
@@ -70,6 +78,10 @@ pub async fn initialize_config(matches: &clap::ArgMatches) -> Result<(GlobalConf
             }
             config
         });
+    let cli_config = JenkinsConfig {
+        url: url_arg.unwrap_or_default().to_string(),
+        ..cli_config
+    };
 
     let has_valid_auth = |c: &JenkinsConfig| {
         let has_basic = !c.user.is_empty() && !c.token.is_empty();
@@ -100,13 +112,7 @@ pub async fn initialize_config(matches: &clap::ArgMatches) -> Result<(GlobalConf
                 config.jenkins = Some(if config.services.is_empty() {
                     cli_config.clone()
                 } else {
-                    let input_url = utils::simplify_url(url);
-                    let matched_config = config
-                        .services
-                        .iter()
-                        .find(|s| input_url == utils::simplify_url(&s.url))
-                        .cloned();
-                    match matched_config {
+                    match find_service_for_url(&config.services, url) {
                         Some(matched) => JenkinsConfig {
                             user: if cli_config.user.is_empty() {
                                 matched.user
@@ -123,6 +129,11 @@ pub async fn initialize_config(matches: &clap::ArgMatches) -> Result<(GlobalConf
                             } else {
                                 cli_config.cookie
                             },
+                            url: if utils::simplify_url(url) == utils::simplify_url(&matched.url) {
+                                matched.url.clone()
+                            } else {
+                                url.to_string() // job/build URL under a configured service
+                            },
                             ..matched
                         },
                         None => cli_config,
@@ -142,13 +153,39 @@ pub async fn initialize_config(matches: &clap::ArgMatches) -> Result<(GlobalConf
     Ok((global_config, service_step_enabled))
 }
 
+/// Find the configured service for a Jenkins URL.
+/// An exact match wins; otherwise the service with the longest URL prefix (e.g. for job or build URLs).
+pub fn find_service_for_url(services: &[JenkinsConfig], url: &str) -> Option<JenkinsConfig> {
+    let input_url = utils::simplify_url(url);
+    if let Some(matched) = services.iter().find(|s| input_url == utils::simplify_url(&s.url)) {
+        return Some(matched.clone());
+    }
+    services
+        .iter()
+        .filter(|s| {
+            let service_url = utils::simplify_url(&s.url);
+            !service_url.is_empty() && input_url.starts_with(&format!("{}/", service_url))
+        })
+        .max_by_key(|s| utils::simplify_url(&s.url).len())
+        .cloned()
+}
+
 pub async fn select_jenkins_service() -> Result<()> {
     let mut config = CONFIG.lock().await;
     let global_enable_history = config.global.as_ref().unwrap().enable_history.unwrap_or(true);
     let services = config.services.clone();
 
     let selected_config = if services.len() > 1 {
-        let service_names: Vec<String> = services.iter().map(|c| c.name.clone()).collect();
+        let service_names: Vec<String> = services
+            .iter()
+            .map(|c| {
+                if c.protected.unwrap_or(false) {
+                    format!("{} {}", c.name, t!("protected-label"))
+                } else {
+                    c.name.clone()
+                }
+            })
+            .collect();
         let selection =
             prompt::handle_selection_opt(prompt::with_prompt_kind(prompt::PromptKind::FuzzySelectVim, || {
                 FuzzySelect::with_theme(&ColorfulTheme::default())
@@ -253,12 +290,21 @@ fn load_config() -> Result<JsonValue, Box<dyn std::error::Error>> {
     let home_dir = home_dir().expect(&t!("get-home-dir-failed"));
     let config_path = home_dir.join(CONFIG_FILE);
     let _ = migrate_config_yaml_to_toml(&config_path);
+    if let Err(e) = migrate_config(&config_path) {
+        // A newer configuration is not downgraded, other failures fall back to the defaults below
+        if e.is::<NewerVersionError>() {
+            eprintln!("{}", e.to_string().red());
+            std::process::exit(1);
+        }
+    }
     let content = r#"[config]
 # locale = "en-US"
 # enable_history = true
 # check_update = true
 # timeout = 30
 # follow_downstream = false
+# check_git_push = false
+# history_runs = 10
 
 [[jenkins]]
 name = ""
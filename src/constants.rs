@@ -28,6 +28,15 @@ pub const DEFAULT_PARAM_VALUE: &str = "<DEFAULT>";
 /// Masked value for password fields
 pub const MASKED_PASSWORD: &str = "*******";
 
+/// Default parameter names (case-insensitive patterns) that hold a git branch
+pub const GIT_BRANCH_PARAM_NAMES: [&str; 2] = ["GIT_BRANCH", "gitBranch"];
+
+/// Default git remote of the branch picker and branch checks
+pub const DEFAULT_GIT_REMOTE: &str = "origin";
+
+/// Number of recent commits offered by the branch picker
+pub const RECENT_COMMITS_LIMIT: usize = 20;
+
 /// Jenkins job types that can be built manually
 pub const JENKINS_BUILDABLE_TYPES: [&str; 2] = [
     "hudson.model.FreeStyleProject",                  // Freestyle project
@@ -218,6 +218,12 @@ pub async fn handle_ctrl_c(mut ctrlc_rx: mpsc::UnboundedReceiver<()>) {
             continue;
         }
 
+        // dialoguer raises SIGINT for a read interrupted by `prompt::interrupt_prompt`
+        if prompt::take_prompt_interrupt() {
+            debug_ctrlc!("prompt interrupted by the program, not a key press");
+            continue;
+        }
+
         if CTRL_C.phase() == CtrlCPhase::Cancelling {
             force_exit();
         }
@@ -0,0 +1,142 @@
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Confirm};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use std::path::{Component, Path, PathBuf};
+
+use crate::i18n::macros::t;
+use crate::jenkins::client::JenkinsClient;
+use crate::{prompt, utils};
+
+/// `tree` filter for the build API
+pub const ARTIFACTS_TREE: &str = "artifacts[fileName,relativePath]";
+
+/// A file archived by a build.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct BuildArtifact {
+    #[serde(rename = "fileName", default)]
+    pub file_name: String,
+    #[serde(rename = "relativePath", default)]
+    pub relative_path: String, // e.g. target/app.jar
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ArtifactsApiResponse {
+    #[serde(default)]
+    artifacts: Vec<BuildArtifact>,
+}
+
+/// Parse the artifacts of a build from `/api/json?tree=artifacts[...]`.
+pub fn parse_build_artifacts(json_data: &JsonValue) -> Vec<BuildArtifact> {
+    let response: ArtifactsApiResponse = serde_json::from_value(json_data.clone()).unwrap_or_default();
+    response
+        .artifacts
+        .into_iter()
+        .filter(|artifact| !artifact.relative_path.is_empty())
+        .collect()
+}
+
+/// Filter artifacts by a glob pattern.
+/// Patterns without `/` match the file name, others match the relative path.
+pub fn filter_artifacts<'a>(artifacts: &'a [BuildArtifact], pattern: &str) -> Vec<&'a BuildArtifact> {
+    let re = utils::glob_to_regex(pattern);
+    let match_path = pattern.contains('/');
+    artifacts
+        .iter()
+        .filter(|artifact| {
+            if match_path {
+                re.is_match(&artifact.relative_path)
+            } else {
+                re.is_match(&artifact.file_name)
+            }
+        })
+        .collect()
+}
+
+/// Local path of an artifact under `dest`, keeping its relative directories.
+/// Returns `None` for paths escaping `dest` (e.g. `../x`).
+pub fn artifact_dest_path(dest: &Path, artifact: &BuildArtifact) -> Option<PathBuf> {
+    let relative = Path::new(&artifact.relative_path);
+    if relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return None;
+    }
+    Some(dest.join(relative))
+}
+
+pub fn print_artifacts(artifacts: &[BuildArtifact]) {
+    if artifacts.is_empty() {
+        println!("{}", t!("artifacts-none").yellow());
+        return;
+    }
+    println!("{} ({}):", t!("artifacts-title").bold(), artifacts.len());
+    for artifact in artifacts {
+        println!("  {}", artifact.relative_path);
+    }
+}
+
+/// Download artifacts into `dest`, showing a progress bar per file.
+/// Returns the number of downloaded files.
+pub async fn download_artifacts(
+    client: &JenkinsClient,
+    build_url: &str,
+    artifacts: &[&BuildArtifact],
+    dest: &Path,
+) -> Result<usize> {
+    let style = ProgressStyle::default_bar()
+        .template("{msg} [{bar:30.green}] {bytes}/{total_bytes} ({bytes_per_sec})")
+        .unwrap()
+        .progress_chars("=> ");
+    let mut downloaded = 0;
+    for artifact in artifacts {
+        let path = artifact_dest_path(dest, artifact)
+            .ok_or_else(|| anyhow!(t!("artifact-invalid-path", "path" => artifact.relative_path.clone())))?;
+        let progress = ProgressBar::new(0);
+        progress.set_style(style.clone());
+        progress.set_message(artifact.file_name.clone());
+        let result = client.download_artifact(build_url, artifact, &path, &progress).await;
+        progress.finish_and_clear();
+        result?;
+        println!("{} {}", "✓".green(), path.display());
+        downloaded += 1;
+    }
+    Ok(downloaded)
+}
+
+/// Offer to download the artifacts of a finished build.
+pub async fn prompt_download_artifacts(client: &JenkinsClient, build_url: &str) {
+    let artifacts = match client.get_build_artifacts(build_url).await {
+        Ok(artifacts) if !artifacts.is_empty() => artifacts,
+        Ok(_) => return,
+        Err(e) => {
+            eprintln!("{}", t!("get-artifacts-failed", "error" => e.to_string()));
+            return;
+        }
+    };
+    print_artifacts(&artifacts);
+
+    let confirmed = prompt::handle_confirm_opt(prompt::with_prompt_kind(prompt::PromptKind::Confirm, || {
+        Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(t!("artifacts-download-prompt", "count" => artifacts.len().to_string()))
+            .default(false)
+            .show_default(true)
+            .wait_for_newline(false)
+            .interact_opt()
+    }));
+    if confirmed != Some(true) {
+        return;
+    }
+    let Some(dest) = prompt::string_input(&t!("artifacts-dest-prompt"), ".", Some(true)) else {
+        return;
+    };
+
+    let selected: Vec<&BuildArtifact> = artifacts.iter().collect();
+    match download_artifacts(client, build_url, &selected, Path::new(&dest)).await {
+        Ok(count) => println!("{}", t!("artifacts-downloaded", "count" => count.to_string())),
+        Err(e) => eprintln!("{}", t!("download-artifacts-failed", "error" => e.to_string())),
+    }
+}
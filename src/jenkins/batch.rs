@@ -0,0 +1,309 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::find_service_for_url;
+use crate::constants::ParamType;
+use crate::i18n::macros::t;
+use crate::jenkins::{
+    builds, captures::CaptureSource, client::JenkinsClient, validation::ParamValidator, JenkinsJobParameter, ParamInfo,
+};
+use crate::models::JenkinsConfig;
+use crate::utils::{delay, format_url, simplify_url};
+
+/// Number of trailing log lines printed for each failed job.
+const FAILED_LOG_LINES: usize = 40;
+
+/// Interval between status checks of a batch job.
+const POLL_INTERVAL_MS: u64 = 2000;
+
+/// Jobs to trigger together, e.g.
+/// ```toml
+/// [params]            # shared by all jobs (ignored by jobs without the parameter)
+/// GIT_BRANCH = "release/1.2"
+///
+/// [[jobs]]
+/// url = "http://jenkins.example.com/job/api/"
+/// preset = "prod"     # optional
+///
+/// [[jobs]]
+/// url = "http://jenkins.example.com/job/web/"
+/// params = { REPLICAS = 3 }
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BatchManifest {
+    #[serde(default)]
+    pub params: HashMap<String, toml::Value>,
+    #[serde(default)]
+    pub jobs: Vec<BatchJob>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BatchJob {
+    pub url: String,
+    #[serde(default)]
+    pub preset: Option<String>,
+    #[serde(default)]
+    pub params: HashMap<String, toml::Value>,
+    /// Values read from the finished build for later `jenkins run` stages (`{{capture.NAME}}`)
+    #[serde(default)]
+    pub captures: BTreeMap<String, CaptureSource>,
+}
+
+/// Read a batch manifest file.
+pub fn load_manifest(path: &Path) -> Result<BatchManifest> {
+    let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let manifest: BatchManifest =
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))?;
+    if manifest.jobs.is_empty() {
+        anyhow::bail!("No [[jobs]] in {}", path.display());
+    }
+    Ok(manifest)
+}
+
+/// String value of a manifest parameter (`true`, `3`, `"main"` -> `main`).
+pub fn toml_value_to_string(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Apply manifest parameters on top of `base`.
+/// Shared parameters only apply to jobs that define them; job parameters missing
+/// from the job definitions are returned as unknown.
+pub fn apply_manifest_params(
+    mut base: HashMap<String, ParamInfo>,
+    definitions: &[JenkinsJobParameter],
+    shared: &HashMap<String, toml::Value>,
+    job_params: &HashMap<String, toml::Value>,
+) -> (HashMap<String, ParamInfo>, Vec<String>) {
+    let mut unknown = Vec::new();
+    for (params, report_unknown) in [(shared, false), (job_params, true)] {
+        for (name, value) in params {
+            match definitions.iter().find(|definition| &definition.name == name) {
+                Some(definition) => {
+                    base.insert(
+                        name.clone(),
+                        ParamInfo {
+                            value: toml_value_to_string(value),
+                            r#type: definition.param_type.clone().unwrap_or(ParamType::String),
+                        },
+                    );
+                }
+                None if report_unknown => unknown.push(name.clone()),
+                None => {}
+            }
+        }
+    }
+    unknown.sort();
+    (base, unknown)
+}
+
+/// The configured service of a manifest job URL: the one with the longest URL prefix.
+/// The selected service (which may carry command line credentials) replaces its configured entry
+/// and is used for URLs no service matches.
+pub fn service_for_job(selected: &JenkinsConfig, services: &[JenkinsConfig], job_url: &str) -> JenkinsConfig {
+    let selected_url = simplify_url(&JenkinsClient::service_base_url(&selected.url));
+    let mut candidates = vec![JenkinsConfig {
+        url: selected_url.clone(),
+        ..selected.clone()
+    }];
+    candidates.extend(
+        services
+            .iter()
+            .filter(|service| simplify_url(&service.url) != selected_url)
+            .cloned(),
+    );
+    match find_service_for_url(&candidates, job_url) {
+        Some(service) if simplify_url(&service.url) != selected_url => service,
+        _ => selected.clone(),
+    }
+}
+
+/// A job ready to be triggered.
+#[derive(Debug, Clone)]
+pub struct PreparedJob {
+    pub name: String,     // display name
+    pub job_name: String, // for per-job settings
+    pub job_url: String,
+    pub service: JenkinsConfig,     // the service of `job_url`
+    pub client: Arc<JenkinsClient>, // client of `service`
+    pub params: HashMap<String, ParamInfo>,
+    pub validator: ParamValidator, // rechecks values filled from captures
+    pub captures: BTreeMap<String, CaptureSource>,
+    pub protected: Option<String>, // job name to type when the job is protected
+}
+
+/// Final state of a batch job.
+#[derive(Debug, Clone)]
+pub struct BatchOutcome {
+    pub name: String,
+    pub build_url: Option<String>,
+    pub result: String, // SUCCESS, FAILURE, ... or ERROR when it could not be triggered/monitored
+    pub duration: Option<u64>, // milliseconds
+    pub error: Option<String>,
+}
+
+impl BatchOutcome {
+    pub fn new(name: &str) -> Self {
+        BatchOutcome {
+            name: name.to_string(),
+            build_url: None,
+            result: "ERROR".to_string(),
+            duration: None,
+            error: None,
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.result == "SUCCESS"
+    }
+}
+
+/// Trigger all jobs, then monitor them concurrently until they finish.
+/// Outcomes are returned in the order of `jobs`.
+pub async fn run_batch(jobs: Vec<PreparedJob>) -> Vec<BatchOutcome> {
+    let multi = MultiProgress::new();
+    let style = ProgressStyle::default_spinner()
+        .tick_strings(&["-", "\\", "|", "/", ""])
+        .template("{spinner:.green} {prefix:.bold} {msg}")
+        .unwrap();
+    let name_width = jobs.iter().map(|job| job.name.chars().count()).max().unwrap_or(0);
+
+    let mut tasks = Vec::new();
+    for job in jobs {
+        let bar = multi.add(ProgressBar::new_spinner());
+        bar.set_style(style.clone());
+        bar.set_prefix(format!("{:<width$}", job.name, width = name_width));
+        bar.enable_steady_tick(Duration::from_millis(100));
+        let name = job.name.clone();
+        tasks.push((name, tokio::spawn(async move { monitor_job(job, &bar).await })));
+    }
+    join_outcomes(tasks).await
+}
+
+/// Wait for the batch tasks, each with the name of its job. The tasks run concurrently;
+/// one that panicked or was cancelled yields an `ERROR` outcome for its job,
+/// so there is always one outcome per job, in job order.
+pub async fn join_outcomes(tasks: Vec<(String, tokio::task::JoinHandle<BatchOutcome>)>) -> Vec<BatchOutcome> {
+    let mut outcomes = Vec::new();
+    for (name, task) in tasks {
+        let outcome = match task.await {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                let mut outcome = BatchOutcome::new(&name);
+                outcome.error = Some(e.to_string());
+                outcome
+            }
+        };
+        outcomes.push(outcome);
+    }
+    outcomes
+}
+
+async fn monitor_job(job: PreparedJob, bar: &ProgressBar) -> BatchOutcome {
+    let mut outcome = BatchOutcome::new(&job.name);
+    if let Err(e) = wait_for_build(&job.client, &job, bar, &mut outcome).await {
+        outcome.error = Some(e.to_string());
+    }
+    let message = match outcome.result.as_str() {
+        "SUCCESS" => outcome.result.green().to_string(),
+        "UNSTABLE" | "ABORTED" => outcome.result.yellow().to_string(),
+        _ => outcome.result.red().to_string(),
+    };
+    let location = outcome.build_url.clone().or(outcome.error.clone()).unwrap_or_default();
+    bar.finish_with_message(format!("{} {}", message, location.dimmed()));
+    outcome
+}
+
+async fn wait_for_build(
+    client: &JenkinsClient,
+    job: &PreparedJob,
+    bar: &ProgressBar,
+    outcome: &mut BatchOutcome,
+) -> Result<()> {
+    bar.set_message(t!("batch-triggering"));
+    let queue_url = client.trigger_build(&job.job_url, job.params.clone()).await?;
+
+    bar.set_message(t!("polling-queue-item"));
+    let number = loop {
+        if let Some(number) = client.get_queue_executable(&queue_url).await? {
+            break number;
+        }
+        delay(POLL_INTERVAL_MS).await;
+    };
+    let build_url = format_url(&format!("{}/{}/", job.job_url, number));
+    outcome.build_url = Some(build_url.clone());
+
+    loop {
+        let build = client.get_build_by_url(&build_url).await?;
+        if !build.building && build.result.is_some() {
+            outcome.result = build.status().to_string();
+            outcome.duration = Some(build.duration);
+            return Ok(());
+        }
+        let elapsed = (crate::utils::current_timestamp() * 1000 - build.timestamp).max(0) as u64;
+        bar.set_message(format!(
+            "#{} {} {}",
+            number,
+            t!("batch-building"),
+            builds::format_duration(elapsed)
+        ));
+        delay(POLL_INTERVAL_MS).await;
+    }
+}
+
+/// Print the result table and the log tail of failed jobs.
+/// `clients` are the clients of the jobs, in the order of `outcomes`.
+pub async fn print_batch_summary(clients: &[Arc<JenkinsClient>], outcomes: &[BatchOutcome]) {
+    println!();
+    println!("{}", t!("batch-summary-title").bold());
+    for outcome in outcomes {
+        let mark = if outcome.is_success() {
+            "✓".green()
+        } else {
+            "✗".red()
+        };
+        println!(
+            "  {} {} {} {}",
+            mark,
+            outcome.name.bold(),
+            outcome.result,
+            outcome.build_url.as_deref().unwrap_or_default().dimmed()
+        );
+    }
+
+    for (client, outcome) in clients
+        .iter()
+        .zip(outcomes)
+        .filter(|(_, outcome)| !outcome.is_success())
+    {
+        println!();
+        println!("{}", format!("==> {} ({})", outcome.name, outcome.result).red().bold());
+        if let Some(error) = &outcome.error {
+            println!("{}", error);
+        }
+        let Some(build_url) = &outcome.build_url else {
+            continue;
+        };
+        match client.get_console_text(build_url).await {
+            Ok(log) => {
+                let lines: Vec<&str> = log.lines().collect();
+                for line in &lines[lines.len().saturating_sub(FAILED_LOG_LINES)..] {
+                    println!("{}", line);
+                }
+            }
+            Err(e) => eprintln!("{}", t!("get-build-log-failed", "error" => e.to_string())),
+        }
+        println!(
+            "Log URL: {}",
+            format_url(&format!("{}/consoleText", build_url)).underline().blue()
+        );
+    }
+}
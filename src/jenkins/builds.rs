@@ -0,0 +1,377 @@
+use chrono::{DateTime, Local};
+use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, FuzzySelect};
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+
+use crate::constants::{ParamType, DEFAULT_PARAM_VALUE};
+use crate::i18n::macros::t;
+use crate::jenkins::{
+    client::JenkinsClient,
+    history::{History, HistoryEntry},
+    presets, JenkinsJobParameter, ParamInfo,
+};
+use crate::prompt;
+
+/// Number of builds listed by the build history browser.
+pub const DEFAULT_BUILDS_LIMIT: usize = 20;
+
+/// Maximum length of the parameter summary in the build list.
+const MAX_PARAMS_DISPLAY_CHARS: usize = 60;
+
+/// `tree` fields of a build in the job API.
+pub const BUILD_TREE_FIELDS: &str = "number,url,result,building,duration,timestamp,\
+actions[causes[shortDescription,userId,userName],parameters[name,value]]";
+
+/// A build listed from the job's Jenkins build history.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BuildSummary {
+    pub number: u64,
+    pub url: String,
+    pub result: Option<String>, // SUCCESS, UNSTABLE, FAILURE, ABORTED, NOT_BUILT; None while building
+    pub building: bool,
+    pub duration: u64,  // milliseconds
+    pub timestamp: i64, // start time, milliseconds
+    pub triggered_by: Option<String>,
+    pub causes: Vec<String>,
+    pub parameters: Vec<(String, Option<String>)>, // in build order; password values are hidden by Jenkins
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct BuildsApiResponse {
+    #[serde(default)]
+    builds: Vec<BuildApi>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct BuildApi {
+    #[serde(default)]
+    number: u64,
+    #[serde(default)]
+    url: String,
+    result: Option<String>,
+    #[serde(default)]
+    building: bool,
+    #[serde(default)]
+    duration: u64,
+    #[serde(default)]
+    timestamp: i64,
+    #[serde(default)]
+    actions: Vec<Option<BuildActionApi>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct BuildActionApi {
+    #[serde(default)]
+    causes: Vec<BuildCauseApi>,
+    #[serde(default)]
+    parameters: Vec<BuildParameterApi>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct BuildCauseApi {
+    #[serde(rename = "shortDescription")]
+    short_description: Option<String>,
+    #[serde(rename = "userId")]
+    user_id: Option<String>,
+    #[serde(rename = "userName")]
+    user_name: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct BuildParameterApi {
+    #[serde(default)]
+    name: String,
+    value: Option<JsonValue>,
+}
+
+impl From<BuildApi> for BuildSummary {
+    fn from(build: BuildApi) -> Self {
+        let mut summary = BuildSummary {
+            number: build.number,
+            url: build.url,
+            result: build.result,
+            building: build.building,
+            duration: build.duration,
+            timestamp: build.timestamp,
+            ..Default::default()
+        };
+        for action in build.actions.into_iter().flatten() {
+            for cause in action.causes {
+                if summary.triggered_by.is_none() {
+                    summary.triggered_by = cause.user_name.or(cause.user_id);
+                }
+                if let Some(description) = cause.short_description {
+                    summary.causes.push(description);
+                }
+            }
+            for parameter in action.parameters {
+                let value = parameter.value.as_ref().and_then(super::json_value_to_string);
+                summary.parameters.push((parameter.name, value));
+            }
+        }
+        summary
+    }
+}
+
+/// Parse the builds of a job from `/api/json?tree=builds[...]`.
+pub fn parse_builds(json_data: &JsonValue) -> Vec<BuildSummary> {
+    let response: BuildsApiResponse = serde_json::from_value(json_data.clone()).unwrap_or_default();
+    response.builds.into_iter().map(BuildSummary::from).collect()
+}
+
+/// Parse a single build from `/<build>/api/json`.
+pub fn parse_build(json_data: &JsonValue) -> BuildSummary {
+    let build: BuildApi = serde_json::from_value(json_data.clone()).unwrap_or_default();
+    BuildSummary::from(build)
+}
+
+/// Format a duration in milliseconds, e.g. `1h 02m 03s`, `2m 05s`, `8s`.
+pub fn format_duration(ms: u64) -> String {
+    let secs = ms / 1000;
+    let (hours, minutes, seconds) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if hours > 0 {
+        format!("{}h {:02}m {:02}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+impl BuildSummary {
+    /// `SUCCESS`, `FAILURE`, ... or `BUILDING`
+    pub fn status(&self) -> &str {
+        if self.building {
+            "BUILDING"
+        } else {
+            self.result.as_deref().unwrap_or("UNKNOWN")
+        }
+    }
+
+    /// Build parameters typed by the current job definitions.
+    /// Hidden password values fall back to the Jenkins default.
+    pub fn param_infos(&self, definitions: &[JenkinsJobParameter]) -> HashMap<String, ParamInfo> {
+        let mut params = HashMap::new();
+        for (name, value) in &self.parameters {
+            let param_type = definitions
+                .iter()
+                .find(|definition| &definition.name == name)
+                .and_then(|definition| definition.param_type.clone())
+                .unwrap_or(ParamType::String);
+            let value = match value {
+                Some(value) => value.clone(),
+                None if param_type == ParamType::Password => DEFAULT_PARAM_VALUE.to_string(),
+                None => continue,
+            };
+            params.insert(
+                name.clone(),
+                ParamInfo {
+                    value,
+                    r#type: param_type,
+                },
+            );
+        }
+        params
+    }
+
+    /// One-line summary for the build list.
+    pub fn display_line(&self) -> String {
+        let started = DateTime::from_timestamp_millis(self.timestamp)
+            .map(|datetime| datetime.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        let mut line = format!(
+            "#{:<5} {:<9} {:>9}  {}",
+            self.number,
+            self.status(),
+            format_duration(self.duration),
+            started
+        );
+        if let Some(user) = &self.triggered_by {
+            line.push_str(&format!("  @{}", user));
+        } else if let Some(cause) = self.causes.first() {
+            line.push_str(&format!("  {}", cause));
+        }
+        let params: Vec<(&str, &str)> = self
+            .parameters
+            .iter()
+            .filter_map(|(name, value)| value.as_deref().map(|value| (name.as_str(), value)))
+            .collect();
+        if let Some(summary) = params_summary(&params) {
+            line.push_str(&format!("  {}", summary));
+        }
+        line
+    }
+}
+
+/// Truncated `[NAME=value, ...]` summary of parameters for lists, `None` without parameters.
+pub fn params_summary(params: &[(&str, &str)]) -> Option<String> {
+    if params.is_empty() {
+        return None;
+    }
+    let params = params
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value.replace('\n', " ")))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if params.chars().count() > MAX_PARAMS_DISPLAY_CHARS {
+        let truncated: String = params.chars().take(MAX_PARAMS_DISPLAY_CHARS).collect();
+        Some(format!("[{}...]", truncated))
+    } else {
+        Some(format!("[{}]", params))
+    }
+}
+
+/// Print the cause and parameters of a build.
+pub fn print_build_details(build: &BuildSummary, definitions: &[JenkinsJobParameter]) {
+    println!("{}", build.display_line());
+    for cause in &build.causes {
+        println!("  {}", cause.dimmed());
+    }
+    println!();
+    let params = build.param_infos(definitions);
+    if params.is_empty() {
+        println!("{}", t!("build-no-parameters").yellow());
+    } else {
+        presets::print_params(&params);
+    }
+}
+
+/// Parameters for rebuilding, merged with the current job definitions.
+pub fn rebuild_parameters(build: &BuildSummary, definitions: &[JenkinsJobParameter]) -> HashMap<String, ParamInfo> {
+    let history_entry = HistoryEntry {
+        params: Some(build.param_infos(definitions)),
+        ..Default::default()
+    };
+    History::merge_parameters(&history_entry, definitions)
+}
+
+/// Build parameters that no longer exist in the current job definitions.
+pub fn obsolete_parameters(build: &BuildSummary, definitions: &[JenkinsJobParameter]) -> Vec<String> {
+    build
+        .parameters
+        .iter()
+        .filter(|(name, _)| !definitions.iter().any(|definition| &definition.name == name))
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+/// Print the parameters a rebuild will use, warning about dropped parameters.
+pub fn print_rebuild_parameters(
+    build: &BuildSummary,
+    definitions: &[JenkinsJobParameter],
+    params: &HashMap<String, ParamInfo>,
+) {
+    println!(
+        "{}",
+        t!("rebuild-params-title", "number" => build.number.to_string()).bold()
+    );
+    println!("{}", build.display_line().dimmed());
+    println!();
+    presets::print_params(params);
+    let obsolete = obsolete_parameters(build, definitions);
+    if !obsolete.is_empty() {
+        println!(
+            "{}",
+            t!("rebuild-obsolete-params", "names" => obsolete.join(", ")).yellow()
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BuildAction {
+    ViewLog,
+    ViewParams,
+    Rebuild,
+    DiffDefaults,
+    Back,
+}
+
+/// Browse the job's build history.
+/// Returns the build to rebuild when a rebuild is chosen, `None` to go back.
+pub async fn browse_builds(
+    client: &JenkinsClient,
+    job_url: &str,
+    definitions: &[JenkinsJobParameter],
+) -> Option<BuildSummary> {
+    let builds = match client.get_builds(job_url, DEFAULT_BUILDS_LIMIT).await {
+        Ok(builds) => builds,
+        Err(e) => {
+            eprintln!("{}", t!("get-builds-failed", "error" => e.to_string()));
+            return None;
+        }
+    };
+    if builds.is_empty() {
+        println!("{}", t!("no-builds").yellow());
+        return None;
+    }
+
+    let items: Vec<String> = builds.iter().map(BuildSummary::display_line).collect();
+    loop {
+        let selection =
+            prompt::handle_selection_opt(prompt::with_prompt_kind(prompt::PromptKind::FuzzySelectVim, || {
+                FuzzySelect::with_theme(&ColorfulTheme::default())
+                    .with_prompt(t!("select-build"))
+                    .items(&items)
+                    .default(0)
+                    .vim_mode(true)
+                    .with_initial_text("")
+                    .interact_opt()
+            }))?;
+        let build = &builds[selection];
+
+        while let Some(action) = select_build_action(build) {
+            match action {
+                BuildAction::ViewLog => match client.get_console_text(&build.url).await {
+                    Ok(log) => println!("{}", log),
+                    Err(e) => eprintln!("{}", t!("get-build-log-failed", "error" => e.to_string())),
+                },
+                BuildAction::ViewParams => print_build_details(build, definitions),
+                BuildAction::Rebuild => return Some(build.clone()),
+                BuildAction::DiffDefaults => {
+                    let defaults = History::merge_parameters(&HistoryEntry::default(), definitions);
+                    let changes = presets::diff_params(&defaults, &build.param_infos(definitions));
+                    if changes.is_empty() {
+                        println!("{}", t!("build-params-match-defaults").green());
+                    } else {
+                        println!(
+                            "{}",
+                            t!("build-params-diff-title", "number" => build.number.to_string()).bold()
+                        );
+                        presets::print_param_diff(&changes);
+                    }
+                }
+                BuildAction::Back => break,
+            }
+        }
+    }
+}
+
+fn select_build_action(build: &BuildSummary) -> Option<BuildAction> {
+    let items = [
+        t!("build-action-view-log"),
+        t!("build-action-view-params"),
+        t!("build-action-rebuild"),
+        t!("build-action-diff-defaults"),
+        t!("manage-preset-back"),
+    ];
+    let actions = [
+        BuildAction::ViewLog,
+        BuildAction::ViewParams,
+        BuildAction::Rebuild,
+        BuildAction::DiffDefaults,
+        BuildAction::Back,
+    ];
+    let selection = prompt::handle_selection_opt(prompt::with_prompt_kind(prompt::PromptKind::FuzzySelectVim, || {
+        FuzzySelect::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("#{}", build.number))
+            .items(&items)
+            .default(0)
+            .vim_mode(true)
+            .with_initial_text("")
+            .interact_opt()
+    }));
+
+    selection.map(|idx| actions[idx])
+}
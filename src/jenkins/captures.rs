@@ -0,0 +1,97 @@
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::jenkins::client::JenkinsClient;
+
+/// Where a value is read from once a build finishes, e.g.
+/// ```toml
+/// captures = { IMAGE_TAG = { log = "pushed image: \\S+:(\\S+)" }, VERSION = { artifact = "dist/version.txt" } }
+/// captures = { COMMIT = { env = "GIT_COMMIT" }, NOTE = "description" }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptureSource {
+    /// Regex over the console text: the first group, or the whole match without groups.
+    Log(String),
+    /// The build description.
+    Description,
+    /// A variable from `/injectedEnvVars` (EnvInject plugin).
+    Env(String),
+    /// The trimmed content of an archived artifact (relative path).
+    Artifact(String),
+}
+
+/// Value of the first match of `pattern` in `text`.
+pub fn capture_from_text(text: &str, pattern: &str) -> Result<Option<String>> {
+    let re = Regex::new(pattern).map_err(|e| anyhow!("Invalid capture regex {}: {}", pattern, e))?;
+    Ok(re.captures(text).map(|captures| {
+        captures
+            .get(1)
+            .or_else(|| captures.get(0))
+            .map(|matched| matched.as_str().trim().to_string())
+            .unwrap_or_default()
+    }))
+}
+
+/// Parse the variables of `/injectedEnvVars/api/json`.
+pub fn parse_injected_env_vars(json_data: &JsonValue) -> HashMap<String, String> {
+    json_data
+        .get("envMap")
+        .and_then(JsonValue::as_object)
+        .map(|env_map| {
+            env_map
+                .iter()
+                .filter_map(|(name, value)| value.as_str().map(|value| (name.clone(), value.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Read the captured values of a finished build.
+/// Each source is fetched at most once, however many values use it.
+pub async fn collect_captures(
+    client: &JenkinsClient,
+    build_url: &str,
+    captures: &BTreeMap<String, CaptureSource>,
+) -> Result<HashMap<String, String>> {
+    let mut console_text: Option<String> = None;
+    let mut env_vars: Option<HashMap<String, String>> = None;
+    let mut description: Option<Option<String>> = None;
+    let mut values = HashMap::new();
+
+    for (name, source) in captures {
+        let value = match source {
+            CaptureSource::Log(pattern) => {
+                if console_text.is_none() {
+                    console_text = Some(client.get_console_text(build_url).await?);
+                }
+                capture_from_text(console_text.as_deref().unwrap_or_default(), pattern)?
+            }
+            CaptureSource::Description => {
+                if description.is_none() {
+                    description = Some(client.get_build_description(build_url).await?);
+                }
+                description.clone().flatten()
+            }
+            CaptureSource::Env(variable) => {
+                if env_vars.is_none() {
+                    env_vars = Some(client.get_injected_env_vars(build_url).await?);
+                }
+                env_vars.as_ref().and_then(|env_vars| env_vars.get(variable).cloned())
+            }
+            CaptureSource::Artifact(relative_path) => Some(
+                client
+                    .get_artifact_text(build_url, relative_path)
+                    .await?
+                    .trim()
+                    .to_string(),
+            ),
+        };
+        let value = value.ok_or_else(|| anyhow!("Captured value {} not found in {}", name, build_url))?;
+        values.insert(name.clone(), value);
+    }
+    Ok(values)
+}
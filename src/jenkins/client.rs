@@ -22,22 +22,33 @@ use crate::constants::{
 use crate::i18n::macros::t;
 use crate::prompt;
 use crate::{
-    jenkins::{self, cookie::CookieStore, Event, JenkinsJob, JenkinsJobParameter, JenkinsResponse, ParamInfo},
-    models::CookieRefreshConfig,
-    spinner, terminal,
-    utils::{
-        clear_screen, delay, finish_terminal_line, format_url, get_current_branch, get_git_branches,
-        reset_terminal_line,
+    jenkins::{
+        self,
+        artifacts::{self, BuildArtifact},
+        builds::{self, BuildSummary},
+        captures,
+        cookie::CookieStore,
+        git_check, notify,
+        test_report::{self, TestReport},
+        validation::ParamValidator,
+        Event, JenkinsJob, JenkinsJobParameter, JenkinsResponse, ParamInfo,
     },
+    models::{BranchConfig, CookieRefreshConfig, GlobalConfig, JenkinsConfig, NotifyConfig},
+    spinner, terminal,
+    utils::{clear_screen, delay, finish_terminal_line, format_url, get_current_branch, reset_terminal_line},
 };
 
 /// Configuration for the Jenkins client.
+/// Fields are added over time: start from `ClientConfig::default()` and set the ones needed.
 #[derive(Debug, Clone, Default)]
+#[non_exhaustive]
 pub struct ClientConfig {
     /// HTTP request timeout in seconds (default: 30).
     pub timeout: Option<u64>,
     /// Follow detected downstream builds.
     pub follow_downstream: bool,
+    /// Notifications when a polled build finishes.
+    pub notify: Option<NotifyConfig>,
     // example:
     // pub max_retries: Option<u32>,
     // pub proxy: Option<String>,
@@ -81,10 +92,20 @@ pub struct JenkinsClient {
     cookie_refresh_attempted: AtomicBool,
     client: reqwest::Client,
     follow_downstream: bool,
+    notify: Option<NotifyConfig>,
     // shared states
     pub job_url: Option<String>, // e.g. http://jenkins_url/job/job_name
 }
 
+impl std::fmt::Debug for JenkinsClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JenkinsClient")
+            .field("base_url", &self.base_url)
+            .field("job_url", &self.job_url)
+            .finish_non_exhaustive()
+    }
+}
+
 impl JenkinsClient {
     /// Builds the headers for the request.
     ///
@@ -581,6 +602,7 @@ impl JenkinsClient {
         let config = config.unwrap_or_default();
         let timeout_secs = config.timeout.unwrap_or(30);
         let follow_downstream = config.follow_downstream;
+        let notify = config.notify;
 
         // println!("Authorization: {}", authorization);
         // std::env::set_var("NO_PROXY", "jenkins.example.com,other.example.com"); // Bypass proxy
@@ -600,10 +622,46 @@ impl JenkinsClient {
             cookie_refresh_attempted: AtomicBool::new(false),
             client,
             follow_downstream,
+            notify,
             job_url: None,
         }
     }
 
+    /// Creates a client for a configured Jenkins service.
+    ///
+    /// # Arguments
+    ///
+    /// * `jenkins_config` - The service; its URL may point to a job or build.
+    /// * `global_config` - Global options such as the request timeout.
+    pub fn from_service(jenkins_config: &JenkinsConfig, global_config: Option<&GlobalConfig>) -> Self {
+        let auth = if jenkins_config.user.is_empty() || jenkins_config.token.is_empty() {
+            None
+        } else {
+            Some(format!("{}:{}", jenkins_config.user, jenkins_config.token))
+        };
+        let client_config = global_config.map(|g| ClientConfig {
+            timeout: g.timeout,
+            follow_downstream: g.follow_downstream.unwrap_or(false),
+            notify: g.notify.clone(),
+        });
+        Self::new(
+            &Self::service_base_url(&jenkins_config.url),
+            auth.as_deref(),
+            if jenkins_config.cookie.is_empty() {
+                None
+            } else {
+                Some(jenkins_config.cookie.as_str())
+            },
+            jenkins_config.cookie_refresh.clone(),
+            client_config,
+        )
+    }
+
+    /// Strip the job path from a Jenkins URL, e.g. http://jenkins_url/job/a/42/ -> http://jenkins_url
+    pub fn service_base_url(url: &str) -> String {
+        url.split("/job/").next().unwrap_or(url).to_string()
+    }
+
     /// Retrieves the list of projects from the Jenkins server.
     ///
     /// # Returns
@@ -741,17 +799,20 @@ impl JenkinsClient {
     /// # Arguments
     ///
     /// * `parameter_definitions` - The parameter definitions.
+    /// * `branch_config` - Which parameters get the branch picker, and its sources.
+    /// * `validator` - Validation rules; invalid values are prompted again.
     ///
     /// # Returns
     ///
     /// `Some(HashMap)` with parameters, or `None` if user pressed Ctrl+C to go back
     pub async fn prompt_job_parameters(
         parameter_definitions: Vec<JenkinsJobParameter>,
+        branch_config: &BranchConfig,
+        validator: &ParamValidator,
     ) -> Option<HashMap<String, ParamInfo>> {
         use dialoguer::theme::ColorfulTheme; // ColorfulTheme/SimpleTheme
         let mut parameters = HashMap::new();
-        let branches = get_git_branches();
-        let branch_names = ["GIT_BRANCH", "gitBranch"];
+        let branches = git_check::branch_options(branch_config);
 
         for param in parameter_definitions {
             let JenkinsJobParameter {
@@ -763,7 +824,7 @@ impl JenkinsClient {
                 trim,
                 ..
             } = param;
-            let default_value = default_value.unwrap_or_else(|| "".to_string());
+            let mut default_value = default_value.unwrap_or_else(|| "".to_string());
             let fmt_name = format!("'{}'", name.bold().yellow());
             let fmt_desc = description
                 .as_ref()
@@ -771,107 +832,121 @@ impl JenkinsClient {
             // let fmt_choices = choices.as_ref().map_or("".to_string(), |c| {
             //     format!(" [可选值: {}]", c.join(", ").bold().green())
             // });
-            let (final_value, param_type) = if let Some(choices) = choices {
-                let default_selection = Self::default_choice_selection(&choices, &default_value);
-                // Use Select to display the Choice list
-                let selection =
-                    prompt::handle_selection_opt(prompt::with_prompt_kind(prompt::PromptKind::FuzzySelect, || {
-                        dialoguer::FuzzySelect::with_theme(&ColorfulTheme::default())
-                            .with_prompt(format!("{}{}", t!("prompt-select", "name" => &fmt_name), fmt_desc))
-                            .items(&choices)
-                            .default(default_selection)
-                            .interact_opt()
-                    }));
-
-                match selection {
-                    Some(idx) => (choices[idx].clone(), ParamType::Choice),
-                    None => return None, // Ctrl+C pressed - go back
-                }
-            } else if param_type == Some(ParamType::Boolean) {
-                let default_bool = default_value.parse::<bool>().unwrap_or(false);
-                let value = prompt::handle_confirm_opt(prompt::with_prompt_kind(prompt::PromptKind::Confirm, || {
-                    dialoguer::Confirm::with_theme(&ColorfulTheme::default())
-                        .with_prompt(format!("{}{}", t!("prompt-confirm", "name" => fmt_name), fmt_desc))
-                        .default(default_bool)
-                        .show_default(true)
-                        .wait_for_newline(false)
-                        .interact_opt()
-                }));
-
-                match value {
-                    Some(v) => (v.to_string(), ParamType::Boolean),
-                    None => return None, // Ctrl+C pressed - go back
-                }
-            } else if param_type == Some(ParamType::Password) {
-                let prompt_text = format!("{}{}", t!("prompt-password", "name" => fmt_name), fmt_desc);
-                match prompt::password_input(&prompt_text, &default_value) {
-                    Some(pwd) if pwd.is_empty() => (default_value.to_string(), ParamType::Password),
-                    Some(pwd) => (pwd, ParamType::Password),
-                    None => return None, // Ctrl+C pressed - go back
-                }
-            } else if param_type == Some(ParamType::Text) {
-                let prompt_text = format!("{}{}", t!("prompt-text", "name" => fmt_name), fmt_desc);
-                match prompt::text_input(&prompt_text, &default_value) {
-                    Some(v) => (v, ParamType::Text),
-                    None => return None, // Ctrl+C pressed - go back
-                }
-            } else if !branches.is_empty()
-                && branch_names
-                    .iter()
-                    .any(|&b| name.to_lowercase().contains(&b.to_lowercase()))
-            {
-                // If the parameter name contains GIT_BRANCH
-                let current_branch = get_current_branch();
-                let manual_input = t!("manual-input");
-                let branch_options = Self::build_branch_options(BranchOptionsInput {
-                    branches: &branches,
-                    default_branch: Some(&default_value),
-                    current_branch: Some(&current_branch),
-                    manual_input: &manual_input,
-                });
-
-                // Priority: default_value, then current_branch, finally use 0
-                let default_selection = branch_options
-                    .iter()
-                    .position(|b| b == &default_value)
-                    .or_else(|| branch_options.iter().position(|b| b == &current_branch))
-                    .unwrap_or(0);
-                let custom_theme = ColorfulTheme {
-                    // active_item_style: console::Style::new(), // Cancel default style
-                    ..ColorfulTheme::default()
+            // Re-prompt until the value passes the job's validation rules
+            let (final_value, param_type) = loop {
+                let (value, value_type) = if let Some(choices) = &choices {
+                    let default_selection = Self::default_choice_selection(choices, &default_value);
+                    // Use Select to display the Choice list
+                    let selection =
+                        prompt::handle_selection_opt(prompt::with_prompt_kind(prompt::PromptKind::FuzzySelect, || {
+                            dialoguer::FuzzySelect::with_theme(&ColorfulTheme::default())
+                                .with_prompt(format!("{}{}", t!("prompt-select", "name" => &fmt_name), fmt_desc))
+                                .items(choices)
+                                .default(default_selection)
+                                .interact_opt()
+                        }));
+
+                    match selection {
+                        Some(idx) => (choices[idx].clone(), ParamType::Choice),
+                        None => return None, // Ctrl+C pressed - go back
+                    }
+                } else if param_type == Some(ParamType::Boolean) {
+                    let default_bool = default_value.parse::<bool>().unwrap_or(false);
+                    let value =
+                        prompt::handle_confirm_opt(prompt::with_prompt_kind(prompt::PromptKind::Confirm, || {
+                            dialoguer::Confirm::with_theme(&ColorfulTheme::default())
+                                .with_prompt(format!("{}{}", t!("prompt-confirm", "name" => &fmt_name), fmt_desc))
+                                .default(default_bool)
+                                .show_default(true)
+                                .wait_for_newline(false)
+                                .interact_opt()
+                        }));
+
+                    match value {
+                        Some(v) => (v.to_string(), ParamType::Boolean),
+                        None => return None, // Ctrl+C pressed - go back
+                    }
+                } else if param_type == Some(ParamType::Password) {
+                    let prompt_text = format!("{}{}", t!("prompt-password", "name" => &fmt_name), fmt_desc);
+                    match prompt::password_input(&prompt_text, &default_value) {
+                        Some(pwd) if pwd.is_empty() => (default_value.to_string(), ParamType::Password),
+                        Some(pwd) => (pwd, ParamType::Password),
+                        None => return None, // Ctrl+C pressed - go back
+                    }
+                } else if param_type == Some(ParamType::Text) {
+                    let prompt_text = format!("{}{}", t!("prompt-text", "name" => &fmt_name), fmt_desc);
+                    match prompt::text_input(&prompt_text, &default_value) {
+                        Some(v) => (v, ParamType::Text),
+                        None => return None, // Ctrl+C pressed - go back
+                    }
+                } else if !branches.is_empty() && branch_config.is_branch_param(&name) {
+                    // If the parameter name matches a branch parameter (GIT_BRANCH by default)
+                    let current_branch = get_current_branch();
+                    let manual_input = t!("manual-input");
+                    let branch_options = Self::build_branch_options(BranchOptionsInput {
+                        branches: &branches,
+                        default_branch: Some(&default_value),
+                        current_branch: Some(&current_branch),
+                        manual_input: &manual_input,
+                    });
+
+                    // Priority: default_value, then current_branch, finally use 0
+                    let default_selection = branch_options
+                        .iter()
+                        .position(|b| b == &default_value)
+                        .or_else(|| branch_options.iter().position(|b| b == &current_branch))
+                        .unwrap_or(0);
+                    let custom_theme = ColorfulTheme {
+                        // active_item_style: console::Style::new(), // Cancel default style
+                        ..ColorfulTheme::default()
+                    };
+                    let selected_idx = prompt::handle_selection_opt(prompt::with_prompt_kind(
+                        prompt::PromptKind::FuzzySelectVim,
+                        || {
+                            dialoguer::FuzzySelect::with_theme(&custom_theme)
+                                .with_prompt(format!(
+                                    "{}{}",
+                                    t!("prompt-select-branch", "name" => &fmt_name),
+                                    fmt_desc
+                                ))
+                                .items(&branch_options)
+                                .default(default_selection)
+                                .vim_mode(true) // Esc, j|k
+                                .with_initial_text("")
+                                .interact_opt()
+                        },
+                    ));
+
+                    match selected_idx {
+                        Some(idx) if branch_options[idx] == manual_input => {
+                            let prompt_text = format!("{}{}", t!("prompt-input", "name" => &fmt_name), fmt_desc);
+                            match prompt::string_input(&prompt_text, "", trim) {
+                                Some(v) => (v, ParamType::String),
+                                None => return None, // Ctrl+C in manual input
+                            }
+                        }
+                        Some(idx) => (
+                            git_check::option_value(&branch_options[idx]).to_string(),
+                            ParamType::String,
+                        ),
+                        None => return None, // Ctrl+C pressed - go back
+                    }
+                } else {
+                    // For other types, use text input
+                    let prompt_text = format!("{}{}", t!("prompt-input", "name" => &fmt_name), fmt_desc);
+                    match prompt::string_input(&prompt_text, &default_value, trim) {
+                        Some(v) => (v, param_type.clone().unwrap_or(ParamType::String)),
+                        None => return None, // Ctrl+C pressed
+                    }
                 };
-                let selected_idx =
-                    prompt::handle_selection_opt(prompt::with_prompt_kind(prompt::PromptKind::FuzzySelectVim, || {
-                        dialoguer::FuzzySelect::with_theme(&custom_theme)
-                            .with_prompt(format!(
-                                "{}{}",
-                                t!("prompt-select-branch", "name" => &fmt_name),
-                                fmt_desc
-                            ))
-                            .items(&branch_options)
-                            .default(default_selection)
-                            .vim_mode(true) // Esc, j|k
-                            .with_initial_text("")
-                            .interact_opt()
-                    }));
-
-                match selected_idx {
-                    Some(idx) if branch_options[idx] == manual_input => {
-                        let prompt_text = format!("{}{}", t!("prompt-input", "name" => fmt_name), fmt_desc);
-                        match prompt::string_input(&prompt_text, "", trim) {
-                            Some(v) => (v, ParamType::String),
-                            None => return None, // Ctrl+C in manual input
+                match validator.check(&name, &value) {
+                    Ok(()) => break (value, value_type),
+                    Err(violation) => {
+                        eprintln!("{}: {}", name.bold(), violation.to_string().red());
+                        if value_type != ParamType::Password {
+                            default_value = value; // keep the input for correction
                         }
                     }
-                    Some(idx) => (branch_options[idx].clone(), ParamType::String),
-                    None => return None, // Ctrl+C pressed - go back
-                }
-            } else {
-                // For other types, use text input
-                let prompt_text = format!("{}{}", t!("prompt-input", "name" => fmt_name), fmt_desc);
-                match prompt::string_input(&prompt_text, &default_value, trim) {
-                    Some(v) => (v, param_type.unwrap_or(ParamType::String)),
-                    None => return None, // Ctrl+C pressed
                 }
             };
 
@@ -886,24 +961,17 @@ impl JenkinsClient {
         Some(parameters)
     }
 
-    /// Triggers a build for a specific job on the Jenkins server.
-    ///
-    /// # Arguments
-    /// * `job_url` - The URL of the job.
-    /// * `parameters` - The parameters to pass to the job.
-    ///
-    /// # Returns
-    /// A `Result` containing the queue_location or an `anyhow::Error` if the request fails.
-    pub async fn trigger_build(
-        &self,
+    /// URL and form fields of the request triggering a build.
+    /// Parameters left at the Jenkins default are not sent.
+    pub fn trigger_request(
         job_url: &str,
-        parameters: HashMap<String, ParamInfo>,
-    ) -> Result<String, anyhow::Error> {
+        parameters: &HashMap<String, ParamInfo>,
+    ) -> (String, HashMap<String, String>) {
         // Triggering with format!("{}/build?delay=0sec", job_url) doesn't use a queue
         let params: HashMap<String, String> = parameters
-            .into_iter()
+            .iter()
             .filter(|(_, v)| v.value != DEFAULT_PARAM_VALUE)
-            .map(|(k, v)| (k, v.value))
+            .map(|(k, v)| (k.clone(), v.value.clone()))
             .collect();
 
         let url = format_url(&format!(
@@ -915,7 +983,23 @@ impl JenkinsClient {
                 "buildWithParameters"
             }
         ));
+        (url, params)
+    }
 
+    /// Triggers a build for a specific job on the Jenkins server.
+    ///
+    /// # Arguments
+    /// * `job_url` - The URL of the job.
+    /// * `parameters` - The parameters to pass to the job.
+    ///
+    /// # Returns
+    /// A `Result` containing the queue_location or an `anyhow::Error` if the request fails.
+    pub async fn trigger_build(
+        &self,
+        job_url: &str,
+        parameters: HashMap<String, ParamInfo>,
+    ) -> Result<String, anyhow::Error> {
+        let (url, params) = Self::trigger_request(job_url, &parameters);
         let response = self.post_with_crumb_retry(&url, Some(&params)).await?;
         // queue URL, e.g. http://jenkins_url/queue/item/1/
         let queue_location = response
@@ -933,7 +1017,6 @@ impl JenkinsClient {
         queue_url: &str,
         event_receiver: &mut mpsc::Receiver<Event>,
     ) -> Result<String, anyhow::Error> {
-        let api_url = format_url(&format!("{}/api/json", queue_url));
         let mut spinner = Some(spinner::Spinner::new(t!("polling-queue-item")));
         let mut paused = false;
 
@@ -943,21 +1026,15 @@ impl JenkinsClient {
                     if paused {
                         continue;
                     }
-                    let response = self.get_with_refresh(&api_url).await?;
-                    let queue_item: serde_json::Value = response.json().await?;
-                    // println!("{}, queue: {:?}", api_url, queue_item);
-                    if let Some(executable) = queue_item["executable"].as_object() {
-                        // if let Some(build_url) = executable["url"].as_str() // maybe domain is different
-                        if let Some(number) = executable["number"].as_i64() {
-                            let job_url = self.job_url.as_ref().unwrap();
-                            let build_url = format_url(&format!("{}/{}", job_url, number));
-                            if let Some(sp) = spinner.take() {
-                                sp.finish_with_message(format!("Build URL: {}", build_url.underline().blue()));
-                            } else {
-                                println!("Build URL: {}", build_url.underline().blue());
-                            }
-                            break Ok(build_url.to_string());
+                    if let Some(number) = self.get_queue_executable(queue_url).await? {
+                        let job_url = self.job_url.as_ref().unwrap();
+                        let build_url = format_url(&format!("{}/{}", job_url, number));
+                        if let Some(sp) = spinner.take() {
+                            sp.finish_with_message(format!("Build URL: {}", build_url.underline().blue()));
+                        } else {
+                            println!("Build URL: {}", build_url.underline().blue());
                         }
+                        break Ok(build_url.to_string());
                     }
                 },
                 msg = event_receiver.recv() => {
@@ -986,7 +1063,25 @@ impl JenkinsClient {
         }
     }
 
-    /// Poll the build status until it completes
+    /// Check a queue item once and get its build number when it has started.
+    ///
+    /// # Returns
+    /// * `Ok(None)` while the item is still waiting in the queue
+    /// * `Err` if the queue item was cancelled
+    pub async fn get_queue_executable(&self, queue_url: &str) -> Result<Option<u64>, anyhow::Error> {
+        let api_url = format_url(&format!("{}/api/json", queue_url));
+        let response = self.get_with_refresh(&api_url).await?;
+        let queue_item: serde_json::Value = response.json().await?;
+        // println!("{}, queue: {:?}", api_url, queue_item);
+        if queue_item["cancelled"].as_bool().unwrap_or(false) {
+            return Err(anyhow!("Queue item cancelled"));
+        }
+        // executable.url may use a different domain, build the URL from the number instead
+        Ok(queue_item["executable"]["number"].as_u64())
+    }
+
+    /// Poll the build status until it completes, then send the configured notifications
+    /// (not when the polling is cancelled)
     ///
     /// # Arguments
     /// * `build_url` - The URL of the build
@@ -1002,8 +1097,14 @@ impl JenkinsClient {
         event_receiver: &mut mpsc::Receiver<Event>,
     ) -> Result<(), anyhow::Error> {
         let mut visited_builds = HashSet::new();
-        self.poll_build_status_inner(build_url, event_receiver, true, &mut visited_builds)
-            .await
+        let result = self
+            .poll_build_status_inner(build_url, event_receiver, true, &mut visited_builds)
+            .await;
+        let cancelled = matches!(&result, Err(e) if e.to_string().contains("cancelled!"));
+        if let Some(notify_config) = self.notify.as_ref().filter(|_| !cancelled) {
+            notify::notify_build_finished(self, notify_config, build_url).await;
+        }
+        result
     }
 
     async fn poll_build_status_inner(
@@ -1368,14 +1469,151 @@ impl JenkinsClient {
     /// Get Jenkins build log
     #[allow(dead_code)]
     pub async fn get_jenkins_console_log(&self, build_url: &str) -> Result<(), anyhow::Error> {
-        let api_url = format_url(&format!("{}/consoleText", build_url));
-        let response = self.get_with_refresh(&api_url).await?;
-        let console_log = response.text().await?;
+        let console_log = self.get_console_text(build_url).await?;
         clear_screen();
         println!("{}", console_log);
         Ok(())
     }
 
+    /// Get the plain text console output of a build.
+    pub async fn get_console_text(&self, build_url: &str) -> Result<String, anyhow::Error> {
+        let api_url = format_url(&format!("{}/consoleText", build_url));
+        let response = self.get_with_refresh(&api_url).await?;
+        Ok(response.text().await?)
+    }
+
+    /// Get a build of a job by number.
+    ///
+    /// # Arguments
+    /// * `job_url` - e.g. http://jenkins_url/job/job_name
+    /// * `number` - Build number.
+    pub async fn get_build(&self, job_url: &str, number: u64) -> Result<BuildSummary, anyhow::Error> {
+        self.get_build_by_url(&format!("{}/{}", job_url, number)).await
+    }
+
+    /// Get a build by its URL, e.g. http://jenkins_url/job/job_name/42/
+    pub async fn get_build_by_url(&self, build_url: &str) -> Result<BuildSummary, anyhow::Error> {
+        let api_url = format_url(&format!("{}/api/json?tree={}", build_url, builds::BUILD_TREE_FIELDS));
+        let response = self.get_with_refresh(&api_url).await?;
+        let json_response: serde_json::Value = response.json().await?;
+        Ok(builds::parse_build(&json_response))
+    }
+
+    /// Get the most recent builds of a job, newest first.
+    ///
+    /// # Arguments
+    /// * `job_url` - e.g. http://jenkins_url/job/job_name
+    /// * `limit` - Maximum number of builds.
+    pub async fn get_builds(&self, job_url: &str, limit: usize) -> Result<Vec<BuildSummary>, anyhow::Error> {
+        let api_url = format_url(&format!(
+            "{}/api/json?tree=builds[{}]{{0,{}}}",
+            job_url,
+            builds::BUILD_TREE_FIELDS,
+            limit
+        ));
+        let response = self.get_with_refresh(&api_url).await?;
+        let json_response: serde_json::Value = response.json().await?;
+        Ok(builds::parse_builds(&json_response))
+    }
+
+    /// Get the test report of a finished build.
+    ///
+    /// # Returns
+    /// `Ok(None)` if the build has no test results.
+    pub async fn get_test_report(&self, build_url: &str) -> Result<Option<TestReport>, anyhow::Error> {
+        let api_url = format_url(&format!(
+            "{}/testReport/api/json?tree={}",
+            build_url,
+            test_report::TEST_REPORT_TREE
+        ));
+        let response = self.get_with_refresh_raw(&api_url).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let response = self.handle_response(Ok(response)).await?;
+        let json_response: serde_json::Value = response.json().await?;
+        Ok(Some(test_report::parse_test_report(&json_response)))
+    }
+
+    /// Get the archived artifacts of a build.
+    pub async fn get_build_artifacts(&self, build_url: &str) -> Result<Vec<BuildArtifact>, anyhow::Error> {
+        let api_url = format_url(&format!("{}/api/json?tree={}", build_url, artifacts::ARTIFACTS_TREE));
+        let response = self.get_with_refresh(&api_url).await?;
+        let json_response: serde_json::Value = response.json().await?;
+        Ok(artifacts::parse_build_artifacts(&json_response))
+    }
+
+    /// URL of an archived artifact, e.g. http://jenkins_url/job/job_name/42/artifact/target/app.jar
+    fn artifact_url(build_url: &str, relative_path: &str) -> Result<reqwest::Url, anyhow::Error> {
+        let mut url = reqwest::Url::parse(&format_url(build_url))?;
+        url.path_segments_mut()
+            .map_err(|_| anyhow!("Invalid build URL: {}", build_url))?
+            .pop_if_empty()
+            .push("artifact")
+            .extend(relative_path.split('/'));
+        Ok(url)
+    }
+
+    /// Get the content of a (text) build artifact.
+    pub async fn get_artifact_text(&self, build_url: &str, relative_path: &str) -> Result<String, anyhow::Error> {
+        let url = Self::artifact_url(build_url, relative_path)?;
+        let response = self.get_with_refresh(url.as_str()).await?;
+        Ok(response.text().await?)
+    }
+
+    /// Get the description of a build, `None` if it has none.
+    pub async fn get_build_description(&self, build_url: &str) -> Result<Option<String>, anyhow::Error> {
+        let api_url = format_url(&format!("{}/api/json?tree=description", build_url));
+        let response = self.get_with_refresh(&api_url).await?;
+        let json_response: serde_json::Value = response.json().await?;
+        Ok(json_response
+            .get("description")
+            .and_then(|description| description.as_str())
+            .map(|description| description.to_string()))
+    }
+
+    /// Get the environment variables injected into a build (EnvInject plugin).
+    pub async fn get_injected_env_vars(&self, build_url: &str) -> Result<HashMap<String, String>, anyhow::Error> {
+        let api_url = format_url(&format!("{}/injectedEnvVars/api/json", build_url));
+        let response = self.get_with_refresh(&api_url).await?;
+        let json_response: serde_json::Value = response.json().await?;
+        Ok(captures::parse_injected_env_vars(&json_response))
+    }
+
+    /// Download a build artifact to `dest`, streaming the body to disk.
+    ///
+    /// # Arguments
+    /// * `build_url` - e.g. http://jenkins_url/job/job_name/42/
+    /// * `artifact` - Artifact listed by `get_build_artifacts`.
+    /// * `dest` - Local file path; parent directories are created.
+    /// * `progress` - Progress bar updated with the downloaded bytes.
+    pub async fn download_artifact(
+        &self,
+        build_url: &str,
+        artifact: &BuildArtifact,
+        dest: &std::path::Path,
+        progress: &indicatif::ProgressBar,
+    ) -> Result<(), anyhow::Error> {
+        use std::io::Write;
+
+        let url = Self::artifact_url(build_url, &artifact.relative_path)?;
+        let mut response = self.get_with_refresh(url.as_str()).await?;
+        if let Some(length) = response.content_length() {
+            progress.set_length(length);
+        }
+
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::File::create(dest)?;
+        while let Some(chunk) = response.chunk().await? {
+            file.write_all(&chunk)?;
+            progress.inc(chunk.len() as u64);
+        }
+        file.flush()?;
+        Ok(())
+    }
+
     /// Check if there is an ongoing build and return the build status and number
     pub async fn is_building(&self) -> Result<BuildStatus, anyhow::Error> {
         let job_url = self.job_url.as_ref().unwrap();
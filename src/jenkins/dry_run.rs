@@ -0,0 +1,158 @@
+use colored::Colorize;
+use std::collections::HashMap;
+
+use crate::constants::{ParamType, MASKED_PASSWORD};
+use crate::i18n::macros::t;
+use crate::jenkins::{client::JenkinsClient, presets, ParamInfo};
+
+fn is_password(parameters: &HashMap<String, ParamInfo>, name: &str) -> bool {
+    parameters
+        .get(name)
+        .is_some_and(|param| param.r#type == ParamType::Password)
+}
+
+/// Form fields of the trigger request sorted by name, with password values masked.
+pub fn masked_form_fields(parameters: &HashMap<String, ParamInfo>) -> Vec<(String, String)> {
+    let (_, form) = JenkinsClient::trigger_request("", parameters);
+    let mut fields: Vec<(String, String)> = form
+        .into_iter()
+        .map(|(name, value)| {
+            let value = if is_password(parameters, &name) {
+                MASKED_PASSWORD.to_string()
+            } else {
+                value
+            };
+            (name, value)
+        })
+        .collect();
+    fields.sort();
+    fields
+}
+
+/// `application/x-www-form-urlencoded` body, e.g. `GIT_BRANCH=release%2F1.2&SKIP_TESTS=true`
+pub fn form_body(fields: &[(String, String)]) -> String {
+    url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(fields)
+        .finish()
+}
+
+/// Quote a value for POSIX shells.
+pub fn shell_quote(value: &str) -> String {
+    if !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c))
+    {
+        return value.to_string();
+    }
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Command triggering the job with a saved preset.
+pub fn preset_command(job_url: &str, preset_name: &str, protected: bool) -> String {
+    let mut command = format!(
+        "jenkins -U {} --preset {}",
+        shell_quote(job_url),
+        shell_quote(preset_name)
+    );
+    if protected {
+        command.push_str(" --yes-i-mean-prod");
+    }
+    command
+}
+
+/// Environment variable holding the value of a password parameter, e.g. `DB_PASSWORD`:
+/// the name in upper case, with characters other than `[A-Za-z0-9_]` replaced by `_`.
+fn password_env_var(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Environment variables the curl command reads: the credentials and the password parameters.
+pub fn curl_env_vars(parameters: &HashMap<String, ParamInfo>) -> Vec<String> {
+    let mut names = vec!["JENKINS_USER".to_string(), "JENKINS_TOKEN".to_string()];
+    names.extend(
+        masked_form_fields(parameters)
+            .into_iter()
+            .filter(|(name, _)| is_password(parameters, name))
+            .map(|(name, _)| password_env_var(&name)),
+    );
+    names
+}
+
+/// curl command sending the same request; credentials and passwords are read from the environment,
+/// see [`curl_env_vars`].
+pub fn curl_command(url: &str, parameters: &HashMap<String, ParamInfo>) -> String {
+    let mut command = format!(
+        "curl -X POST --user \"$JENKINS_USER:$JENKINS_TOKEN\" {}",
+        shell_quote(url)
+    );
+    for (name, value) in masked_form_fields(parameters) {
+        let field = if !is_password(parameters, &name) {
+            shell_quote(&format!("{}={}", name, value))
+        } else if password_env_var(&name) == name {
+            format!("\"{}=${}\"", name, name)
+        } else {
+            format!("{}\"${}\"", shell_quote(&format!("{}=", name)), password_env_var(&name))
+        };
+        command.push_str(&format!(" \\\n  --data-urlencode {}", field));
+    }
+    command
+}
+
+/// What a dry run shows instead of triggering.
+pub struct DryRunReport<'a> {
+    pub job_url: &'a str,
+    pub params: &'a HashMap<String, ParamInfo>,
+    pub defaults: &'a HashMap<String, ParamInfo>,
+    pub last_build: Option<&'a HashMap<String, ParamInfo>>,
+    pub preset_name: Option<&'a str>,
+    pub protected: bool,
+}
+
+fn print_diff(title: String, old: &HashMap<String, ParamInfo>, new: &HashMap<String, ParamInfo>) {
+    println!("{}", title.bold());
+    let changes = presets::diff_params(old, new);
+    if changes.is_empty() {
+        println!("  {}", t!("dry-run-no-changes").dimmed());
+    } else {
+        presets::print_param_diff(&changes);
+    }
+}
+
+pub fn print_dry_run(report: &DryRunReport) {
+    let (url, _) = JenkinsClient::trigger_request(report.job_url, report.params);
+    let fields = masked_form_fields(report.params);
+
+    println!();
+    println!("{}", t!("dry-run-title").yellow().bold());
+    println!("POST {}", url.underline().blue());
+    if !fields.is_empty() {
+        println!("{}", form_body(&fields));
+    }
+    println!();
+    print_diff(t!("dry-run-vs-defaults"), report.defaults, report.params);
+    match report.last_build {
+        Some(last_build) => print_diff(t!("dry-run-vs-last-build"), last_build, report.params),
+        None => println!("{}", t!("dry-run-no-last-build").dimmed()),
+    }
+    println!();
+    println!("{}", t!("dry-run-command").bold());
+    match report.preset_name {
+        Some(preset_name) => println!("{}", preset_command(report.job_url, preset_name, report.protected)),
+        None => {
+            println!("{}", curl_command(&url, report.params));
+            println!(
+                "{}",
+                t!("dry-run-curl-env", "names" => curl_env_vars(report.params).join(", ")).dimmed()
+            );
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, FuzzySelect};
+use std::collections::HashMap;
+
+use crate::constants::RECENT_COMMITS_LIMIT;
+use crate::i18n::macros::t;
+use crate::jenkins::ParamInfo;
+use crate::models::{BranchConfig, BranchSource, JenkinsConfig};
+use crate::prompt;
+use crate::utils::{
+    get_current_branch, get_git_tags, get_recent_commits, get_remote_branches, git_output, sort_tags_by_semver,
+};
+
+/// Branch settings of a job: matching `jobs` entries override the service `branches`.
+pub fn resolve_branch_config(config: &JenkinsConfig, job_name: &str, display_name: &str) -> BranchConfig {
+    let service_config = config.branches.clone().unwrap_or_default();
+    config
+        .job_configs(job_name, display_name)
+        .filter_map(|job_config| job_config.branches.as_ref())
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev() // the first matching entry wins
+        .fold(service_config, |merged, job_branches| merged.merge(job_branches))
+}
+
+/// Options of the branch picker from the configured sources, in source order.
+/// Commits are listed as `<short sha> <subject>`, see [`option_value`].
+pub fn branch_options(config: &BranchConfig) -> Vec<String> {
+    let remote = find_local_remote(config.remote(), &local_remotes()).unwrap_or_else(|| config.remote().to_string());
+    config
+        .sources()
+        .into_iter()
+        .flat_map(|source| match source {
+            BranchSource::Branches => get_remote_branches(&remote),
+            BranchSource::Tags => sort_tags_by_semver(get_git_tags()),
+            BranchSource::Commits => get_recent_commits(RECENT_COMMITS_LIMIT),
+        })
+        .collect()
+}
+
+/// Parameter value of a picker option: the branch/tag name, or the sha of a commit line.
+pub fn option_value(option: &str) -> &str {
+    option.split_whitespace().next().unwrap_or(option)
+}
+
+/// Branches used by the build parameters, without duplicates.
+pub fn branch_params(params: &HashMap<String, ParamInfo>, config: &BranchConfig) -> Vec<String> {
+    let mut names: Vec<&String> = params.keys().filter(|name| config.is_branch_param(name)).collect();
+    names.sort();
+    let mut branches: Vec<String> = Vec::new();
+    for name in names {
+        let branch = params[name].value.trim();
+        if !branch.is_empty() && !branches.iter().any(|b| b == branch) {
+            branches.push(branch.to_string());
+        }
+    }
+    branches
+}
+
+/// Local and remote state of a branch.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BranchState {
+    pub branch: String,
+    pub is_current: bool,
+    pub local_sha: Option<String>, // HEAD for the current branch, `None` without a local branch
+    pub remote_sha: Option<String>, // `None` when the branch is not on the remote
+    pub dirty: bool,               // uncommitted changes (current branch only)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BranchIssue {
+    NotOnRemote,
+    HeadMismatch { local: String, remote: String }, // short commit ids
+    Uncommitted,
+}
+
+fn short_sha(sha: &str) -> String {
+    sha.chars().take(8).collect()
+}
+
+/// What would make the build run other code than the local branch.
+pub fn branch_issues(state: &BranchState) -> Vec<BranchIssue> {
+    let mut issues = Vec::new();
+    match (&state.local_sha, &state.remote_sha) {
+        (_, None) => issues.push(BranchIssue::NotOnRemote),
+        (Some(local), Some(remote)) if local != remote => issues.push(BranchIssue::HeadMismatch {
+            local: short_sha(local),
+            remote: short_sha(remote),
+        }),
+        _ => {}
+    }
+    if state.is_current && state.dirty {
+        issues.push(BranchIssue::Uncommitted);
+    }
+    issues
+}
+
+/// Pushing helps when a local branch is missing on or differs from the remote.
+pub fn can_push(state: &BranchState, issues: &[BranchIssue]) -> bool {
+    state.local_sha.is_some()
+        && issues
+            .iter()
+            .any(|issue| matches!(issue, BranchIssue::NotOnRemote | BranchIssue::HeadMismatch { .. }))
+}
+
+/// Read the state of a branch with git.
+/// Returns `None` outside a git work tree or when the remote cannot be reached.
+pub fn read_branch_state(branch: &str, remote: &str) -> Option<BranchState> {
+    if git_output(&["rev-parse", "--is-inside-work-tree"]).as_deref() != Some("true") {
+        return None;
+    }
+    let remote_ref = format!("refs/heads/{}", branch);
+    let remote_output = git_output(&["ls-remote", "--heads", remote, &remote_ref])?;
+    let remote_sha = remote_output
+        .lines()
+        .find_map(|line| line.split_once('\t').filter(|(_, name)| *name == remote_ref))
+        .map(|(sha, _)| sha.to_string());
+
+    let is_current = get_current_branch() == branch;
+    let local_sha = if is_current {
+        git_output(&["rev-parse", "HEAD"])
+    } else {
+        git_output(&["rev-parse", "--verify", "--quiet", &remote_ref])
+    };
+    if remote_sha.is_none() && local_sha.is_none() && is_tag_or_commit(branch) {
+        return None; // picked from tags or commits, not a branch
+    }
+    let dirty = is_current && git_output(&["status", "--porcelain"]).is_some_and(|status| !status.is_empty());
+    Some(BranchState {
+        branch: branch.to_string(),
+        is_current,
+        local_sha,
+        remote_sha,
+        dirty,
+    })
+}
+
+fn is_tag_or_commit(value: &str) -> bool {
+    let is_tag = git_output(&["rev-parse", "--verify", "--quiet", &format!("refs/tags/{}", value)]).is_some();
+    let is_commit = value.len() >= 7
+        && value.chars().all(|c| c.is_ascii_hexdigit())
+        && git_output(&["cat-file", "-e", &format!("{}^{{commit}}", value)]).is_some();
+    is_tag || is_commit
+}
+
+fn normalize_remote_url(url: &str) -> &str {
+    let url = url.trim_end_matches('/');
+    url.strip_suffix(".git").unwrap_or(url)
+}
+
+/// The local remote for the configured `remote`, which is a remote name or URL.
+/// `remotes` are the `(name, url)` pairs of the current repository; `None` when no remote matches,
+/// e.g. when the command is run in the repository of another project.
+pub fn find_local_remote(configured: &str, remotes: &[(String, String)]) -> Option<String> {
+    remotes
+        .iter()
+        .find(|(name, _)| name == configured)
+        .or_else(|| {
+            remotes
+                .iter()
+                .find(|(_, url)| normalize_remote_url(url) == normalize_remote_url(configured))
+        })
+        .map(|(name, _)| name.clone())
+}
+
+/// `(name, url)` of the remotes of the current repository.
+fn local_remotes() -> Vec<(String, String)> {
+    git_output(&["config", "--get-regexp", r"^remote\..*\.url$"])
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let (key, url) = line.split_once(' ')?;
+            let name = key.strip_prefix("remote.")?.strip_suffix(".url")?;
+            Some((name.to_string(), url.trim().to_string()))
+        })
+        .collect()
+}
+
+/// `git push <remote> <branch>`, showing git's output.
+pub fn push_branch(branch: &str, remote: &str) -> Result<()> {
+    let status = std::process::Command::new("git")
+        .args(["push", remote, branch])
+        .status()?;
+    if !status.success() {
+        return Err(anyhow!("exit code {}", status.code().unwrap_or(-1)));
+    }
+    Ok(())
+}
+
+/// Message reporting an issue of a branch on `remote`.
+pub fn issue_message(state: &BranchState, issue: &BranchIssue, remote: &str) -> String {
+    match issue {
+        BranchIssue::NotOnRemote => {
+            t!("git-branch-not-on-remote", "branch" => state.branch.as_str(), "remote" => remote)
+        }
+        BranchIssue::HeadMismatch {
+            local,
+            remote: remote_sha,
+        } => t!(
+            "git-branch-head-mismatch",
+            "branch" => state.branch.as_str(),
+            "remote" => remote,
+            "local_sha" => local.as_str(),
+            "remote_sha" => remote_sha.as_str()
+        ),
+        BranchIssue::Uncommitted => t!("git-uncommitted-changes", "branch" => state.branch.as_str()),
+    }
+}
+
+fn print_issues(state: &BranchState, issues: &[BranchIssue], remote: &str) {
+    for issue in issues {
+        let message = issue_message(state, issue, remote);
+        println!("{} {}", "!".yellow().bold(), message.yellow());
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BranchAction {
+    Push,
+    Continue,
+    Abort,
+}
+
+fn select_branch_action(state: &BranchState, pushable: bool, remote: &str) -> BranchAction {
+    let mut actions = Vec::new();
+    if pushable {
+        actions.push((
+            t!("git-check-action-push", "branch" => state.branch.as_str(), "remote" => remote),
+            BranchAction::Push,
+        ));
+    }
+    actions.push((t!("git-check-action-continue"), BranchAction::Continue));
+    actions.push((t!("git-check-action-abort"), BranchAction::Abort));
+    let items: Vec<&String> = actions.iter().map(|(label, _)| label).collect();
+    let selection = prompt::handle_selection_opt(prompt::with_prompt_kind(prompt::PromptKind::FuzzySelectVim, || {
+        FuzzySelect::with_theme(&ColorfulTheme::default())
+            .with_prompt(t!("git-check-prompt"))
+            .items(&items)
+            .default(0)
+            .vim_mode(true)
+            .with_initial_text("")
+            .interact_opt()
+    }));
+    selection.map(|idx| actions[idx].1).unwrap_or(BranchAction::Abort)
+}
+
+/// Check that the branches used by the build are pushed, offering to push or abort.
+/// Skipped unless the current repository has the configured remote. Without `interactive`
+/// any issue aborts the build. Returns `false` if the build should be aborted.
+pub fn confirm_branches_pushed(params: &HashMap<String, ParamInfo>, config: &BranchConfig, interactive: bool) -> bool {
+    let Some(remote) = find_local_remote(config.remote(), &local_remotes()) else {
+        return true;
+    };
+    let remote = remote.as_str();
+    for branch in branch_params(params, config) {
+        let Some(mut state) = read_branch_state(&branch, remote) else {
+            continue;
+        };
+        loop {
+            let issues = branch_issues(&state);
+            if issues.is_empty() {
+                break;
+            }
+            print_issues(&state, &issues, remote);
+            if !interactive {
+                eprintln!("{}", t!("git-check-non-interactive").red());
+                return false;
+            }
+            match select_branch_action(&state, can_push(&state, &issues), remote) {
+                BranchAction::Push => {
+                    if let Err(e) = push_branch(&branch, remote) {
+                        eprintln!("{}", t!("git-push-failed", "error" => e.to_string()).red());
+                    }
+                    match read_branch_state(&branch, remote) {
+                        Some(new_state) => state = new_state,
+                        None => break,
+                    }
+                }
+                BranchAction::Continue => break,
+                BranchAction::Abort => return false,
+            }
+        }
+    }
+    true
+}
@@ -3,20 +3,27 @@ use chrono::{DateTime, Local};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use std::fs::{self, File, OpenOptions};
-use std::io::{BufReader, BufWriter, Read, Write};
-use std::path::PathBuf;
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
 
 use crate::config::DATA_DIR;
 use crate::constants::{ParamType, MASKED_PASSWORD};
 use crate::i18n::macros::t;
-use crate::jenkins::{JenkinsJobParameter, ParamInfo};
-use crate::migrations::{migrate_history, CURRENT_HISTORY_VERSION};
+use crate::jenkins::{
+    builds::{self, BuildSummary},
+    JenkinsJobParameter, ParamInfo,
+};
+use crate::migrations::{migrate_history, NewerVersionError, CURRENT_HISTORY_VERSION};
 use crate::prompt;
+use crate::storage;
 use crate::utils::{self, current_timestamp};
 
 pub const HISTORY_FILE: &str = "history.toml";
 
+/// Number of runs kept per job.
+pub const MAX_RUNS_PER_JOB: usize = 10;
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct History {
     pub entries: Vec<HistoryEntry>,
@@ -24,9 +31,13 @@ pub struct History {
     pub file_path: PathBuf,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub version: Option<u32>,
+    #[serde(skip)]
+    pub saved: Vec<HistoryEntry>, // entries of the file when loaded or last saved, merged with on save
+    #[serde(skip)]
+    pub max_runs: Option<usize>, // runs kept per job, default MAX_RUNS_PER_JOB
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 pub struct HistoryEntry {
     pub job_url: String,
     pub name: String,
@@ -34,6 +45,96 @@ pub struct HistoryEntry {
     pub params: Option<HashMap<String, ParamInfo>>,
     pub created_at: Option<i64>,
     pub completed_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub runs: Vec<HistoryRun>, // newest first, at most `History::max_runs`
+}
+
+/// A triggered build of a job.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct HistoryRun {
+    #[serde(default)]
+    pub params: HashMap<String, ParamInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>, // SUCCESS, FAILURE, ...
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u64>, // milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>, // preset used as is
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<i64>,
+}
+
+impl HistoryRun {
+    /// One-line summary for the run picker.
+    pub fn display_line(&self) -> String {
+        let started = self
+            .created_at
+            .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+            .map(|datetime| datetime.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        let number = self.number.map(|number| format!("#{}", number)).unwrap_or_default();
+        let mut line = format!(
+            "{:<16} {:<6} {:<9} {:>9}",
+            started,
+            number,
+            self.result.as_deref().unwrap_or("-"),
+            self.duration.map(builds::format_duration).unwrap_or_default()
+        );
+        let mut params: Vec<(&str, &str)> = self
+            .params
+            .iter()
+            .map(|(name, param)| {
+                let value = if param.r#type == ParamType::Password {
+                    MASKED_PASSWORD
+                } else {
+                    param.value.as_str()
+                };
+                (name.as_str(), value)
+            })
+            .collect();
+        params.sort();
+        if let Some(summary) = builds::params_summary(&params) {
+            line.push_str(&format!("  {}", summary));
+        }
+        line
+    }
+}
+
+impl HistoryEntry {
+    /// Recorded runs, newest first.
+    /// Entries saved before runs were recorded yield their last parameters as a single run.
+    pub fn recent_runs(&self) -> Vec<HistoryRun> {
+        if !self.runs.is_empty() {
+            return self.runs.clone();
+        }
+        self.params
+            .clone()
+            .map(|params| {
+                vec![HistoryRun {
+                    params,
+                    created_at: self.created_at,
+                    completed_at: self.completed_at,
+                    ..Default::default()
+                }]
+            })
+            .unwrap_or_default()
+    }
+
+    /// This entry with the parameters of one of its runs.
+    pub fn with_run(&self, run: &HistoryRun) -> HistoryEntry {
+        HistoryEntry {
+            params: Some(run.params.clone()),
+            created_at: run.created_at,
+            completed_at: run.completed_at,
+            ..self.clone()
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -75,8 +176,12 @@ impl History {
     pub fn new() -> Result<Self> {
         let file_path = DATA_DIR.join(HISTORY_FILE);
 
-        if let Err(_e) = migrate_history() {
-            // eprintln!("Warning: Failed to migrate history: {}.", _e);
+        if let Err(e) = migrate_history() {
+            // A newer history must not be overwritten by this version
+            if e.is::<NewerVersionError>() {
+                return Err(e);
+            }
+            // eprintln!("Warning: Failed to migrate history: {}.", e);
         }
 
         // auto create history file
@@ -88,6 +193,8 @@ impl History {
             entries: vec![],
             version: Some(CURRENT_HISTORY_VERSION),
             file_path,
+            saved: vec![],
+            max_runs: None,
         };
 
         history.load_history()?;
@@ -96,55 +203,114 @@ impl History {
         Ok(history)
     }
 
-    pub fn load_history(&mut self) -> Result<()> {
-        let file = File::open(&self.file_path).context("Failed to open history file")?;
-        let metadata = file.metadata().context("Failed to get file metadata")?;
-        // println!("metadata: {:?}", metadata);
-
-        // If the file is empty
-        if metadata.len() == 0 {
-            // println!("history file is empty");
-            self.entries = vec![]; // *self = Self::default();
-            return Ok(());
-        }
+    /// Runs kept per job; older runs are dropped on save.
+    pub fn max_runs(&self) -> usize {
+        self.max_runs.unwrap_or(MAX_RUNS_PER_JOB)
+    }
 
+    /// Read the history file; `None` when it is missing or empty.
+    /// A file that cannot be parsed is moved aside with a warning.
+    fn read_file(file_path: &Path) -> Result<Option<History>> {
+        if !file_path.exists() {
+            return Ok(None);
+        }
+        let file = File::open(file_path).context("Failed to open history file")?;
         let mut content = String::new();
         let mut reader = BufReader::new(file);
         reader
             .read_to_string(&mut content)
             .context("Failed to read file content")?;
-        // println!("load_history: {}", content);
+        if content.trim().is_empty() {
+            return Ok(None);
+        }
         match toml::from_str::<History>(content.trim()) {
-            Ok(file_history) => {
+            Ok(file_history) => Ok(Some(file_history)),
+            Err(_e) => {
+                storage::quarantine_corrupted(file_path)?;
+                Ok(None)
+            }
+        }
+    }
+
+    pub fn load_history(&mut self) -> Result<()> {
+        match Self::read_file(&self.file_path)? {
+            Some(file_history) => {
                 self.entries = file_history.entries;
                 self.version = file_history.version;
-                Ok(())
-            }
-            Err(_e) => {
-                self.entries = vec![];
-                Ok(())
             }
+            None => self.entries = vec![], // *self = Self::default();
         }
+        self.saved = self.entries.clone();
+        Ok(())
     }
 
-    pub fn save_history(&self) -> Result<()> {
+    /// Write the history, merged with the changes other processes saved since it was loaded.
+    /// The file is locked while it is read and replaced.
+    pub fn save_history(&mut self) -> Result<()> {
         // println!("save_history: {:?}, {:?}", self.entries, self.file_path);
-        let file = OpenOptions::new()
-            .create(true)
-            .write(true)
-            .truncate(true)
-            .open(&self.file_path)
-            .context("Failed to open history file for writing")?;
-        let mut writer = BufWriter::new(file);
+        let _lock = storage::lock_file(&self.file_path)?;
+        if let Some(file_history) = Self::read_file(&self.file_path)? {
+            let max_runs = self.max_runs();
+            self.entries = storage::merge_records(
+                &self.saved,
+                &self.entries,
+                &file_history.entries,
+                |entry| (entry.job_url.clone(), entry.name.clone()),
+                |base, ours, theirs| Self::merge_entry(base, ours, theirs, max_runs),
+            );
+        }
         let content = toml::to_string(self).context("Failed to serialize history")?;
-        writer
-            .write_all(content.as_bytes())
-            .context("Failed to write history to file")?;
+        storage::write_atomic(&self.file_path, &content).context("Failed to write history to file")?;
+        self.saved = self.entries.clone();
         Ok(())
     }
 
+    /// A job triggered from two processes: the newer entry with the runs of both.
+    fn merge_entry(
+        base: Option<&HistoryEntry>,
+        ours: &HistoryEntry,
+        theirs: &HistoryEntry,
+        max_runs: usize,
+    ) -> HistoryEntry {
+        let base_runs = base.map(|base| base.runs.as_slice()).unwrap_or_default();
+        let mut runs = storage::merge_records(
+            base_runs,
+            &ours.runs,
+            &theirs.runs,
+            |run| run.created_at,
+            |_, ours, _| ours.clone(),
+        );
+        runs.sort_by_key(|run| std::cmp::Reverse(run.created_at.unwrap_or(0)));
+        runs.truncate(max_runs);
+        let newer = if theirs.created_at > ours.created_at {
+            theirs
+        } else {
+            ours
+        };
+        HistoryEntry { runs, ..newer.clone() }
+    }
+
+    /// Save the entry of a job, recording its parameters as the newest run.
     pub fn upsert_history(&mut self, entry: &mut HistoryEntry) -> Result<()> {
         entry.created_at = Some(current_timestamp());
+        let mut runs = self
+            .entries
+            .iter()
+            .find(|e| Self::matches_entry(e, entry))
+            .map(HistoryEntry::recent_runs)
+            .unwrap_or_default();
+        if let Some(params) = &entry.params {
+            runs.insert(
+                0,
+                HistoryRun {
+                    params: params.clone(),
+                    created_at: entry.created_at,
+                    ..Default::default()
+                },
+            );
+            runs.truncate(self.max_runs());
+        }
+        entry.runs = runs;
         if let Some(existing_entry) = self.entries.iter_mut().find(|e| Self::matches_entry(e, entry)) {
             *existing_entry = entry.clone();
         } else {
@@ -202,6 +368,51 @@ impl History {
         }
     }
 
+    /// Record the finished build of the newest run.
+    pub fn complete_run(
+        &mut self,
+        info: &HistoryEntry,
+        build_url: &str,
+        build: Option<&BuildSummary>,
+        preset: Option<&str>,
+    ) -> Result<()> {
+        let completed_at = Some(current_timestamp());
+        self.update_field(info, |entry| {
+            entry.completed_at = completed_at;
+            if let Some(run) = entry.runs.first_mut() {
+                run.build_url = Some(build_url.to_string());
+                run.preset = preset.map(str::to_string);
+                run.completed_at = completed_at;
+                if let Some(build) = build {
+                    run.number = Some(build.number);
+                    run.result = build.result.clone();
+                    run.duration = Some(build.duration);
+                }
+            }
+        })
+    }
+
+    /// Pick one of the recent runs of a job; with a single run it is used directly.
+    /// Returns the entry with the picked run's parameters, or `None` if user pressed Ctrl+C to go back.
+    pub fn select_run(entry: &HistoryEntry) -> Option<HistoryEntry> {
+        let runs = entry.recent_runs();
+        if runs.len() <= 1 {
+            return Some(runs.first().map_or_else(|| entry.clone(), |run| entry.with_run(run)));
+        }
+        let items: Vec<String> = runs.iter().map(HistoryRun::display_line).collect();
+        let selection =
+            prompt::handle_selection_opt(prompt::with_prompt_kind(prompt::PromptKind::FuzzySelectVim, || {
+                dialoguer::FuzzySelect::with_theme(&dialoguer::theme::ColorfulTheme::default())
+                    .with_prompt(t!("select-history-run"))
+                    .items(&items)
+                    .default(0)
+                    .vim_mode(true)
+                    .with_initial_text("")
+                    .interact_opt()
+            }))?;
+        Some(entry.with_run(&runs[selection]))
+    }
+
     /// Display parameter differences and ask user how to handle previous parameters.
     /// Returns `Some(action)` if user made a selection,
     /// or `None` if user pressed Ctrl+C to go back.
@@ -0,0 +1,120 @@
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::process::Command;
+
+use crate::constants::{ParamType, MASKED_PASSWORD};
+use crate::jenkins::ParamInfo;
+use crate::models::{HooksConfig, JenkinsConfig};
+
+/// Hooks of a job: matching `jobs` entries override the service `hooks` (the first one setting a hook wins).
+pub fn resolve_hooks(config: &JenkinsConfig, job_name: &str, display_name: &str) -> HooksConfig {
+    let service_hooks = config.hooks.clone().unwrap_or_default();
+    let job_hooks: Vec<&HooksConfig> = config
+        .job_configs(job_name, display_name)
+        .map(|job_config| &job_config.hooks)
+        .collect();
+    HooksConfig {
+        pre_build: job_hooks
+            .iter()
+            .find_map(|hooks| hooks.pre_build.clone())
+            .or(service_hooks.pre_build),
+        post_build: job_hooks
+            .iter()
+            .find_map(|hooks| hooks.post_build.clone())
+            .or(service_hooks.post_build),
+    }
+}
+
+/// What a hook knows about the build.
+#[derive(Debug, Clone, Default)]
+pub struct HookContext<'a> {
+    pub job_url: &'a str,
+    pub job_name: &'a str,
+    pub params: Option<&'a HashMap<String, ParamInfo>>,
+    pub build_url: Option<&'a str>,
+    pub result: Option<&'a str>,
+}
+
+/// Environment variable name of a build parameter, e.g. `git-branch` -> `JENKINS_PARAM_GIT_BRANCH`
+pub fn param_env_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("JENKINS_PARAM_{}", name)
+}
+
+/// Environment variables exposed to hooks:
+/// `JENKINS_JOB_URL`, `JENKINS_JOB_NAME`, `JENKINS_PARAMS` (JSON object), `JENKINS_PARAM_<NAME>`,
+/// and after the build `JENKINS_BUILD_URL` and `JENKINS_BUILD_RESULT`. Password values are masked.
+pub fn hook_env(context: &HookContext) -> Vec<(String, String)> {
+    let mut env = vec![
+        ("JENKINS_JOB_URL".to_string(), context.job_url.to_string()),
+        ("JENKINS_JOB_NAME".to_string(), context.job_name.to_string()),
+    ];
+    if let Some(params) = context.params {
+        let mut names: Vec<&String> = params.keys().collect();
+        names.sort();
+        let value = |name: &str| {
+            let param = &params[name];
+            if param.r#type == ParamType::Password {
+                MASKED_PASSWORD.to_string()
+            } else {
+                param.value.clone()
+            }
+        };
+        let json: serde_json::Map<String, serde_json::Value> = names
+            .iter()
+            .map(|name| ((*name).clone(), serde_json::Value::String(value(name))))
+            .collect();
+        env.push((
+            "JENKINS_PARAMS".to_string(),
+            serde_json::Value::Object(json).to_string(),
+        ));
+        for name in names {
+            env.push((param_env_name(name), value(name)));
+        }
+    }
+    if let Some(build_url) = context.build_url {
+        env.push(("JENKINS_BUILD_URL".to_string(), build_url.to_string()));
+    }
+    if let Some(result) = context.result {
+        env.push(("JENKINS_BUILD_RESULT".to_string(), result.to_string()));
+    }
+    env
+}
+
+/// Run a hook command with the shell, inheriting the terminal.
+/// Returns an error if the command cannot be started or exits with a non-zero status.
+pub fn run_hook(command: &str, context: &HookContext) -> Result<()> {
+    let mut process = if cfg!(windows) {
+        let mut process = Command::new("cmd");
+        process.args(["/C", command]);
+        process
+    } else {
+        let mut process = Command::new("sh");
+        process.args(["-c", command]);
+        process
+    };
+    let status = process
+        .envs(hook_env(context))
+        .status()
+        .map_err(|e| anyhow!("{}: {}", command, e))?;
+    if !status.success() {
+        return Err(anyhow!(
+            "{}: exit code {}",
+            command,
+            status
+                .code()
+                .map(|code| code.to_string())
+                .unwrap_or_else(|| "none".to_string())
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::jenkins::JenkinsJob;
+use crate::storage;
+use crate::utils::simplify_url;
+
+/// Directory of the job list caches in `DATA_DIR`, one file per service.
+pub const JOB_CACHE_DIR: &str = "job-cache";
+
+/// Seconds a cached job list is used before it is refreshed in the background.
+pub const DEFAULT_JOB_CACHE_TTL: u64 = 3600;
+
+/// The job list of a service as last fetched.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct JobCache {
+    pub service_url: String,
+    pub fetched_at: i64, // unix timestamp
+    #[serde(default)]
+    pub jobs: Vec<JenkinsJob>,
+}
+
+impl JobCache {
+    /// Whether the list was fetched less than `ttl` seconds before `now`.
+    pub fn is_fresh(&self, ttl: u64, now: i64) -> bool {
+        let age = now - self.fetched_at;
+        age >= 0 && (age as u64) < ttl
+    }
+}
+
+/// Cache file of a service, e.g. `job-cache/jenkins.example.com_8080.toml`.
+pub fn cache_path(dir: &Path, service_url: &str) -> PathBuf {
+    let name: String = simplify_url(service_url)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    dir.join(format!("{}.toml", name))
+}
+
+/// The cached list, `None` when there is none or it cannot be read.
+pub fn load_cache(path: &Path) -> Option<JobCache> {
+    let content = fs::read_to_string(path).ok()?;
+    toml::from_str(&content).ok()
+}
+
+pub fn save_cache(path: &Path, cache: &JobCache) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let content = toml::to_string(cache).context("Failed to serialize job cache")?;
+    storage::write_atomic(path, &content)
+}
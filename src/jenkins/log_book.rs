@@ -0,0 +1,233 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Local, NaiveDate};
+use colored::Colorize;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::i18n::macros::t;
+use crate::jenkins::builds::format_duration;
+use crate::jenkins::history::{History, HistoryEntry, HistoryRun};
+use crate::utils::{glob_to_regex, simplify_url};
+
+/// Number of presets listed in the statistics.
+const TOP_PRESETS: usize = 3;
+
+/// Which runs `jenkins log-book` shows; unset fields match everything.
+#[derive(Debug, Default)]
+pub struct LogBookFilter {
+    pub service_url: Option<String>,  // base URL of a configured service
+    pub job: Option<Regex>,           // matched against the job name or display name
+    pub since: Option<NaiveDate>,     // inclusive, local date
+    pub until: Option<NaiveDate>,     // inclusive, local date
+    pub result: Option<String>,       // case-insensitive, e.g. failure
+    pub params: Vec<(String, Regex)>, // NAME=glob
+}
+
+/// Parse a `NAME=glob` parameter filter, e.g. `GIT_BRANCH=release/*`.
+pub fn parse_param_filter(value: &str) -> Result<(String, Regex)> {
+    let (name, pattern) = value
+        .split_once('=')
+        .filter(|(name, _)| !name.trim().is_empty())
+        .ok_or_else(|| anyhow!("invalid parameter filter `{}`, expected NAME=VALUE", value))?;
+    Ok((name.trim().to_string(), glob_to_regex(pattern)))
+}
+
+/// Parse a `YYYY-MM-DD` date.
+pub fn parse_date(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| anyhow!("invalid date `{}`, expected YYYY-MM-DD", value))
+}
+
+/// A run together with the job it belongs to.
+#[derive(Debug, Clone)]
+pub struct LogBookRecord<'a> {
+    pub entry: &'a HistoryEntry,
+    pub run: HistoryRun,
+}
+
+fn local_date(timestamp: i64) -> Option<NaiveDate> {
+    DateTime::from_timestamp(timestamp, 0).map(|datetime| datetime.with_timezone(&Local).date_naive())
+}
+
+impl LogBookFilter {
+    pub fn matches(&self, entry: &HistoryEntry, run: &HistoryRun) -> bool {
+        if let Some(service_url) = &self.service_url {
+            let service_url = simplify_url(service_url);
+            let job_url = simplify_url(&entry.job_url);
+            if job_url != service_url && !job_url.starts_with(&format!("{}/", service_url)) {
+                return false;
+            }
+        }
+        if let Some(job) = &self.job {
+            let display_name = entry.display_name.as_deref().unwrap_or_default();
+            if !job.is_match(&entry.name) && !job.is_match(display_name) {
+                return false;
+            }
+        }
+        if self.since.is_some() || self.until.is_some() {
+            let Some(date) = run.created_at.and_then(local_date) else {
+                return false;
+            };
+            if self.since.is_some_and(|since| date < since) || self.until.is_some_and(|until| date > until) {
+                return false;
+            }
+        }
+        if let Some(result) = &self.result {
+            if !run
+                .result
+                .as_deref()
+                .is_some_and(|run_result| run_result.eq_ignore_ascii_case(result))
+            {
+                return false;
+            }
+        }
+        self.params
+            .iter()
+            .all(|(name, pattern)| run.params.get(name).is_some_and(|param| pattern.is_match(&param.value)))
+    }
+}
+
+/// Matching runs of all jobs, newest first.
+pub fn find_runs<'a>(history: &'a History, filter: &LogBookFilter) -> Vec<LogBookRecord<'a>> {
+    let mut records: Vec<LogBookRecord> = history
+        .entries
+        .iter()
+        .flat_map(|entry| {
+            entry
+                .recent_runs()
+                .into_iter()
+                .filter(|run| filter.matches(entry, run))
+                .map(move |run| LogBookRecord { entry, run })
+        })
+        .collect();
+    records.sort_by_key(|record| std::cmp::Reverse(record.run.created_at.unwrap_or(0)));
+    records
+}
+
+/// Aggregates over runs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogBookStats {
+    pub runs: usize,
+    pub finished: usize, // runs with a result
+    pub succeeded: usize,
+    pub median_duration: Option<u64>,      // milliseconds, over finished runs
+    pub top_presets: Vec<(String, usize)>, // most used first
+}
+
+impl LogBookStats {
+    /// Percentage of finished runs that succeeded.
+    pub fn success_rate(&self) -> Option<f64> {
+        (self.finished > 0).then(|| self.succeeded as f64 * 100.0 / self.finished as f64)
+    }
+}
+
+pub fn compute_stats(records: &[LogBookRecord]) -> LogBookStats {
+    let finished: Vec<&HistoryRun> = records
+        .iter()
+        .map(|record| &record.run)
+        .filter(|run| run.result.is_some())
+        .collect();
+    let mut durations: Vec<u64> = finished.iter().filter_map(|run| run.duration).collect();
+    durations.sort_unstable();
+    let median_duration = match durations.len() {
+        0 => None,
+        len if len % 2 == 1 => Some(durations[len / 2]),
+        len => Some((durations[len / 2 - 1] + durations[len / 2]) / 2),
+    };
+
+    let mut preset_counts: HashMap<&str, usize> = HashMap::new();
+    for record in records {
+        if let Some(preset) = record.run.preset.as_deref() {
+            *preset_counts.entry(preset).or_default() += 1;
+        }
+    }
+    let mut top_presets: Vec<(String, usize)> = preset_counts
+        .into_iter()
+        .map(|(preset, count)| (preset.to_string(), count))
+        .collect();
+    top_presets.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    top_presets.truncate(TOP_PRESETS);
+
+    LogBookStats {
+        runs: records.len(),
+        finished: finished.len(),
+        succeeded: finished
+            .iter()
+            .filter(|run| run.result.as_deref() == Some("SUCCESS"))
+            .count(),
+        median_duration,
+        top_presets,
+    }
+}
+
+fn display_line(record: &LogBookRecord) -> String {
+    let run = &record.run;
+    let started = run
+        .created_at
+        .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+        .map(|datetime| datetime.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default();
+    let result = run.result.as_deref().unwrap_or("-");
+    let result = match result {
+        "SUCCESS" => result.green(),
+        "UNSTABLE" | "ABORTED" => result.yellow(),
+        "-" => result.dimmed(),
+        _ => result.red(),
+    };
+    let mut line = format!(
+        "{:<16} {:<24} {:<6} {:<9} {:>9}",
+        started,
+        record.entry.display_name.as_deref().unwrap_or(&record.entry.name),
+        run.number.map(|number| format!("#{}", number)).unwrap_or_default(),
+        result,
+        run.duration.map(format_duration).unwrap_or_default()
+    );
+    if let Some(preset) = &run.preset {
+        line.push_str(&format!("  ({})", preset));
+    }
+    line
+}
+
+/// Print the newest `limit` runs and the statistics of all matching runs.
+/// `max_runs` is the number of runs history keeps per job, older ones are not in the log book.
+pub fn print_log_book(records: &[LogBookRecord], limit: usize, max_runs: usize) {
+    if records.is_empty() {
+        println!("{}", t!("log-book-empty").yellow());
+        return;
+    }
+    for record in records.iter().take(limit) {
+        println!("{}", display_line(record));
+    }
+    if records.len() > limit {
+        println!(
+            "{}",
+            t!("log-book-more", "count" => (records.len() - limit).to_string()).dimmed()
+        );
+    }
+
+    let stats = compute_stats(records);
+    println!();
+    println!("{}", t!("log-book-stats-title").bold());
+    println!("  {}", t!("log-book-runs", "count" => stats.runs.to_string()));
+    if let Some(rate) = stats.success_rate() {
+        println!(
+            "  {}",
+            t!("log-book-success-rate", "rate" => format!("{:.1}", rate), "succeeded" => stats.succeeded.to_string(), "finished" => stats.finished.to_string())
+        );
+    }
+    if let Some(duration) = stats.median_duration {
+        println!(
+            "  {}",
+            t!("log-book-median-duration", "duration" => format_duration(duration))
+        );
+    }
+    if !stats.top_presets.is_empty() {
+        let presets: Vec<String> = stats
+            .top_presets
+            .iter()
+            .map(|(preset, count)| format!("{} ({})", preset, count))
+            .collect();
+        println!("  {}", t!("log-book-top-presets", "presets" => presets.join(", ")));
+    }
+    println!("{}", t!("log-book-runs-kept", "count" => max_runs.to_string()).dimmed());
+}
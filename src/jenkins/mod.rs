@@ -6,14 +6,47 @@ use std::collections::{HashMap, HashSet};
 use std::io::BufReader;
 
 use crate::constants::{ParamType, DEFAULT_PARAM_VALUE};
+#[doc(hidden)]
+pub mod artifacts;
+#[doc(hidden)]
+pub mod batch;
+#[doc(hidden)]
+pub mod builds;
+#[doc(hidden)]
+pub mod captures;
 pub mod client;
 mod console_html;
 pub mod cookie;
-pub use client::ClientConfig;
+#[doc(hidden)]
+pub mod dry_run;
+#[doc(hidden)]
+pub mod git_check;
 #[doc(hidden)]
 pub mod history;
 #[doc(hidden)]
+pub mod hooks;
+#[doc(hidden)]
+pub mod job_cache;
+#[doc(hidden)]
+pub mod log_book;
+#[doc(hidden)]
+pub mod notify;
+#[doc(hidden)]
+pub mod param_templates;
+#[doc(hidden)]
+pub mod pipeline;
+#[doc(hidden)]
+pub mod policy;
+#[doc(hidden)]
+pub mod preset_share;
+#[doc(hidden)]
 pub mod presets;
+#[doc(hidden)]
+pub mod template;
+#[doc(hidden)]
+pub mod test_report;
+#[doc(hidden)]
+pub mod validation;
 
 #[derive(Debug, Clone)]
 #[doc(hidden)]
@@ -54,7 +87,7 @@ struct JenkinsResponse {
 }
 
 // job config
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct JenkinsJobParameter {
     pub param_type: Option<ParamType>, // ParamType string, text, choice, boolean, password
     pub name: String,                  // parameter name
@@ -0,0 +1,128 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::io::Write;
+use std::time::Duration;
+
+use crate::i18n::macros::t;
+use crate::jenkins::{
+    builds::{self, BuildSummary},
+    client::JenkinsClient,
+};
+use crate::models::NotifyConfig;
+
+/// Webhook request timeout in seconds.
+const WEBHOOK_TIMEOUT_SECS: u64 = 10;
+
+/// Payload of the webhook POST, e.g.
+/// `{"job":"folder/app","build_url":"...","number":42,"result":"SUCCESS","duration_ms":125000,"triggered_by":"alice"}`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BuildNotification {
+    pub job: String,
+    pub build_url: String,
+    pub number: u64,
+    pub result: String,
+    pub duration_ms: u64,
+    pub triggered_by: Option<String>,
+}
+
+impl BuildNotification {
+    pub fn from_build(build_url: &str, build: &BuildSummary) -> Self {
+        BuildNotification {
+            job: job_name_from_build_url(build_url),
+            build_url: build_url.to_string(),
+            number: build.number,
+            result: build.status().to_string(),
+            duration_ms: build.duration,
+            triggered_by: build.triggered_by.clone(),
+        }
+    }
+
+    /// e.g. `folder/app #42`
+    pub fn title(&self) -> String {
+        format!("{} #{}", self.job, self.number)
+    }
+
+    /// e.g. `SUCCESS in 2m 05s`
+    pub fn summary(&self) -> String {
+        format!("{} in {}", self.result, builds::format_duration(self.duration_ms))
+    }
+}
+
+/// Full job name of a build URL, e.g. http://jenkins_url/job/folder/job/app/42/ -> folder/app
+pub fn job_name_from_build_url(build_url: &str) -> String {
+    build_url
+        .split("/job/")
+        .skip(1)
+        .filter_map(|segment| segment.split('/').next())
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// POST the notification as JSON.
+pub async fn send_webhook(url: &str, notification: &BuildNotification) -> Result<()> {
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECS))
+        .user_agent("Jenkins CLI")
+        .build()?;
+    let response = client.post(url).json(notification).send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow!("{} responded with {}", url, response.status()));
+    }
+    Ok(())
+}
+
+/// Show a desktop notification with `notify-send` (Linux) or `osascript` (macOS).
+pub fn send_desktop_notification(title: &str, body: &str) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification {:?} with title {:?}",
+            body,
+            format!("Jenkins: {}", title)
+        );
+        let mut command = std::process::Command::new("osascript");
+        command.args(["-e", &script]);
+        command
+    } else if cfg!(target_os = "linux") {
+        let mut command = std::process::Command::new("notify-send");
+        command.args(["--app-name=jenkins-cli", &format!("Jenkins: {}", title), body]);
+        command
+    } else {
+        return Ok(());
+    };
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(anyhow!(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    Ok(())
+}
+
+/// Notify that a build finished, as configured in `[config.notify]`.
+/// Failures are printed as warnings; polling that was cancelled is not notified.
+pub async fn notify_build_finished(client: &JenkinsClient, config: &NotifyConfig, build_url: &str) {
+    let build = match client.get_build_by_url(build_url).await {
+        Ok(build) if !build.building => build,
+        Ok(_) => return,
+        Err(e) => {
+            eprintln!("{}", t!("notify-failed", "error" => e.to_string()));
+            return;
+        }
+    };
+    let notification = BuildNotification::from_build(build_url, &build);
+
+    if config.bell.unwrap_or(false) {
+        print!("\x07");
+        let _ = std::io::stdout().flush();
+    }
+    if config.desktop.unwrap_or(false) {
+        if let Err(e) = send_desktop_notification(&notification.title(), &notification.summary()) {
+            eprintln!("{}", t!("notify-failed", "error" => e.to_string()));
+        }
+    }
+    if let Some(url) = config.webhook.as_deref().filter(|url| !url.is_empty()) {
+        if let Err(e) = send_webhook(url, &notification).await {
+            eprintln!("{}", t!("notify-failed", "error" => e.to_string()));
+        }
+    }
+}
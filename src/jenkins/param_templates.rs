@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use crate::constants::ParamType;
+use crate::jenkins::presets::{ParameterPreset, PresetSource};
+use crate::jenkins::{JenkinsJobParameter, ParamInfo};
+use crate::models::{GlobalConfig, JenkinsConfig, ParamTemplate};
+
+/// Whether the job defines every parameter of the template.
+pub fn template_applies(template: &ParamTemplate, definitions: &[JenkinsJobParameter]) -> bool {
+    !template.params.is_empty()
+        && template
+            .params
+            .keys()
+            .all(|name| definitions.iter().any(|definition| &definition.name == name))
+}
+
+/// The template as a read-only preset, typed like the job's parameters.
+pub fn template_preset(template: &ParamTemplate, definitions: &[JenkinsJobParameter]) -> ParameterPreset {
+    let params: HashMap<String, ParamInfo> = template
+        .params
+        .iter()
+        .map(|(name, value)| {
+            let r#type = definitions
+                .iter()
+                .find(|definition| &definition.name == name)
+                .and_then(|definition| definition.param_type.clone())
+                .unwrap_or(ParamType::String);
+            (
+                name.clone(),
+                ParamInfo {
+                    value: value.clone(),
+                    r#type,
+                },
+            )
+        })
+        .collect();
+    ParameterPreset {
+        name: template.name.clone(),
+        description: template.description.clone(),
+        params,
+        source: Some(PresetSource::Template),
+        ..Default::default()
+    }
+}
+
+/// Templates of the service and the global configuration that apply to a job, by name.
+/// A service template overrides the global template with the same name.
+pub fn matching_templates(
+    global: Option<&GlobalConfig>,
+    service: &JenkinsConfig,
+    definitions: &[JenkinsJobParameter],
+) -> Vec<ParameterPreset> {
+    let global_templates = global.map(|global| global.templates.as_slice()).unwrap_or_default();
+    let mut templates: Vec<&ParamTemplate> = service.templates.iter().collect();
+    for template in global_templates {
+        if !templates.iter().any(|existing| existing.name == template.name) {
+            templates.push(template);
+        }
+    }
+    let mut presets: Vec<ParameterPreset> = templates
+        .into_iter()
+        .filter(|template| template_applies(template, definitions))
+        .map(|template| template_preset(template, definitions))
+        .collect();
+    presets.sort_by(|a, b| a.name.cmp(&b.name));
+    presets
+}
@@ -0,0 +1,436 @@
+use anyhow::{anyhow, Context, Result};
+use colored::Colorize;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+use crate::i18n::macros::t;
+use crate::jenkins::{
+    batch::{self, BatchJob, BatchOutcome, PreparedJob},
+    builds,
+    captures::{self, CaptureSource},
+    client::JenkinsClient,
+    template, validation, Event,
+};
+use crate::spinner;
+use crate::utils::{delay, format_url};
+
+/// Ordered stages of jobs, e.g.
+/// ```toml
+/// [params]                  # shared by all jobs
+/// GIT_BRANCH = "release/1.2"
+///
+/// [[stages]]
+/// name = "build"
+/// jobs = [
+///   { url = "http://jenkins.example.com/job/backend/", preset = "prod", captures = { IMAGE_TAG = { log = "tag: (\\S+)" } } },
+///   { url = "http://jenkins.example.com/job/frontend/" },
+/// ]
+///
+/// [[stages]]
+/// name = "deploy-sit"
+/// when = "success"          # success (default) | unstable | failure | always
+/// jobs = [{ url = "http://jenkins.example.com/job/deploy/", params = { ENV = "sit", TAG = "{{capture.IMAGE_TAG}}" } }]
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PipelineManifest {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub params: HashMap<String, toml::Value>,
+    #[serde(default)]
+    pub stages: Vec<Stage>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Stage {
+    pub name: String,
+    #[serde(default)]
+    pub when: StageCondition,
+    #[serde(default)]
+    pub jobs: Vec<BatchJob>,
+}
+
+/// Gate on the result of the previous stage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StageCondition {
+    /// Previous stage succeeded (always true for the first stage).
+    #[default]
+    Success,
+    /// Previous stage succeeded or was unstable.
+    Unstable,
+    /// Previous stage failed.
+    Failure,
+    /// Run regardless of the previous stage.
+    Always,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageStatus {
+    Success,
+    Unstable,
+    Failure,
+    Skipped,
+}
+
+impl StageStatus {
+    /// Status of a stage from the results of its jobs; the worst result wins.
+    /// A job with an error (e.g. a value that could not be captured) fails the stage.
+    pub fn from_outcomes(outcomes: &[BatchOutcome]) -> Self {
+        if outcomes.iter().any(|outcome| outcome.error.is_some()) {
+            StageStatus::Failure
+        } else if outcomes.iter().all(|outcome| outcome.is_success()) {
+            StageStatus::Success
+        } else if outcomes
+            .iter()
+            .all(|outcome| outcome.is_success() || outcome.result == "UNSTABLE")
+        {
+            StageStatus::Unstable
+        } else {
+            StageStatus::Failure
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StageStatus::Success => "SUCCESS",
+            StageStatus::Unstable => "UNSTABLE",
+            StageStatus::Failure => "FAILURE",
+            StageStatus::Skipped => "SKIPPED",
+        }
+    }
+}
+
+impl StageCondition {
+    /// Whether a stage runs after a stage with `previous` status (`None` for the first stage).
+    pub fn allows(&self, previous: Option<StageStatus>) -> bool {
+        match (self, previous) {
+            (StageCondition::Always, _) => true,
+            (StageCondition::Failure, previous) => previous == Some(StageStatus::Failure),
+            (_, None) => true,
+            (StageCondition::Success, Some(previous)) => previous == StageStatus::Success,
+            (StageCondition::Unstable, Some(previous)) => {
+                matches!(previous, StageStatus::Success | StageStatus::Unstable)
+            }
+        }
+    }
+}
+
+/// Read a pipeline manifest file.
+pub fn load_pipeline(path: &Path) -> Result<PipelineManifest> {
+    let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let manifest: PipelineManifest =
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))?;
+    if manifest.stages.is_empty() {
+        anyhow::bail!("No [[stages]] in {}", path.display());
+    }
+    if let Some(stage) = manifest.stages.iter().find(|stage| stage.jobs.is_empty()) {
+        anyhow::bail!("Stage \"{}\" has no jobs", stage.name);
+    }
+    check_captures(&manifest)?;
+    Ok(manifest)
+}
+
+/// Check that every `{{capture.NAME}}` is captured by an earlier stage
+/// (or by any stage for shared parameters, which only apply to jobs defining them).
+fn check_captures(manifest: &PipelineManifest) -> Result<()> {
+    fn used_captures(params: &HashMap<String, toml::Value>) -> Vec<String> {
+        params
+            .values()
+            .flat_map(|value| {
+                let value = batch::toml_value_to_string(value);
+                template::placeholders(&value)
+                    .into_iter()
+                    .filter_map(|expression| expression.strip_prefix(template::CAPTURE_PREFIX))
+                    .map(|name| name.to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    let all_captured: HashSet<&str> = manifest
+        .stages
+        .iter()
+        .flat_map(|stage| stage.jobs.iter())
+        .flat_map(|job| job.captures.keys().map(|name| name.as_str()))
+        .collect();
+    if let Some(name) = used_captures(&manifest.params)
+        .into_iter()
+        .find(|name| !all_captured.contains(name.as_str()))
+    {
+        anyhow::bail!("[params] uses capture \"{}\" that no stage captures", name);
+    }
+
+    let mut captured: HashSet<&str> = HashSet::new();
+    for stage in &manifest.stages {
+        for job in &stage.jobs {
+            if let Some(name) = used_captures(&job.params)
+                .into_iter()
+                .find(|name| !captured.contains(name.as_str()))
+            {
+                anyhow::bail!(
+                    "Stage \"{}\" uses capture \"{}\" before an earlier stage captures it",
+                    stage.name,
+                    name
+                );
+            }
+        }
+        captured.extend(
+            stage
+                .jobs
+                .iter()
+                .flat_map(|job| job.captures.keys().map(|name| name.as_str())),
+        );
+    }
+    Ok(())
+}
+
+/// A stage with its jobs ready to be triggered.
+#[derive(Debug, Clone)]
+pub struct PreparedStage {
+    pub name: String,
+    pub when: StageCondition,
+    pub jobs: Vec<PreparedJob>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StageReport {
+    pub name: String,
+    pub status: StageStatus,
+    pub outcomes: Vec<BatchOutcome>,
+}
+
+/// Run the stages in order; jobs of a stage run in parallel.
+/// Values captured from finished builds are filled into the parameters of later stages.
+pub async fn run_pipeline(stages: Vec<PreparedStage>) -> Vec<StageReport> {
+    let mut reports: Vec<StageReport> = Vec::new();
+    let mut captured: HashMap<String, String> = HashMap::new();
+    for stage in stages {
+        let previous = reports.last().map(|report| report.status);
+        println!();
+        if !stage.when.allows(previous) {
+            println!(
+                "{}",
+                t!("pipeline-stage-skipped", "name" => stage.name.clone()).dimmed()
+            );
+            reports.push(StageReport {
+                name: stage.name,
+                status: StageStatus::Skipped,
+                outcomes: Vec::new(),
+            });
+            continue;
+        }
+
+        println!("{}", format!("==> {}", stage.name).bold().cyan());
+        let mut jobs = Vec::new();
+        let mut not_triggered = Vec::new();
+        for mut job in stage.jobs {
+            match template::render_params(&job.params, &captured) {
+                Ok(params) => {
+                    let violations = job.validator.check_all(&params);
+                    if !violations.is_empty() {
+                        eprintln!("{} {}", job.name.bold(), t!("params-invalid").red());
+                        validation::print_violations(&violations);
+                        let mut outcome = BatchOutcome::new(&job.name);
+                        outcome.error = Some(
+                            violations
+                                .iter()
+                                .map(|(name, violation)| format!("{}: {}", name, violation))
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                        );
+                        not_triggered.push(outcome);
+                        continue;
+                    }
+                    job.params = params;
+                    jobs.push(job);
+                }
+                Err(e) => {
+                    eprintln!("{} {}", job.name.bold(), e.to_string().red());
+                    let mut outcome = BatchOutcome::new(&job.name);
+                    outcome.error = Some(e.to_string());
+                    not_triggered.push(outcome);
+                }
+            }
+        }
+
+        let clients: Vec<Arc<JenkinsClient>> = jobs.iter().map(|job| Arc::clone(&job.client)).collect();
+        let capture_sources: Vec<BTreeMap<String, CaptureSource>> =
+            jobs.iter().map(|job| job.captures.clone()).collect();
+        let mut outcomes = match jobs.len() {
+            0 => Vec::new(),
+            1 => {
+                let job = jobs.into_iter().next().expect("stage has one job");
+                vec![run_job_streamed(job).await]
+            }
+            _ => {
+                let outcomes = batch::run_batch(jobs).await;
+                batch::print_batch_summary(&clients, &outcomes).await;
+                outcomes
+            }
+        };
+        for ((outcome, sources), client) in outcomes.iter_mut().zip(&capture_sources).zip(&clients) {
+            if sources.is_empty() || !matches!(outcome.result.as_str(), "SUCCESS" | "UNSTABLE") {
+                continue;
+            }
+            let Some(build_url) = outcome.build_url.clone() else {
+                continue;
+            };
+            match captures::collect_captures(client, &build_url, sources).await {
+                Ok(values) => {
+                    for name in sources.keys() {
+                        println!(
+                            "{}",
+                            t!("pipeline-captured", "name" => name.as_str(), "value" => values[name].as_str()).dimmed()
+                        );
+                    }
+                    captured.extend(values);
+                }
+                Err(e) => {
+                    eprintln!(
+                        "{}",
+                        t!("pipeline-capture-failed", "name" => outcome.name.clone(), "error" => e.to_string()).red()
+                    );
+                    outcome.error = Some(e.to_string());
+                }
+            }
+        }
+        outcomes.extend(not_triggered);
+        reports.push(StageReport {
+            name: stage.name,
+            status: StageStatus::from_outcomes(&outcomes),
+            outcomes,
+        });
+    }
+    reports
+}
+
+/// Trigger a single job and stream its console output.
+async fn run_job_streamed(job: PreparedJob) -> BatchOutcome {
+    let client = &job.client;
+    let mut outcome = BatchOutcome::new(&job.name);
+    println!("{} {}", job.name.bold(), job.job_url.underline().blue());
+    let result: Result<()> = async {
+        let queue_url = client.trigger_build(&job.job_url, job.params.clone()).await?;
+        let spinner = spinner::Spinner::new(t!("polling-queue-item"));
+        let number = loop {
+            match client.get_queue_executable(&queue_url).await {
+                Ok(Some(number)) => break number,
+                Ok(None) => delay(2000).await,
+                Err(e) => {
+                    spinner.finish_with_message(String::new());
+                    return Err(e);
+                }
+            }
+        };
+        let build_url = format_url(&format!("{}/{}/", job.job_url, number));
+        spinner.finish_with_message(format!("Build URL: {}", build_url.underline().blue()));
+        outcome.build_url = Some(build_url.clone());
+
+        // No cancel events: the pipeline runs until every stage is done.
+        let (_event_sender, mut event_receiver) = mpsc::channel::<Event>(1);
+        let poll_error = client
+            .poll_build_status(&build_url, &mut event_receiver)
+            .await
+            .err()
+            .map(|e| anyhow!(console::strip_ansi_codes(&e.to_string()).into_owned()));
+        let build = match client.get_build_by_url(&build_url).await {
+            Ok(build) => build,
+            Err(e) => return Err(poll_error.unwrap_or(e)),
+        };
+        if build.building || build.result.is_none() {
+            // monitoring stopped before the build finished
+            return Err(poll_error.unwrap_or_else(|| anyhow!("{}", build.status())));
+        }
+        outcome.result = build.status().to_string();
+        outcome.duration = Some(build.duration);
+        // A failed build is reported by its result, other errors (e.g. downstream builds) are kept
+        match poll_error {
+            Some(e) if outcome.is_success() => Err(e),
+            _ => Ok(()),
+        }
+    }
+    .await;
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        outcome.error = Some(e.to_string());
+    }
+    outcome
+}
+
+/// Print a table with the result of each stage and job.
+pub fn print_pipeline_summary(name: Option<&str>, reports: &[StageReport]) {
+    println!();
+    println!(
+        "{}",
+        match name {
+            Some(name) => format!("{}: {}", t!("pipeline-summary-title"), name),
+            None => t!("pipeline-summary-title"),
+        }
+        .bold()
+    );
+
+    let mut rows: Vec<[String; 5]> = Vec::new();
+    for report in reports {
+        if report.outcomes.is_empty() {
+            rows.push([
+                report.name.clone(),
+                "-".to_string(),
+                report.status.as_str().to_string(),
+                "-".to_string(),
+                String::new(),
+            ]);
+        }
+        for outcome in &report.outcomes {
+            rows.push([
+                report.name.clone(),
+                outcome.name.clone(),
+                outcome.result.clone(),
+                outcome
+                    .duration
+                    .map(builds::format_duration)
+                    .unwrap_or_else(|| "-".to_string()),
+                outcome.build_url.clone().unwrap_or_default(),
+            ]);
+        }
+    }
+
+    let headers = [
+        t!("pipeline-column-stage"),
+        t!("pipeline-column-job"),
+        t!("pipeline-column-result"),
+        t!("pipeline-column-duration"),
+        t!("pipeline-column-url"),
+    ];
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain([headers[column].chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let pad = |value: &str, width: usize| format!("{}{}", value, " ".repeat(width - value.chars().count()));
+
+    let header_line: Vec<String> = headers.iter().zip(&widths).map(|(h, w)| pad(h, *w)).collect();
+    println!("{}", header_line.join("  ").dimmed());
+    for row in &rows {
+        let result = match row[2].as_str() {
+            "SUCCESS" => pad(&row[2], widths[2]).green(),
+            "UNSTABLE" | "SKIPPED" | "ABORTED" => pad(&row[2], widths[2]).yellow(),
+            _ => pad(&row[2], widths[2]).red(),
+        };
+        println!(
+            "{}  {}  {}  {}  {}",
+            pad(&row[0], widths[0]),
+            pad(&row[1], widths[1]),
+            result,
+            pad(&row[3], widths[3]),
+            row[4].dimmed()
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use anyhow::{anyhow, Result};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use colored::Colorize;
+use std::collections::HashMap;
+
+use crate::constants::ParamType;
+use crate::i18n::macros::t;
+use crate::jenkins::{builds::BuildSummary, presets, ParamInfo};
+use crate::models::{FreezeAction, FreezeWindow, GlobalConfig, JenkinsConfig};
+use crate::prompt;
+
+/// Whether a job needs the typed confirmation before triggering.
+/// The first matching `jobs` entry setting `protected` overrides the service setting.
+pub fn is_protected(config: &JenkinsConfig, job_name: &str, display_name: &str) -> bool {
+    config
+        .job_configs(job_name, display_name)
+        .find_map(|job_config| job_config.protected)
+        .or(config.protected)
+        .unwrap_or(false)
+}
+
+/// Whether the typed confirmation matches the job name (surrounding whitespace is ignored).
+pub fn typed_name_matches(input: &str, job_name: &str) -> bool {
+    input.trim() == job_name
+}
+
+/// Parameters of the last build, typed like `params`.
+/// Password values are hidden by Jenkins, so they count as unchanged.
+pub fn last_build_params(build: &BuildSummary, params: &HashMap<String, ParamInfo>) -> HashMap<String, ParamInfo> {
+    let mut last_params = HashMap::new();
+    for (name, value) in &build.parameters {
+        let param_type = params
+            .get(name)
+            .map(|param| param.r#type.clone())
+            .unwrap_or(ParamType::String);
+        let value = match value {
+            Some(value) => value.clone(),
+            None => match params.get(name) {
+                Some(param) if param.r#type == ParamType::Password => param.value.clone(),
+                _ => continue,
+            },
+        };
+        last_params.insert(
+            name.clone(),
+            ParamInfo {
+                value,
+                r#type: param_type,
+            },
+        );
+    }
+    last_params
+}
+
+/// Print the protected job warning and the parameter changes since the last build.
+pub fn print_protected_summary(
+    service_name: &str,
+    job_url: &str,
+    params: &HashMap<String, ParamInfo>,
+    last_build: Option<&BuildSummary>,
+) {
+    println!();
+    println!("{}", t!("protected-job-title", "service" => service_name).red().bold());
+    println!("{}", job_url.underline().blue());
+    println!();
+    let Some(build) = last_build else {
+        println!("{}", t!("protected-no-last-build").yellow());
+        presets::print_params(params);
+        return;
+    };
+    println!(
+        "{}",
+        t!("protected-diff-title", "number" => build.number.to_string()).bold()
+    );
+    let changes = presets::diff_params(&last_build_params(build, params), params);
+    if changes.is_empty() {
+        println!("{}", t!("protected-no-changes").green());
+    } else {
+        presets::print_param_diff(&changes);
+    }
+}
+
+/// Ask the user to type the job name. Returns false on a mismatch or Ctrl+C.
+pub fn confirm_job_name(job_name: &str) -> bool {
+    println!();
+    let Some(input) = prompt::string_input(&t!("protected-type-name", "name" => job_name), "", Some(true)) else {
+        return false;
+    };
+    if typed_name_matches(&input, job_name) {
+        return true;
+    }
+    eprintln!("{}", t!("protected-name-mismatch").red());
+    false
+}
+
+fn parse_time(value: &str) -> Result<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").map_err(|_| anyhow!("invalid time `{}`, expected HH:MM", value))
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| anyhow!("invalid date `{}`, expected YYYY-MM-DD", value))
+}
+
+/// Whether a freeze window covers `now`. A window wrapping past midnight (`to <= from`) belongs
+/// to the day it starts on, so `days` and `start`/`end` are checked against the previous day after midnight.
+pub fn freeze_active(window: &FreezeWindow, now: NaiveDateTime) -> Result<bool> {
+    let time = now.time();
+    let from = window.from.as_deref().map(parse_time).transpose()?;
+    let to = window.to.as_deref().map(parse_time).transpose()?;
+    let (in_window, date) = match (from, to) {
+        (Some(from), Some(to)) if to <= from && time < to => (true, now.date() - chrono::Duration::days(1)),
+        (Some(from), Some(to)) if to <= from => (time >= from, now.date()),
+        (from, to) => (
+            from.is_none_or(|from| time >= from) && to.is_none_or(|to| time < to),
+            now.date(),
+        ),
+    };
+    if let Some(start) = window.start.as_deref() {
+        if date < parse_date(start)? {
+            return Ok(false);
+        }
+    }
+    if let Some(end) = window.end.as_deref() {
+        if date > parse_date(end)? {
+            return Ok(false);
+        }
+    }
+    if !window.days.is_empty() {
+        let mut days = Vec::new();
+        for day in &window.days {
+            let weekday: Weekday = day.trim().parse().map_err(|_| anyhow!("invalid weekday `{}`", day))?;
+            days.push(weekday);
+        }
+        if !days.contains(&date.weekday()) {
+            return Ok(false);
+        }
+    }
+    Ok(in_window)
+}
+
+/// Whether a freeze window applies to a job (all jobs when `jobs` is empty).
+pub fn freeze_applies_to(window: &FreezeWindow, job_name: &str, display_name: &str) -> bool {
+    window.jobs.is_empty()
+        || window.jobs.iter().any(|pattern| match regex::Regex::new(pattern) {
+            Ok(re) => re.is_match(job_name) || re.is_match(display_name),
+            Err(_) => pattern == job_name,
+        })
+}
+
+/// Short description of a freeze window for messages, e.g. `fri 16:00-` or `2026-12-24..2027-01-02`.
+pub fn freeze_label(window: &FreezeWindow) -> String {
+    if let Some(name) = &window.name {
+        return name.clone();
+    }
+    let mut parts = Vec::new();
+    if window.start.is_some() || window.end.is_some() {
+        parts.push(format!(
+            "{}..{}",
+            window.start.as_deref().unwrap_or_default(),
+            window.end.as_deref().unwrap_or_default()
+        ));
+    }
+    if !window.days.is_empty() {
+        parts.push(window.days.join(","));
+    }
+    if window.from.is_some() || window.to.is_some() {
+        parts.push(format!(
+            "{}-{}",
+            window.from.as_deref().unwrap_or_default(),
+            window.to.as_deref().unwrap_or_default()
+        ));
+    }
+    parts.join(" ")
+}
+
+/// Freeze windows of the global and service configuration that cover a job at `now`.
+pub fn active_freezes<'a>(
+    global: Option<&'a GlobalConfig>,
+    service: &'a JenkinsConfig,
+    job_name: &str,
+    display_name: &str,
+    now: NaiveDateTime,
+) -> Result<Vec<&'a FreezeWindow>> {
+    let mut active = Vec::new();
+    let windows = global
+        .map(|global| global.freeze.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .chain(&service.freeze);
+    for window in windows {
+        if !freeze_applies_to(window, job_name, display_name) {
+            continue;
+        }
+        let is_active = freeze_active(window, now).map_err(|e| anyhow!("{}: {}", freeze_label(window), e))?;
+        if is_active {
+            active.push(window);
+        }
+    }
+    Ok(active)
+}
+
+/// Check the freeze windows right before triggering a job, printing active ones.
+/// Returns false when a blocking window is active or the configuration is invalid.
+pub fn check_freeze(
+    global: Option<&GlobalConfig>,
+    service: &JenkinsConfig,
+    job_name: &str,
+    display_name: &str,
+) -> bool {
+    let now = chrono::Local::now().naive_local();
+    let active = match active_freezes(global, service, job_name, display_name, now) {
+        Ok(active) => active,
+        Err(e) => {
+            eprintln!("{}", t!("freeze-invalid", "error" => e.to_string()).red());
+            return false;
+        }
+    };
+    let mut allowed = true;
+    for window in active {
+        let label = freeze_label(window);
+        match window.action {
+            FreezeAction::Warn => {
+                println!(
+                    "{}",
+                    t!("freeze-warning", "name" => label, "job" => job_name.to_string()).yellow()
+                );
+            }
+            FreezeAction::Block => {
+                eprintln!(
+                    "{}",
+                    t!("freeze-blocked", "name" => label, "job" => job_name.to_string()).red()
+                );
+                allowed = false;
+            }
+        }
+    }
+    allowed
+}
@@ -0,0 +1,307 @@
+use anyhow::{anyhow, Context, Result};
+use colored::Colorize;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::constants::ParamType;
+use crate::i18n::macros::t;
+use crate::jenkins::presets::{JobPresetIdentity, JobPresets, ParameterPreset, PresetSource, PresetStore};
+use crate::models::JenkinsConfig;
+use crate::utils::simplify_url;
+
+const CURRENT_EXPORT_VERSION: u32 = 1;
+
+/// Presets shared between teammates (`jenkins presets export/import`).
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct PresetExport {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
+    #[serde(default)]
+    pub jobs: Vec<ExportedJob>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ExportedJob {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_name: Option<String>, // name of the service at export, used to map hostnames on import
+    pub service_url: String,
+    pub job_url: String,
+    pub job_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub presets: Vec<ParameterPreset>,
+}
+
+/// Which jobs are exported; unset fields match everything.
+#[derive(Debug, Default)]
+pub struct ExportFilter {
+    pub service_url: Option<String>, // base URL of a configured service
+    pub job: Option<Regex>,          // matched against the job name or display name
+}
+
+/// What to do when an imported preset has the name of a different local preset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictAction {
+    Skip,
+    Overwrite,
+    Rename,
+}
+
+impl std::str::FromStr for ConflictAction {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "skip" => Ok(ConflictAction::Skip),
+            "overwrite" => Ok(ConflictAction::Overwrite),
+            "rename" => Ok(ConflictAction::Rename),
+            _ => Err(anyhow!("invalid conflict action `{}`", value)),
+        }
+    }
+}
+
+/// Counts of an import, for the summary.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub added: usize,
+    pub overwritten: usize,
+    pub renamed: usize,
+    pub skipped: usize,
+    pub unchanged: usize, // identical presets that already exist
+}
+
+/// Preset as shared: password values and personal usage data removed.
+fn shared_preset(preset: &ParameterPreset) -> ParameterPreset {
+    let mut preset = preset.clone();
+    preset.params.retain(|_, param| param.r#type != ParamType::Password);
+    preset.last_used_at = None;
+    preset
+}
+
+/// Presets of the jobs matching `filter`. Password parameters are left out.
+pub fn export_presets(store: &PresetStore, services: &[JenkinsConfig], filter: &ExportFilter) -> PresetExport {
+    let jobs = store
+        .jobs
+        .iter()
+        .filter(|job| !job.presets.is_empty())
+        .filter(|job| {
+            filter
+                .service_url
+                .as_deref()
+                .is_none_or(|service_url| simplify_url(&job.service_url) == simplify_url(service_url))
+        })
+        .filter(|job| {
+            filter.job.as_ref().is_none_or(|job_re| {
+                job_re.is_match(&job.job_name) || job.display_name.as_deref().is_some_and(|name| job_re.is_match(name))
+            })
+        })
+        .map(|job| ExportedJob {
+            service_name: services
+                .iter()
+                .find(|service| simplify_url(&service.url) == simplify_url(&job.service_url))
+                .map(|service| service.name.clone()),
+            service_url: job.service_url.clone(),
+            job_url: job.job_url.clone(),
+            job_name: job.job_name.clone(),
+            display_name: job.display_name.clone(),
+            presets: job.presets.iter().map(shared_preset).collect(),
+        })
+        .collect();
+    PresetExport {
+        version: Some(CURRENT_EXPORT_VERSION),
+        jobs,
+    }
+}
+
+pub fn load_export(path: &Path) -> Result<PresetExport> {
+    let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// The local service for an exported job: `target` if given, then the service with the same URL,
+/// then the service with the same name (a teammate's hostname for the same Jenkins).
+pub fn resolve_service<'a>(
+    job: &ExportedJob,
+    services: &'a [JenkinsConfig],
+    target: Option<&'a JenkinsConfig>,
+) -> Option<&'a JenkinsConfig> {
+    target
+        .or_else(|| {
+            services
+                .iter()
+                .find(|service| simplify_url(&service.url) == simplify_url(&job.service_url))
+        })
+        .or_else(|| {
+            let name = job.service_name.as_deref()?;
+            services.iter().find(|service| service.name == name)
+        })
+}
+
+/// Move a job URL from the exported service to a local one,
+/// e.g. `jenkins.corp:8080/job/app` to `10.0.0.5:8080/job/app`.
+pub fn rewrite_job_url(job_url: &str, from_service_url: &str, to_service_url: &str) -> String {
+    let job_url = simplify_url(job_url);
+    match job_url.strip_prefix(&simplify_url(from_service_url)) {
+        Some(path) => format!("{}{}", simplify_url(to_service_url), path),
+        None => job_url,
+    }
+}
+
+/// A preset name not used by the job yet, e.g. `release-2`.
+pub fn unique_preset_name(store: &PresetStore, identity: &JobPresetIdentity, name: &str) -> String {
+    (2..)
+        .map(|n| format!("{}-{}", name, n))
+        .find(|candidate| !store.preset_exists(identity, candidate))
+        .expect("unbounded candidates")
+}
+
+/// Add the exported presets to the store (not saved). `on_conflict` is asked when a preset
+/// with the same name but different parameters exists; `None` stops the import.
+/// Fails before changing the store when a job's service is not configured.
+pub fn import_presets<F>(
+    store: &mut PresetStore,
+    export: &PresetExport,
+    services: &[JenkinsConfig],
+    target: Option<&JenkinsConfig>,
+    mut on_conflict: F,
+) -> Result<Option<ImportSummary>>
+where
+    F: FnMut(&JobPresetIdentity, &ParameterPreset) -> Option<ConflictAction>,
+{
+    let mut identities = Vec::new();
+    for job in &export.jobs {
+        let service = resolve_service(job, services, target).ok_or_else(|| {
+            anyhow!(
+                "no configured service for {} ({}), use --service NAME",
+                job.service_url,
+                job.service_name.as_deref().unwrap_or("-")
+            )
+        })?;
+        identities.push(JobPresetIdentity {
+            service_url: service.url.clone(),
+            job_url: rewrite_job_url(&job.job_url, &job.service_url, &service.url),
+            job_name: job.job_name.clone(),
+            display_name: job.display_name.clone(),
+        });
+    }
+
+    let mut summary = ImportSummary::default();
+    for (job, identity) in export.jobs.iter().zip(&identities) {
+        for preset in &job.presets {
+            let mut preset = shared_preset(preset);
+            let existing = store.find_preset(identity, &preset.name);
+            let action = match &existing {
+                None => None,
+                Some(existing) if shared_preset(existing).params == preset.params => {
+                    summary.unchanged += 1;
+                    continue;
+                }
+                Some(_) => match on_conflict(identity, &preset) {
+                    Some(action) => Some(action),
+                    None => return Ok(None),
+                },
+            };
+            match action {
+                None => summary.added += 1,
+                Some(ConflictAction::Skip) => {
+                    summary.skipped += 1;
+                    continue;
+                }
+                Some(ConflictAction::Overwrite) => {
+                    // Keep the local passwords, they are never shared
+                    for (name, param) in existing.into_iter().flat_map(|existing| existing.params) {
+                        if param.r#type == ParamType::Password {
+                            preset.params.entry(name).or_insert(param);
+                        }
+                    }
+                    summary.overwritten += 1;
+                }
+                Some(ConflictAction::Rename) => {
+                    preset.name = unique_preset_name(store, identity, &preset.name);
+                    summary.renamed += 1;
+                }
+            }
+            store.insert_preset(identity, preset);
+        }
+    }
+    Ok(Some(summary))
+}
+
+/// Files of a preset source: the file itself, or the `*.toml` files of a directory by name.
+/// A leading `~/` is the home directory, relative paths are relative to the working directory.
+pub fn preset_source_files(source: &str) -> Result<Vec<PathBuf>> {
+    let path = match source.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().context("Failed to get home directory")?.join(rest),
+        None => PathBuf::from(source),
+    };
+    if !path.is_dir() {
+        return Ok(vec![path]);
+    }
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|file| file.is_file() && file.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Read-only presets of an exported file, moved to the matching local service.
+/// Jobs without a configured service keep their URLs.
+pub fn shared_job_presets(export: &PresetExport, source: &str, services: &[JenkinsConfig]) -> Vec<JobPresets> {
+    export
+        .jobs
+        .iter()
+        .map(|job| {
+            let service_url = resolve_service(job, services, None)
+                .map(|service| service.url.clone())
+                .unwrap_or_else(|| job.service_url.clone());
+            JobPresets {
+                job_url: rewrite_job_url(&job.job_url, &job.service_url, &service_url),
+                service_url,
+                job_name: job.job_name.clone(),
+                display_name: job.display_name.clone(),
+                last_preset: None,
+                presets: job
+                    .presets
+                    .iter()
+                    .map(|preset| ParameterPreset {
+                        source: Some(PresetSource::Shared(source.to_string())),
+                        last_used_at: None,
+                        ..preset.clone()
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// Load the `preset_sources` of the configuration. Unreadable sources are reported and skipped.
+pub fn load_shared_presets(sources: &[String], services: &[JenkinsConfig]) -> Vec<JobPresets> {
+    let mut shared = Vec::new();
+    for source in sources {
+        let files = match preset_source_files(source) {
+            Ok(files) => files,
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    t!("preset-source-failed", "source" => source.to_string(), "error" => format!("{:#}", e)).yellow()
+                );
+                continue;
+            }
+        };
+        for file in files {
+            let label = file.display().to_string();
+            match load_export(&file) {
+                Ok(export) => shared.extend(shared_job_presets(&export, &label, services)),
+                Err(e) => eprintln!(
+                    "{}",
+                    t!("preset-source-failed", "source" => label.clone(), "error" => format!("{:#}", e)).yellow()
+                ),
+            }
+        }
+    }
+    shared
+}
@@ -1,21 +1,25 @@
 use anyhow::{Context, Result};
-use colored::Colorize;
+use colored::{ColoredString, Colorize};
 use dialoguer::{theme::ColorfulTheme, FuzzySelect};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use std::fs::{self, File, OpenOptions};
-use std::io::{BufReader, BufWriter, Read, Write};
-use std::path::PathBuf;
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
 
 use crate::config::DATA_DIR;
 use crate::constants::{ParamType, MASKED_PASSWORD};
 use crate::i18n::macros::t;
-use crate::jenkins::{JenkinsJobParameter, ParamInfo};
+use crate::jenkins::{
+    builds::BuildSummary, client::JenkinsClient, validation::ParamValidator, JenkinsJobParameter, ParamInfo,
+};
+use crate::migrations::{migrate_presets, NewerVersionError, CURRENT_PRESETS_VERSION};
+use crate::models::BranchConfig;
 use crate::prompt;
+use crate::storage;
 use crate::utils::{self, current_timestamp};
 
 pub const PRESETS_FILE: &str = "presets.toml";
-const CURRENT_PRESETS_VERSION: u32 = 1;
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct PresetStore {
@@ -24,9 +28,13 @@ pub struct PresetStore {
     pub file_path: PathBuf,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub version: Option<u32>,
+    #[serde(skip)]
+    pub shared: Vec<JobPresets>, // read-only presets of `preset_sources`
+    #[serde(skip)]
+    pub saved: Vec<JobPresets>, // jobs of the file when loaded or last saved, merged with on save
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 pub struct JobPresets {
     pub service_url: String,
     pub job_url: String,
@@ -38,15 +46,26 @@ pub struct JobPresets {
     pub presets: Vec<ParameterPreset>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 pub struct ParameterPreset {
     pub name: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
     pub params: HashMap<String, ParamInfo>,
     pub created_at: Option<i64>,
     pub updated_at: Option<i64>,
     pub last_used_at: Option<i64>,
+    #[serde(skip)]
+    pub source: Option<PresetSource>, // `None` for personal presets
+}
+
+/// Where a read-only preset comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PresetSource {
+    Shared(String), // file of `preset_sources`
+    Template,       // parameter template of the configuration
 }
 
 #[derive(Debug, Clone)]
@@ -62,6 +81,8 @@ pub enum ParameterSource {
     Preset(ParameterPreset),
     LastBuild,
     JenkinsDefault,
+    BuildHistory,
+    Rebuild(Box<BuildSummary>),
     ManagePresets,
 }
 
@@ -78,14 +99,42 @@ pub enum PresetBuildAction {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum PresetManageAction {
-    Delete,
+    Show,
+    EditParam,
+    Description,
+    Tags,
+    Duplicate,
     Rename,
+    Delete,
     Back,
 }
 
+/// How a preset parameter relates to the job's current definitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamStatus {
+    Current,
+    Obsolete, // in the preset, removed from the job
+    New,      // defined by the job, missing in the preset
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamComparison {
+    pub name: String,
+    pub preset_value: Option<String>,
+    pub job_default: Option<String>,
+    pub r#type: ParamType,
+    pub status: ParamStatus,
+}
+
 impl PresetStore {
     pub fn new() -> Result<Self> {
         let file_path = DATA_DIR.join(PRESETS_FILE);
+        if let Err(e) = migrate_presets(&file_path) {
+            // A newer presets file must not be overwritten by this version
+            if e.is::<NewerVersionError>() {
+                return Err(e);
+            }
+        }
         if !file_path.exists() {
             fs::File::create(&file_path).context("Failed to create presets file")?;
         }
@@ -94,55 +143,87 @@ impl PresetStore {
             jobs: vec![],
             version: Some(CURRENT_PRESETS_VERSION),
             file_path,
+            shared: vec![],
+            saved: vec![],
         };
         store.load_presets()?;
         Ok(store)
     }
 
-    pub fn load_presets(&mut self) -> Result<()> {
-        let file = File::open(&self.file_path).context("Failed to open presets file")?;
-        let metadata = file.metadata().context("Failed to get presets file metadata")?;
-        if metadata.len() == 0 {
-            self.jobs = vec![];
-            self.version = Some(CURRENT_PRESETS_VERSION);
-            return Ok(());
+    /// Read the presets file; `None` when it is missing or empty.
+    /// A file that cannot be parsed is moved aside with a warning.
+    fn read_file(file_path: &Path) -> Result<Option<PresetStore>> {
+        if !file_path.exists() {
+            return Ok(None);
         }
-
+        let file = File::open(file_path).context("Failed to open presets file")?;
         let mut content = String::new();
         let mut reader = BufReader::new(file);
         reader
             .read_to_string(&mut content)
             .context("Failed to read presets file content")?;
-
+        if content.trim().is_empty() {
+            return Ok(None);
+        }
         match toml::from_str::<PresetStore>(content.trim()) {
-            Ok(file_store) => {
+            Ok(file_store) => Ok(Some(file_store)),
+            Err(_) => {
+                storage::quarantine_corrupted(file_path)?;
+                Ok(None)
+            }
+        }
+    }
+
+    pub fn load_presets(&mut self) -> Result<()> {
+        match Self::read_file(&self.file_path)? {
+            Some(file_store) => {
                 self.jobs = file_store.jobs;
                 self.version = file_store.version.or(Some(CURRENT_PRESETS_VERSION));
-                Ok(())
             }
-            Err(_) => {
+            None => {
                 self.jobs = vec![];
                 self.version = Some(CURRENT_PRESETS_VERSION);
-                Ok(())
             }
         }
+        self.saved = self.jobs.clone();
+        Ok(())
     }
 
-    pub fn save_presets(&self) -> Result<()> {
-        let file = OpenOptions::new()
-            .create(true)
-            .write(true)
-            .truncate(true)
-            .open(&self.file_path)
-            .context("Failed to open presets file for writing")?;
-        let mut writer = BufWriter::new(file);
+    /// Write the presets, merged with the changes other processes saved since they were loaded.
+    /// The file is locked while it is read and replaced.
+    pub fn save_presets(&mut self) -> Result<()> {
+        let _lock = storage::lock_file(&self.file_path)?;
+        if let Some(file_store) = Self::read_file(&self.file_path)? {
+            self.jobs = storage::merge_records(
+                &self.saved,
+                &self.jobs,
+                &file_store.jobs,
+                |job| (utils::simplify_url(&job.service_url), utils::simplify_url(&job.job_url)),
+                Self::merge_job,
+            );
+        }
         let content = toml::to_string(self).context("Failed to serialize presets")?;
-        writer
-            .write_all(content.as_bytes())
-            .context("Failed to write presets file")?;
+        storage::write_atomic(&self.file_path, &content).context("Failed to write presets file")?;
+        self.saved = self.jobs.clone();
         Ok(())
     }
 
+    /// Presets of a job changed by two processes, merged preset by preset.
+    fn merge_job(base: Option<&JobPresets>, ours: &JobPresets, theirs: &JobPresets) -> JobPresets {
+        let base_presets = base.map(|base| base.presets.as_slice()).unwrap_or_default();
+        let presets = storage::merge_records(
+            base_presets,
+            &ours.presets,
+            &theirs.presets,
+            |preset| preset.name.clone(),
+            |_, ours, _| ours.clone(),
+        );
+        JobPresets {
+            presets,
+            ..ours.clone()
+        }
+    }
+
     pub fn get_job_presets(&self, identity: &JobPresetIdentity) -> Option<JobPresets> {
         self.jobs.iter().find(|job| Self::matches_job(job, identity)).cloned()
     }
@@ -164,13 +245,32 @@ impl PresetStore {
                 })
                 .then_with(|| a.name.cmp(&b.name))
         });
+        presets.extend(self.shared_presets(identity));
+        presets
+    }
+
+    /// Read-only presets of the job from `preset_sources`, by name.
+    pub fn shared_presets(&self, identity: &JobPresetIdentity) -> Vec<ParameterPreset> {
+        let mut presets: Vec<ParameterPreset> = self
+            .shared
+            .iter()
+            .filter(|job| Self::matches_job(job, identity))
+            .flat_map(|job| job.presets.iter().cloned())
+            .collect();
+        presets.sort_by(|a, b| a.name.cmp(&b.name));
         presets
     }
 
+    /// Find a preset by name; personal presets take precedence over shared ones.
     pub fn find_preset(&self, identity: &JobPresetIdentity, preset_name: &str) -> Option<ParameterPreset> {
         let name = preset_name.trim();
         self.get_job_presets(identity)
             .and_then(|job| job.presets.into_iter().find(|preset| preset.name == name))
+            .or_else(|| {
+                self.shared_presets(identity)
+                    .into_iter()
+                    .find(|preset| preset.name == name)
+            })
     }
 
     pub fn upsert_preset(
@@ -195,16 +295,79 @@ impl PresetStore {
             job.presets.push(ParameterPreset {
                 name: name.to_string(),
                 description: None,
+                tags: vec![],
                 params,
                 created_at: Some(now),
                 updated_at: Some(now),
                 last_used_at: None,
+                source: None,
             });
         }
 
         self.save_presets()
     }
 
+    /// Add a preset, replacing the one with the same name. Not saved.
+    pub fn insert_preset(&mut self, identity: &JobPresetIdentity, preset: ParameterPreset) {
+        let job = self.ensure_job(identity);
+        match job.presets.iter_mut().find(|existing| existing.name == preset.name) {
+            Some(existing) => *existing = preset,
+            None => job.presets.push(preset),
+        }
+    }
+
+    /// Change a personal preset and save it. Returns false if it does not exist.
+    pub fn update_preset<F>(&mut self, identity: &JobPresetIdentity, preset_name: &str, update: F) -> Result<bool>
+    where
+        F: FnOnce(&mut ParameterPreset),
+    {
+        let Some(preset) = self
+            .jobs
+            .iter_mut()
+            .find(|job| Self::matches_job(job, identity))
+            .and_then(|job| job.presets.iter_mut().find(|preset| preset.name == preset_name))
+        else {
+            return Ok(false);
+        };
+        update(preset);
+        preset.updated_at = Some(current_timestamp());
+        self.save_presets()?;
+        Ok(true)
+    }
+
+    /// Copy a personal preset under a new name. Returns false if it does not exist.
+    pub fn duplicate_preset(
+        &mut self,
+        identity: &JobPresetIdentity,
+        preset_name: &str,
+        new_name: &str,
+    ) -> Result<bool> {
+        let new_name = new_name.trim();
+        if new_name.is_empty() {
+            anyhow::bail!("Preset name cannot be empty");
+        }
+        let Some(job) = self.jobs.iter_mut().find(|job| Self::matches_job(job, identity)) else {
+            return Ok(false);
+        };
+        if job.presets.iter().any(|preset| preset.name == new_name) {
+            anyhow::bail!("Preset already exists");
+        }
+        let Some(preset) = job.presets.iter().find(|preset| preset.name == preset_name) else {
+            return Ok(false);
+        };
+        let now = current_timestamp();
+        let copy = ParameterPreset {
+            name: new_name.to_string(),
+            created_at: Some(now),
+            updated_at: Some(now),
+            last_used_at: None,
+            ..preset.clone()
+        };
+        job.presets.push(copy);
+        self.save_presets()?;
+        Ok(true)
+    }
+
     pub fn mark_preset_used(&mut self, identity: &JobPresetIdentity, preset_name: &str) -> Result<()> {
         let now = current_timestamp();
         if let Some(job) = self.jobs.iter_mut().find(|job| Self::matches_job(job, identity)) {
@@ -338,6 +501,58 @@ pub fn print_params(params: &HashMap<String, ParamInfo>) {
     }
 }
 
+/// A parameter whose value differs between two parameter sets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamChange {
+    pub name: String,
+    pub old: Option<ParamInfo>, // None: added
+    pub new: Option<ParamInfo>, // None: removed
+}
+
+/// Compare two parameter sets, returning the changed parameters sorted by name.
+pub fn diff_params(old: &HashMap<String, ParamInfo>, new: &HashMap<String, ParamInfo>) -> Vec<ParamChange> {
+    let mut names: Vec<&String> = old
+        .keys()
+        .chain(new.keys())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    names.sort();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let (old_value, new_value) = (old.get(name), new.get(name));
+            if old_value.map(|p| &p.value) == new_value.map(|p| &p.value) {
+                return None;
+            }
+            Some(ParamChange {
+                name: name.clone(),
+                old: old_value.cloned(),
+                new: new_value.cloned(),
+            })
+        })
+        .collect()
+}
+
+/// Print changed parameters as `name: old -> new`.
+pub fn print_param_diff(changes: &[ParamChange]) {
+    fn display(param: Option<&ParamInfo>) -> String {
+        match param {
+            Some(param) if param.r#type == ParamType::Password => MASKED_PASSWORD.to_string(),
+            Some(param) => param.value.replace('\n', "\\n"),
+            None => "-".to_string(),
+        }
+    }
+    for change in changes {
+        println!(
+            "{}: {} -> {}",
+            change.name.bold(),
+            display(change.old.as_ref()).red(),
+            display(change.new.as_ref()).green()
+        );
+    }
+}
+
 fn print_param_line(key: String, value: String) {
     if value.contains('\n') {
         println!("{}: |", key);
@@ -352,12 +567,120 @@ fn print_param_line(key: String, value: String) {
     }
 }
 
+/// Compare preset values with the job's parameter definitions:
+/// job parameters in definition order, then obsolete preset parameters by name.
+pub fn compare_with_definitions(preset: &ParameterPreset, definitions: &[JenkinsJobParameter]) -> Vec<ParamComparison> {
+    let mut comparisons: Vec<ParamComparison> = definitions
+        .iter()
+        .map(|definition| {
+            let preset_param = preset.params.get(&definition.name);
+            ParamComparison {
+                name: definition.name.clone(),
+                preset_value: preset_param.map(|param| param.value.clone()),
+                job_default: definition.default_value.clone(),
+                r#type: definition.param_type.clone().unwrap_or(ParamType::String),
+                status: if preset_param.is_some() {
+                    ParamStatus::Current
+                } else {
+                    ParamStatus::New
+                },
+            }
+        })
+        .collect();
+    let mut obsolete: Vec<ParamComparison> = preset
+        .params
+        .iter()
+        .filter(|(name, _)| !definitions.iter().any(|definition| &definition.name == *name))
+        .map(|(name, param)| ParamComparison {
+            name: name.clone(),
+            preset_value: Some(param.value.clone()),
+            job_default: None,
+            r#type: param.r#type.clone(),
+            status: ParamStatus::Obsolete,
+        })
+        .collect();
+    obsolete.sort_by(|a, b| a.name.cmp(&b.name));
+    comparisons.extend(obsolete);
+    comparisons
+}
+
+fn comparison_value(comparison: &ParamComparison, value: Option<&String>) -> String {
+    match value {
+        Some(_) if comparison.r#type == ParamType::Password => MASKED_PASSWORD.to_string(),
+        Some(value) => value.replace('\n', "\\n"),
+        None => "-".to_string(),
+    }
+}
+
+fn comparison_status(comparison: &ParamComparison) -> ColoredString {
+    match comparison.status {
+        ParamStatus::Current => "".normal(),
+        ParamStatus::Obsolete => t!("preset-param-obsolete").red(),
+        ParamStatus::New => t!("preset-param-new").green(),
+    }
+}
+
+/// Print the preset's values side by side with the job defaults.
+pub fn print_preset_comparison(comparisons: &[ParamComparison]) {
+    let name_width = comparisons.iter().map(|c| c.name.chars().count()).max().unwrap_or(0);
+    let value_width = comparisons
+        .iter()
+        .map(|c| comparison_value(c, c.preset_value.as_ref()).chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(t!("preset-column-value").chars().count());
+    println!(
+        "{}",
+        format!(
+            "{:<name_width$}  {:<value_width$}  {}",
+            t!("preset-column-name"),
+            t!("preset-column-value"),
+            t!("preset-column-default")
+        )
+        .bold()
+    );
+    for comparison in comparisons {
+        println!(
+            "{:<name_width$}  {:<value_width$}  {}  {}",
+            comparison.name,
+            comparison_value(comparison, comparison.preset_value.as_ref()),
+            comparison_value(comparison, comparison.job_default.as_ref()).dimmed(),
+            comparison_status(comparison)
+        );
+    }
+}
+
+/// Tags from comma separated input, e.g. `prod, hotfix` (trimmed, without duplicates).
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(',').map(str::trim).filter(|tag| !tag.is_empty()) {
+        if !tags.iter().any(|existing| existing == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+/// Tags and description shown after the preset name, e.g. ` [prod, hotfix] - Release to production`.
+pub fn preset_details(preset: &ParameterPreset) -> String {
+    let mut details = String::new();
+    if !preset.tags.is_empty() {
+        details.push_str(&format!(" [{}]", preset.tags.join(", ")));
+    }
+    if let Some(description) = preset.description.as_deref().filter(|d| !d.is_empty()) {
+        details.push_str(&format!(" - {}", description));
+    }
+    details
+}
+
 pub async fn select_parameter_source(
     store: &PresetStore,
     identity: &JobPresetIdentity,
+    templates: Vec<ParameterPreset>,
     has_history: bool,
 ) -> Option<ParameterSource> {
-    let presets = store.sorted_presets(identity);
+    let mut presets = store.sorted_presets(identity);
+    presets.extend(templates);
 
     if presets.is_empty() {
         return if has_history {
@@ -371,7 +694,12 @@ pub async fn select_parameter_source(
     let mut sources = Vec::new();
 
     for preset in presets {
-        items.push(format!("{} ({})", preset.name, t!("parameter-source-preset")));
+        let label = match &preset.source {
+            Some(PresetSource::Shared(source)) => t!("parameter-source-shared-preset", "source" => source.as_str()),
+            Some(PresetSource::Template) => t!("parameter-source-template"),
+            None => t!("parameter-source-preset"),
+        };
+        items.push(format!("{} ({}){}", preset.name, label, preset_details(&preset)));
         sources.push(ParameterSource::Preset(preset));
     }
 
@@ -382,6 +710,8 @@ pub async fn select_parameter_source(
 
     items.push(t!("parameter-source-reenter"));
     sources.push(ParameterSource::JenkinsDefault);
+    items.push(t!("parameter-source-build-history"));
+    sources.push(ParameterSource::BuildHistory);
     items.push(t!("parameter-source-manage-presets"));
     sources.push(ParameterSource::ManagePresets);
 
@@ -400,8 +730,28 @@ pub async fn select_parameter_source(
 
 pub async fn select_preset_action(preset: &ParameterPreset) -> Option<PresetBuildAction> {
     println!("{}: {}", t!("parameter-preset").bold(), preset.name.bold().green());
+    if let Some(description) = &preset.description {
+        println!("{}", description.dimmed());
+    }
+    if !preset.tags.is_empty() {
+        println!("{}", preset.tags.join(", ").cyan());
+    }
     println!();
     print_params(&preset.params);
+    if let Some(source) = &preset.source {
+        // Shared presets and templates are read-only, they can be copied to the personal presets
+        let note = match source {
+            PresetSource::Shared(source) => t!("shared-preset-read-only", "source" => source.as_str()),
+            PresetSource::Template => t!("template-read-only"),
+        };
+        println!("{}", note.dimmed());
+        return prompt_preset_action(&[
+            ("y", t!("action-build-now"), PresetBuildAction::Build),
+            ("e", t!("history-action-edit-last"), PresetBuildAction::Edit),
+            ("s", t!("preset-action-edit-save-as"), PresetBuildAction::EditAndSaveAs),
+            ("c", t!("preset-action-save-as"), PresetBuildAction::SaveAs),
+        ]);
+    }
     prompt_preset_action(&[
         ("y", t!("action-build-now"), PresetBuildAction::Build),
         (
@@ -420,6 +770,13 @@ pub async fn select_after_edit_action() -> Option<PresetBuildAction> {
     ])
 }
 
+pub async fn select_rebuild_action() -> Option<PresetBuildAction> {
+    prompt_preset_action(&[
+        ("y", t!("action-build-now"), PresetBuildAction::Build),
+        ("e", t!("rebuild-action-edit"), PresetBuildAction::Edit),
+    ])
+}
+
 pub async fn select_last_build_action() -> Option<PresetBuildAction> {
     prompt_preset_action(&[
         ("y", t!("history-action-use-last"), PresetBuildAction::Build),
@@ -429,12 +786,76 @@ pub async fn select_last_build_action() -> Option<PresetBuildAction> {
     ])
 }
 
-pub async fn manage_presets(store: &mut PresetStore, identity: &JobPresetIdentity) -> Option<()> {
+/// Preset management of a job. `definitions`, `branch_config` and `validator` are used to edit values.
+pub async fn manage_presets(
+    store: &mut PresetStore,
+    identity: &JobPresetIdentity,
+    definitions: &[JenkinsJobParameter],
+    branch_config: &BranchConfig,
+    validator: &ParamValidator,
+) -> Option<()> {
     loop {
         let action = select_manage_action()?;
-        match action {
+        if action == PresetManageAction::Back {
+            return Some(());
+        }
+        let prompt_text = match action {
+            PresetManageAction::Delete => t!("select-preset-to-delete"),
+            PresetManageAction::Rename => t!("select-preset-to-rename"),
+            _ => t!("select-preset-to-manage"),
+        };
+        let preset = select_preset_for_management(store, identity, &prompt_text)?;
+        let result = match action {
+            PresetManageAction::Show => {
+                println!();
+                println!("{}{}", preset.name.bold().green(), preset_details(&preset).dimmed());
+                print_preset_comparison(&compare_with_definitions(&preset, definitions));
+                println!();
+                continue;
+            }
+            PresetManageAction::EditParam => {
+                let Some(result) =
+                    edit_preset_param(store, identity, &preset, definitions, branch_config, validator).await
+                else {
+                    continue; // Ctrl+C pressed - back to the actions
+                };
+                result.map(|updated| updated.then(|| t!("parameter-preset-updated", "name" => preset.name.clone())))
+            }
+            PresetManageAction::Description => {
+                let description = prompt::string_input(
+                    &t!("preset-description"),
+                    preset.description.as_deref().unwrap_or_default(),
+                    Some(false),
+                )?;
+                let description = Some(description.trim().to_string()).filter(|d| !d.is_empty());
+                store
+                    .update_preset(identity, &preset.name, |preset| preset.description = description)
+                    .map(|updated| updated.then(|| t!("parameter-preset-updated", "name" => preset.name.clone())))
+            }
+            PresetManageAction::Tags => {
+                let input = prompt::string_input(&t!("preset-tags"), &preset.tags.join(", "), Some(false))?;
+                let tags = parse_tags(&input);
+                store
+                    .update_preset(identity, &preset.name, |preset| preset.tags = tags)
+                    .map(|updated| updated.then(|| t!("parameter-preset-updated", "name" => preset.name.clone())))
+            }
+            PresetManageAction::Duplicate => {
+                let new_name = prompt_preset_name(Some(&format!("{}-copy", preset.name)))?;
+                store
+                    .duplicate_preset(identity, &preset.name, &new_name)
+                    .map(|duplicated| {
+                        duplicated.then(
+                            || t!("parameter-preset-duplicated", "name" => preset.name.clone(), "new" => new_name),
+                        )
+                    })
+            }
+            PresetManageAction::Rename => {
+                let new_name = prompt_preset_name(Some(&preset.name))?;
+                store.rename_preset(identity, &preset.name, &new_name).map(|renamed| {
+                    renamed.then(|| t!("parameter-preset-renamed", "old" => preset.name.clone(), "new" => new_name))
+                })
+            }
             PresetManageAction::Delete => {
-                let preset = select_preset_for_management(store, identity, &t!("select-preset-to-delete"))?;
                 let confirmed =
                     prompt::handle_confirm_opt(prompt::with_prompt_kind(prompt::PromptKind::Confirm, || {
                         dialoguer::Confirm::with_theme(&ColorfulTheme::default())
@@ -444,35 +865,93 @@ pub async fn manage_presets(store: &mut PresetStore, identity: &JobPresetIdentit
                             .wait_for_newline(false)
                             .interact_opt()
                     }))?;
-                if confirmed {
-                    match store.delete_preset(identity, &preset.name) {
-                        Ok(true) => println!("{}", t!("parameter-preset-deleted", "name" => preset.name)),
-                        Ok(false) => println!("{}", t!("preset-not-found", "name" => preset.name).yellow()),
-                        Err(e) => eprintln!("{}", t!("update-preset-failed", "error" => e.to_string())),
-                    }
-                }
-            }
-            PresetManageAction::Rename => {
-                let preset = select_preset_for_management(store, identity, &t!("select-preset-to-rename"))?;
-                let new_name = prompt_preset_name(Some(&preset.name))?;
-                match store.rename_preset(identity, &preset.name, &new_name) {
-                    Ok(true) => println!(
-                        "{}",
-                        t!("parameter-preset-renamed", "old" => preset.name, "new" => new_name)
-                    ),
-                    Ok(false) => println!("{}", t!("preset-not-found", "name" => preset.name).yellow()),
-                    Err(e) => eprintln!("{}", t!("update-preset-failed", "error" => e.to_string())),
+                if !confirmed {
+                    continue;
                 }
+                store
+                    .delete_preset(identity, &preset.name)
+                    .map(|deleted| deleted.then(|| t!("parameter-preset-deleted", "name" => preset.name.clone())))
             }
-            PresetManageAction::Back => return Some(()),
+            PresetManageAction::Back => unreachable!("handled above"),
+        };
+        match result {
+            Ok(Some(message)) => println!("{}", message),
+            Ok(None) => println!("{}", t!("preset-not-found", "name" => preset.name).yellow()),
+            Err(e) => eprintln!("{}", t!("update-preset-failed", "error" => e.to_string())),
         }
 
-        if store.sorted_presets(identity).is_empty() {
+        if store
+            .sorted_presets(identity)
+            .iter()
+            .all(|preset| preset.source.is_some())
+        {
             return Some(());
         }
     }
 }
 
+/// Edit one parameter value of a preset, prompting like the job's parameter input.
+/// Obsolete parameters can only be removed. Returns `None` on Ctrl+C.
+async fn edit_preset_param(
+    store: &mut PresetStore,
+    identity: &JobPresetIdentity,
+    preset: &ParameterPreset,
+    definitions: &[JenkinsJobParameter],
+    branch_config: &BranchConfig,
+    validator: &ParamValidator,
+) -> Option<Result<bool>> {
+    let comparisons = compare_with_definitions(preset, definitions);
+    if comparisons.is_empty() {
+        println!("{}", t!("preset-no-params").yellow());
+        return None;
+    }
+    let items: Vec<String> = comparisons
+        .iter()
+        .map(|c| {
+            format!(
+                "{} = {}  {}",
+                c.name,
+                comparison_value(c, c.preset_value.as_ref()),
+                comparison_status(c)
+            )
+        })
+        .collect();
+    let selection = prompt::handle_selection_opt(prompt::with_prompt_kind(prompt::PromptKind::FuzzySelectVim, || {
+        FuzzySelect::with_theme(&ColorfulTheme::default())
+            .with_prompt(t!("select-preset-param"))
+            .items(&items)
+            .default(0)
+            .vim_mode(true)
+            .with_initial_text("")
+            .interact_opt()
+    }))?;
+    let comparison = &comparisons[selection];
+
+    let Some(definition) = definitions.iter().find(|definition| definition.name == comparison.name) else {
+        let confirmed = prompt::handle_confirm_opt(prompt::with_prompt_kind(prompt::PromptKind::Confirm, || {
+            dialoguer::Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(t!("remove-obsolete-param-confirm", "name" => comparison.name.clone()))
+                .default(true)
+                .show_default(true)
+                .wait_for_newline(false)
+                .interact_opt()
+        }))?;
+        if !confirmed {
+            return None;
+        }
+        return Some(store.update_preset(identity, &preset.name, |preset| {
+            preset.params.remove(&comparison.name);
+        }));
+    };
+
+    let mut definition = definition.clone();
+    if let Some(value) = &comparison.preset_value {
+        definition.default_value = Some(value.clone());
+    }
+    let params = JenkinsClient::prompt_job_parameters(vec![definition], branch_config, validator).await?;
+    Some(store.update_preset(identity, &preset.name, |preset| preset.params.extend(params)))
+}
+
 pub fn prompt_preset_name(existing_name: Option<&str>) -> Option<String> {
     loop {
         let default_name = existing_name.unwrap_or("");
@@ -494,13 +973,23 @@ pub fn prompt_preset_name(existing_name: Option<&str>) -> Option<String> {
 
 fn select_manage_action() -> Option<PresetManageAction> {
     let items = vec![
-        t!("manage-preset-delete"),
+        t!("manage-preset-show"),
+        t!("manage-preset-edit-param"),
+        t!("manage-preset-description"),
+        t!("manage-preset-tags"),
+        t!("manage-preset-duplicate"),
         t!("manage-preset-rename"),
+        t!("manage-preset-delete"),
         t!("manage-preset-back"),
     ];
     let actions = [
-        PresetManageAction::Delete,
+        PresetManageAction::Show,
+        PresetManageAction::EditParam,
+        PresetManageAction::Description,
+        PresetManageAction::Tags,
+        PresetManageAction::Duplicate,
         PresetManageAction::Rename,
+        PresetManageAction::Delete,
         PresetManageAction::Back,
     ];
     let selection = prompt::handle_selection_opt(prompt::with_prompt_kind(prompt::PromptKind::FuzzySelectVim, || {
@@ -521,7 +1010,11 @@ fn select_preset_for_management(
     identity: &JobPresetIdentity,
     prompt_text: &str,
 ) -> Option<ParameterPreset> {
-    let presets = store.sorted_presets(identity);
+    let presets: Vec<ParameterPreset> = store
+        .sorted_presets(identity)
+        .into_iter()
+        .filter(|preset| preset.source.is_none()) // shared presets are read-only
+        .collect();
     if presets.is_empty() {
         println!("{}", t!("no-parameter-presets").yellow());
         return None;
@@ -0,0 +1,188 @@
+use anyhow::{anyhow, Result};
+use chrono::format::{Item, StrftimeItems};
+use std::collections::HashMap;
+
+use crate::constants::ParamType;
+use crate::i18n::macros::t;
+use crate::jenkins::ParamInfo;
+use crate::prompt;
+use crate::utils::git_output;
+
+/// Prefix of placeholders filled with values captured from earlier builds.
+pub const CAPTURE_PREFIX: &str = "capture.";
+
+/// Placeholder asking for the value when the build is triggered.
+pub const PROMPT_EXPRESSION: &str = "prompt";
+
+/// Replace each `{{expr}}` in `input` with `resolve(expr, matched)`, `matched` being the
+/// placeholder as written so it can be kept.
+/// The expression is trimmed, so `{{ capture.TAG }}` and `{{capture.TAG}}` are equal.
+pub fn render(input: &str, mut resolve: impl FnMut(&str, &str) -> Result<String>) -> Result<String> {
+    let mut output = String::new();
+    let mut rest = input;
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after.find("}}").ok_or_else(|| anyhow!("Invalid template: {}", input))?;
+        output.push_str(&resolve(after[..end].trim(), &rest[start..start + end + 4])?);
+        rest = &after[end + 2..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// Expressions of the `{{...}}` placeholders in `input`, in order.
+pub fn placeholders(input: &str) -> Vec<&str> {
+    let mut expressions = Vec::new();
+    let mut rest = input;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        expressions.push(after[..end].trim());
+        rest = &after[end + 2..];
+    }
+    expressions
+}
+
+/// Replace `{{capture.NAME}}` placeholders with captured values.
+/// Any other text, including other `{{...}}` placeholders, is kept as it is.
+pub fn render_captures(input: &str, captures: &HashMap<String, String>) -> Result<String> {
+    let mut output = String::new();
+    let mut rest = input;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+        match after[..end].trim().strip_prefix(CAPTURE_PREFIX) {
+            Some(name) => output.push_str(
+                captures
+                    .get(name)
+                    .ok_or_else(|| anyhow!("Missing captured value: {}", name))?,
+            ),
+            None => output.push_str(&rest[start..start + end + 4]),
+        }
+        rest = &after[end + 2..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// Whether a value has `{{capture.NAME}}` placeholders, i.e. is only known once earlier stages ran.
+pub fn has_captures(input: &str) -> bool {
+    placeholders(input)
+        .into_iter()
+        .any(|expression| expression.starts_with(CAPTURE_PREFIX))
+}
+
+/// Render the captured values into build parameters.
+pub fn render_params(
+    params: &HashMap<String, ParamInfo>,
+    captures: &HashMap<String, String>,
+) -> Result<HashMap<String, ParamInfo>> {
+    let mut rendered = HashMap::new();
+    for (name, param) in params {
+        let value = render_captures(&param.value, captures).map_err(|e| anyhow!("{}: {}", name, e))?;
+        rendered.insert(
+            name.clone(),
+            ParamInfo {
+                value,
+                r#type: param.r#type.clone(),
+            },
+        );
+    }
+    Ok(rendered)
+}
+
+fn git_value(args: &[&str], expression: &str) -> Result<String> {
+    git_output(args)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| anyhow!("{{{{{}}}}} requires a git repository", expression))
+}
+
+fn format_now(format: &str) -> Result<String> {
+    let items: Vec<Item> = StrftimeItems::new(format).collect();
+    if items.iter().any(|item| matches!(item, Item::Error)) {
+        return Err(anyhow!("Invalid date format: {}", format));
+    }
+    Ok(chrono::Local::now().format_with_items(items.into_iter()).to_string())
+}
+
+/// Value of a placeholder resolved when the build is triggered, `None` if `expression` is not one:
+/// `git.current_branch`, `git.sha`, `git.short_sha`, `date:FORMAT` (strftime) and `env:NAME`.
+pub fn dynamic_value(expression: &str) -> Option<Result<String>> {
+    match expression {
+        "git.current_branch" => Some(git_value(&["rev-parse", "--abbrev-ref", "HEAD"], expression)),
+        "git.sha" => Some(git_value(&["rev-parse", "HEAD"], expression)),
+        "git.short_sha" => Some(git_value(&["rev-parse", "--short", "HEAD"], expression)),
+        _ => {
+            if let Some(format) = expression.strip_prefix("date:") {
+                Some(format_now(format))
+            } else {
+                let name = expression.strip_prefix("env:")?;
+                Some(std::env::var(name).map_err(|_| anyhow!("Environment variable not set: {}", name)))
+            }
+        }
+    }
+}
+
+/// Whether an expression is resolved when the build is triggered (without resolving it).
+fn is_dynamic(expression: &str) -> bool {
+    matches!(
+        expression,
+        PROMPT_EXPRESSION | "git.current_branch" | "git.sha" | "git.short_sha"
+    ) || expression.starts_with("date:")
+        || expression.starts_with("env:")
+}
+
+/// Resolve the dynamic placeholders of parameter values; `ask(name, param)` answers `{{prompt}}`.
+/// Other placeholders, e.g. `{{capture.TAG}}`, are kept. Returns `None` if `ask` is cancelled.
+pub fn render_dynamic_params(
+    params: &HashMap<String, ParamInfo>,
+    mut ask: impl FnMut(&str, &ParamInfo) -> Option<String>,
+) -> Result<Option<HashMap<String, ParamInfo>>> {
+    let mut names: Vec<&String> = params.keys().collect();
+    names.sort(); // ask in a stable order
+    let mut rendered = HashMap::new();
+    for name in names {
+        let param = &params[name];
+        if !placeholders(&param.value).into_iter().any(is_dynamic) {
+            rendered.insert(name.clone(), param.clone());
+            continue;
+        }
+        let mut cancelled = false;
+        let value = render(&param.value, |expression, matched| {
+            if expression == PROMPT_EXPRESSION {
+                return Ok(ask(name, param).unwrap_or_else(|| {
+                    cancelled = true;
+                    String::new()
+                }));
+            }
+            dynamic_value(expression).unwrap_or_else(|| Ok(matched.to_string()))
+        })
+        .map_err(|e| anyhow!("{}: {}", name, e))?;
+        if cancelled {
+            return Ok(None);
+        }
+        rendered.insert(
+            name.clone(),
+            ParamInfo {
+                value,
+                r#type: param.r#type.clone(),
+            },
+        );
+    }
+    Ok(Some(rendered))
+}
+
+/// Ask for a `{{prompt}}` value in the terminal.
+pub fn prompt_param_value(name: &str, param: &ParamInfo) -> Option<String> {
+    if param.r#type == ParamType::Password {
+        prompt::password_input(&t!("prompt-password", "name" => name), "")
+    } else {
+        prompt::string_input(&t!("prompt-input", "name" => name), "", Some(true))
+    }
+}
@@ -0,0 +1,264 @@
+use colored::Colorize;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+
+use crate::i18n::macros::t;
+
+/// Maximum number of failing tests printed in the build summary.
+const MAX_PRINTED_FAILURES: usize = 20;
+
+/// `tree` filter for `/testReport/api/json` (plain and aggregated reports).
+pub const TEST_REPORT_TREE: &str = "passCount,failCount,skipCount,totalCount,duration,\
+suites[name,duration,cases[className,name,status,duration,skipped,errorDetails,errorStackTrace]],\
+childReports[result[passCount,failCount,skipCount,totalCount,duration,\
+suites[name,duration,cases[className,name,status,duration,skipped,errorDetails,errorStackTrace]]]]";
+
+/// Test results collected from a finished build.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TestReport {
+    pub passed: u64,
+    pub failed: u64,
+    pub skipped: u64,
+    pub duration: f64, // seconds
+    pub suites: Vec<TestSuite>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TestSuite {
+    pub name: String,
+    pub duration: f64,
+    pub cases: Vec<TestCase>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TestCase {
+    pub class_name: String,
+    pub name: String,
+    pub status: String, // PASSED, FIXED, FAILED, REGRESSION, SKIPPED
+    pub duration: f64,
+    pub error_details: Option<String>,
+    pub error_stack_trace: Option<String>,
+}
+
+impl TestCase {
+    pub fn is_failed(&self) -> bool {
+        matches!(self.status.as_str(), "FAILED" | "REGRESSION")
+    }
+
+    pub fn is_skipped(&self) -> bool {
+        self.status == "SKIPPED"
+    }
+
+    /// Display name, e.g. `com.example.FooTest.testBar`
+    pub fn full_name(&self) -> String {
+        if self.class_name.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}.{}", self.class_name, self.name)
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TestReportApiResponse {
+    #[serde(rename = "passCount")]
+    pass_count: Option<u64>,
+    #[serde(rename = "failCount", default)]
+    fail_count: u64,
+    #[serde(rename = "skipCount", default)]
+    skip_count: u64,
+    #[serde(rename = "totalCount")]
+    total_count: Option<u64>,
+    #[serde(default)]
+    duration: f64,
+    #[serde(default)]
+    suites: Vec<TestSuiteApi>,
+    #[serde(rename = "childReports", default)]
+    child_reports: Vec<ChildReportApi>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ChildReportApi {
+    result: Option<TestReportApiResponse>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TestSuiteApi {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    duration: f64,
+    #[serde(default)]
+    cases: Vec<TestCaseApi>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TestCaseApi {
+    #[serde(rename = "className", default)]
+    class_name: String,
+    #[serde(default)]
+    name: String,
+    status: Option<String>,
+    #[serde(default)]
+    duration: f64,
+    #[serde(default)]
+    skipped: bool,
+    #[serde(rename = "errorDetails")]
+    error_details: Option<String>,
+    #[serde(rename = "errorStackTrace")]
+    error_stack_trace: Option<String>,
+}
+
+impl From<TestSuiteApi> for TestSuite {
+    fn from(suite: TestSuiteApi) -> Self {
+        TestSuite {
+            name: suite.name,
+            duration: suite.duration,
+            cases: suite
+                .cases
+                .into_iter()
+                .map(|case| TestCase {
+                    status: case
+                        .status
+                        .unwrap_or_else(|| if case.skipped { "SKIPPED" } else { "PASSED" }.to_string()),
+                    class_name: case.class_name,
+                    name: case.name,
+                    duration: case.duration,
+                    error_details: case.error_details.filter(|v| !v.is_empty()),
+                    error_stack_trace: case.error_stack_trace.filter(|v| !v.is_empty()),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn merge_api_report(report: &mut TestReport, response: TestReportApiResponse) {
+    // Aggregated reports (e.g. maven/matrix jobs) nest results per child build,
+    // and their top-level counts are the sums of the children.
+    if !response.child_reports.is_empty() {
+        for child in response.child_reports {
+            if let Some(result) = child.result {
+                merge_api_report(report, result);
+            }
+        }
+        return;
+    }
+
+    let passed = response.pass_count.unwrap_or_else(|| {
+        response
+            .total_count
+            .unwrap_or(0)
+            .saturating_sub(response.fail_count + response.skip_count)
+    });
+    report.passed += passed;
+    report.failed += response.fail_count;
+    report.skipped += response.skip_count;
+    report.duration += response.duration;
+    report.suites.extend(response.suites.into_iter().map(TestSuite::from));
+}
+
+/// Parse the `/testReport/api/json` response of a build.
+pub fn parse_test_report(json_data: &JsonValue) -> TestReport {
+    let response: TestReportApiResponse = serde_json::from_value(json_data.clone()).unwrap_or_default();
+    let mut report = TestReport::default();
+    merge_api_report(&mut report, response);
+    report
+}
+
+impl TestReport {
+    pub fn total(&self) -> u64 {
+        self.passed + self.failed + self.skipped
+    }
+
+    pub fn failed_cases(&self) -> Vec<&TestCase> {
+        self.suites
+            .iter()
+            .flat_map(|suite| suite.cases.iter())
+            .filter(|case| case.is_failed())
+            .collect()
+    }
+
+    /// Render the report as a JUnit XML document.
+    pub fn to_junit_xml(&self) -> String {
+        use quick_xml::escape::escape;
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n",
+            self.total(),
+            self.failed,
+            self.skipped,
+            self.duration
+        ));
+        for suite in &self.suites {
+            let failures = suite.cases.iter().filter(|case| case.is_failed()).count();
+            let skipped = suite.cases.iter().filter(|case| case.is_skipped()).count();
+            xml.push_str(&format!(
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n",
+                escape(&suite.name),
+                suite.cases.len(),
+                failures,
+                skipped,
+                suite.duration
+            ));
+            for case in &suite.cases {
+                let open_tag = format!(
+                    "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.3}\"",
+                    escape(&case.class_name),
+                    escape(&case.name),
+                    case.duration
+                );
+                if case.is_failed() {
+                    xml.push_str(&open_tag);
+                    xml.push_str(">\n");
+                    xml.push_str(&format!(
+                        "      <failure message=\"{}\">{}</failure>\n",
+                        escape(case.error_details.as_deref().unwrap_or("")),
+                        escape(case.error_stack_trace.as_deref().unwrap_or(""))
+                    ));
+                    xml.push_str("    </testcase>\n");
+                } else if case.is_skipped() {
+                    xml.push_str(&open_tag);
+                    xml.push_str(">\n      <skipped/>\n    </testcase>\n");
+                } else {
+                    xml.push_str(&open_tag);
+                    xml.push_str("/>\n");
+                }
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
+
+/// Print pass/fail/skip counts and the failing tests of a report.
+pub fn print_test_report(report: &TestReport) {
+    println!(
+        "{}: {} {}, {} {}, {} {}",
+        t!("test-report-title").bold(),
+        report.passed.to_string().green(),
+        t!("test-report-passed"),
+        report.failed.to_string().red(),
+        t!("test-report-failed"),
+        report.skipped.to_string().yellow(),
+        t!("test-report-skipped"),
+    );
+
+    let failed_cases = report.failed_cases();
+    for case in failed_cases.iter().take(MAX_PRINTED_FAILURES) {
+        println!("  {} {}", "✗".red(), case.full_name().bold());
+        if let Some(details) = case.error_details.as_deref() {
+            for line in details.lines().take(3) {
+                println!("      {}", line.dimmed());
+            }
+        }
+    }
+    if failed_cases.len() > MAX_PRINTED_FAILURES {
+        println!(
+            "  {}",
+            t!("test-report-more-failures", "count" => (failed_cases.len() - MAX_PRINTED_FAILURES).to_string())
+                .dimmed()
+        );
+    }
+}
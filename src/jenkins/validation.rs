@@ -0,0 +1,120 @@
+use colored::Colorize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::constants::DEFAULT_PARAM_VALUE;
+use crate::i18n::macros::t;
+use crate::jenkins::ParamInfo;
+use crate::models::{JenkinsConfig, ParamRule};
+use crate::utils::get_git_tags;
+
+/// Why a parameter value was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleViolation {
+    Required,
+    Pattern(String),         // the regex the value does not match
+    NotAllowed(Vec<String>), // the allowed values
+    NotAGitTag,
+    InvalidRegex(String), // the rule itself is broken
+    Custom(String),       // the rule's `message`
+}
+
+impl fmt::Display for RuleViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            RuleViolation::Required => t!("rule-required"),
+            RuleViolation::Pattern(pattern) => t!("rule-pattern", "pattern" => pattern.clone()),
+            RuleViolation::NotAllowed(allowed) => t!("rule-not-allowed", "values" => allowed.join(", ")),
+            RuleViolation::NotAGitTag => t!("rule-not-git-tag"),
+            RuleViolation::InvalidRegex(pattern) => t!("rule-invalid-regex", "pattern" => pattern.clone()),
+            RuleViolation::Custom(message) => message.clone(),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+/// Validation rules of a job: matching `jobs` entries in configuration order, the first rule of a parameter wins.
+pub fn resolve_rules(config: &JenkinsConfig, job_name: &str, display_name: &str) -> BTreeMap<String, ParamRule> {
+    let mut rules = BTreeMap::new();
+    for job_config in config.job_configs(job_name, display_name) {
+        for (name, rule) in &job_config.rules {
+            rules.entry(name.clone()).or_insert_with(|| rule.clone());
+        }
+    }
+    rules
+}
+
+/// Checks parameter values against the rules of a job.
+#[derive(Debug, Clone, Default)]
+pub struct ParamValidator {
+    rules: BTreeMap<String, ParamRule>,
+    tags: Vec<String>,
+}
+
+impl ParamValidator {
+    /// Git tags are only read when a rule needs them.
+    pub fn new(rules: BTreeMap<String, ParamRule>) -> Self {
+        let needs_tags = rules.values().any(|rule| rule.git_tag.unwrap_or(false));
+        let tags = if needs_tags { get_git_tags() } else { Vec::new() };
+        Self::with_tags(rules, tags)
+    }
+
+    pub fn with_tags(rules: BTreeMap<String, ParamRule>, tags: Vec<String>) -> Self {
+        ParamValidator { rules, tags }
+    }
+
+    /// Check one value. Parameters without a rule always pass.
+    pub fn check(&self, name: &str, value: &str) -> Result<(), RuleViolation> {
+        let Some(rule) = self.rules.get(name) else {
+            return Ok(());
+        };
+        let violation = if value.trim().is_empty() {
+            // Other checks only apply to set values
+            rule.required.unwrap_or(false).then_some(RuleViolation::Required)
+        } else if value == DEFAULT_PARAM_VALUE {
+            None // the Jenkins default is used
+        } else {
+            Self::check_value(rule, value, &self.tags)
+        };
+        match violation {
+            Some(RuleViolation::InvalidRegex(pattern)) => Err(RuleViolation::InvalidRegex(pattern)),
+            Some(violation) => Err(rule.message.clone().map(RuleViolation::Custom).unwrap_or(violation)),
+            None => Ok(()),
+        }
+    }
+
+    fn check_value(rule: &ParamRule, value: &str, tags: &[String]) -> Option<RuleViolation> {
+        if let Some(pattern) = &rule.regex {
+            match regex::Regex::new(pattern) {
+                Ok(re) if re.is_match(value) => {}
+                Ok(_) => return Some(RuleViolation::Pattern(pattern.clone())),
+                Err(_) => return Some(RuleViolation::InvalidRegex(pattern.clone())),
+            }
+        }
+        if !rule.allowed.is_empty() && !rule.allowed.iter().any(|allowed| allowed == value) {
+            return Some(RuleViolation::NotAllowed(rule.allowed.clone()));
+        }
+        if rule.git_tag.unwrap_or(false) && !tags.iter().any(|tag| tag == value) {
+            return Some(RuleViolation::NotAGitTag);
+        }
+        None
+    }
+
+    /// Check all parameters, including required ones that are missing. Sorted by name.
+    pub fn check_all(&self, params: &HashMap<String, ParamInfo>) -> Vec<(String, RuleViolation)> {
+        self.rules
+            .keys()
+            .filter_map(|name| {
+                let value = params.get(name).map(|param| param.value.as_str()).unwrap_or_default();
+                self.check(name, value).err().map(|violation| (name.clone(), violation))
+            })
+            .collect()
+    }
+}
+
+/// Print rule violations, e.g. `VERSION: must match ^\d+\.\d+\.\d+$`
+pub fn print_violations(violations: &[(String, RuleViolation)]) {
+    for (name, violation) in violations {
+        eprintln!("{}: {}", name.bold(), violation.to_string().red());
+    }
+}
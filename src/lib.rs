@@ -20,5 +20,7 @@ pub mod prompt;
 #[doc(hidden)]
 pub mod spinner;
 #[doc(hidden)]
+pub mod storage;
+#[doc(hidden)]
 pub mod terminal;
 pub mod utils;
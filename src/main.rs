@@ -5,6 +5,7 @@ use regex::Regex;
 use std::collections::HashMap;
 use tokio::sync::mpsc;
 
+mod commands;
 mod config;
 mod constants;
 mod env_checks;
@@ -16,6 +17,7 @@ mod migrations;
 mod models;
 mod prompt;
 mod spinner;
+mod storage;
 mod terminal;
 mod update;
 mod utils;
@@ -23,19 +25,27 @@ mod utils;
 // use crate::i18n::I18n;
 use crate::i18n::macros::t;
 use crate::{
-    config::{initialize_config, CONFIG},
+    config::{initialize_config, CONFIG, DATA_DIR},
     env_checks::check_unsupported_terminal,
     flow::{handle_back_and_route, RouteAction, StepTracker},
     interrupts::{handle_ctrl_c, spawn_ctrl_c_key_listener, CtrlCPhase, CTRL_C},
     jenkins::{
+        artifacts, batch, builds,
         client::JenkinsClient,
+        dry_run::{self, DryRunReport},
+        git_check,
         history::{History, HistoryEntry},
+        hooks::{self, HookContext},
+        job_cache::{self, JobCache},
+        param_templates, pipeline, policy, preset_share,
         presets::{self, JobPresetIdentity, ParameterSource, PresetBuildAction, PresetStore},
-        ClientConfig, Event,
+        template, test_report,
+        validation::{self, ParamValidator},
+        Event,
     },
-    models::JenkinsConfig,
+    models::{BranchConfig, GlobalConfig, JenkinsConfig},
     update::{check_update, notify_if_update_available, precheck_update_status},
-    utils::{clear_screen, current_timestamp, format_url, prepare_terminal_for_exit},
+    utils::{clear_screen, format_url, prepare_terminal_for_exit},
 };
 
 #[tokio::main]
@@ -50,6 +60,7 @@ async fn main() {
                 .long("url")
                 .value_name("URL")
                 .help("Sets the Jenkins URL")
+                .global(true)
                 .required(false),
         )
         .arg(
@@ -58,6 +69,7 @@ async fn main() {
                 .long("user")
                 .value_name("USER")
                 .help("Sets the Jenkins User ID")
+                .global(true)
                 .required(false),
         )
         .arg(
@@ -66,6 +78,7 @@ async fn main() {
                 .long("token")
                 .value_name("TOKEN")
                 .help("Sets the Jenkins API Token")
+                .global(true)
                 .required(false),
         )
         .arg(
@@ -74,6 +87,7 @@ async fn main() {
                 .long("cookie")
                 .value_name("COOKIE")
                 .help("Sets the Jenkins auth cookie (e.g. jwt_token=...)")
+                .global(true)
                 .required(false),
         )
         .arg(
@@ -83,13 +97,283 @@ async fn main() {
                 .help("Uses a saved parameter preset for the specified Jenkins job URL")
                 .required(false),
         )
+        .arg(
+            Arg::new("rebuild")
+                .long("rebuild")
+                .value_name("NUMBER")
+                .help("Rebuilds build NUMBER of the specified Jenkins job URL with its parameters")
+                .value_parser(clap::value_parser!(u64))
+                .conflicts_with("preset")
+                .required(false),
+        )
+        .arg(
+            Arg::new("yes_i_mean_prod")
+                .long("yes-i-mean-prod")
+                .help("Allows triggering protected jobs without the typed confirmation (--preset, batch and run with --yes)")
+                .global(true)
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("dry_run")
+                .long("dry-run")
+                .help("Prints the trigger request and an equivalent command instead of triggering the build")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("refresh")
+                .long("refresh")
+                .help("Reloads the job list from Jenkins instead of using the cached list")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("junit")
+                .long("junit")
+                .value_name("PATH")
+                .help("Writes the build's test report as JUnit XML to PATH")
+                .required(false),
+        )
+        .subcommand(
+            Command::new("artifacts")
+                .about("Lists or downloads the artifacts of a build")
+                .arg(
+                    Arg::new("build_url")
+                        .value_name("BUILD_URL")
+                        .help("Jenkins build URL, e.g. http://jenkins_url/job/job_name/42/")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("download")
+                        .long("download")
+                        .value_name("GLOB")
+                        .help("Downloads the artifacts matching GLOB (e.g. \"*.jar\", \"target/**\")")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("dest")
+                        .long("dest")
+                        .value_name("DIR")
+                        .help("Directory for downloaded artifacts")
+                        .default_value("."),
+                ),
+        )
+        .subcommand(
+            Command::new("batch")
+                .about("Triggers the jobs of a manifest and monitors them concurrently")
+                .arg(
+                    Arg::new("manifest")
+                        .value_name("MANIFEST")
+                        .help("TOML file with shared [params] and [[jobs]] (url, preset, params)")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("yes")
+                        .short('y')
+                        .long("yes")
+                        .help("Triggers without confirmation")
+                        .action(clap::ArgAction::SetTrue),
+                ),
+        )
+        .subcommand(
+            Command::new("run")
+                .about("Runs a release pipeline: ordered stages of jobs from a TOML manifest")
+                .arg(
+                    Arg::new("manifest")
+                        .value_name("MANIFEST")
+                        .help("TOML file with shared [params] and [[stages]] (name, when, jobs)")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("yes")
+                        .short('y')
+                        .long("yes")
+                        .help("Runs without confirmation")
+                        .action(clap::ArgAction::SetTrue),
+                ),
+        )
+        .subcommand(
+            Command::new("log-book")
+                .about("Lists the builds triggered from this CLI, with statistics")
+                .arg(
+                    Arg::new("service")
+                        .long("service")
+                        .value_name("NAME")
+                        .help("Only builds of the configured service NAME")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("job")
+                        .long("job")
+                        .value_name("REGEX")
+                        .help("Only jobs whose name matches REGEX")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("since")
+                        .long("since")
+                        .value_name("YYYY-MM-DD")
+                        .help("Only builds triggered on or after this date")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("until")
+                        .long("until")
+                        .value_name("YYYY-MM-DD")
+                        .help("Only builds triggered on or before this date")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("result")
+                        .long("result")
+                        .value_name("RESULT")
+                        .help("Only builds with this result, e.g. SUCCESS, FAILURE")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("param")
+                        .long("param")
+                        .value_name("NAME=GLOB")
+                        .help("Only builds whose parameter matches, e.g. GIT_BRANCH=release/* (repeatable)")
+                        .action(clap::ArgAction::Append),
+                )
+                .arg(
+                    Arg::new("limit")
+                        .long("limit")
+                        .value_name("N")
+                        .help("Number of builds listed; statistics cover all matches")
+                        .value_parser(clap::value_parser!(usize))
+                        .default_value("20"),
+                ),
+        )
+        .subcommand(
+            Command::new("presets")
+                .about("Shares parameter presets with teammates")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("export")
+                        .about("Prints presets as TOML, without password values")
+                        .arg(
+                            Arg::new("service")
+                                .long("service")
+                                .value_name("NAME")
+                                .help("Only presets of the configured service NAME")
+                                .required(false),
+                        )
+                        .arg(
+                            Arg::new("job")
+                                .long("job")
+                                .value_name("REGEX")
+                                .help("Only presets of jobs whose name matches REGEX")
+                                .required(false),
+                        ),
+                )
+                .subcommand(
+                    Command::new("import")
+                        .about("Adds the presets of an exported file")
+                        .arg(
+                            Arg::new("file")
+                                .value_name("FILE")
+                                .help("TOML file written by `jenkins presets export`")
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::new("service")
+                                .long("service")
+                                .value_name("NAME")
+                                .help("Imports all presets into the configured service NAME")
+                                .required(false),
+                        )
+                        .arg(
+                            Arg::new("on_conflict")
+                                .long("on-conflict")
+                                .value_name("ACTION")
+                                .help("For presets whose name exists with other values; asks when omitted")
+                                .value_parser(["skip", "overwrite", "rename"])
+                                .required(false),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("builds")
+                .about("Browses the Jenkins build history of a job")
+                .arg(
+                    Arg::new("job_url")
+                        .value_name("JOB_URL")
+                        .help("Jenkins job URL; selects a project when omitted")
+                        .required(false),
+                ),
+        )
         .get_matches();
     check_unsupported_terminal();
 
+    if let Some(("artifacts", sub_matches)) = matches.subcommand() {
+        let build_url = sub_matches.get_one::<String>("build_url").map(|url| url.as_str());
+        initialize_config(&matches, build_url).await.unwrap();
+        let code = commands::artifacts(sub_matches).await;
+        prepare_terminal_for_exit();
+        std::process::exit(code);
+    }
+
+    if let Some(("batch", sub_matches)) = matches.subcommand() {
+        let path = sub_matches.get_one::<String>("manifest").expect("required argument");
+        let manifest = match batch::load_manifest(std::path::Path::new(path)) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                eprintln!("{:#}", e);
+                std::process::exit(1);
+            }
+        };
+        initialize_config(&matches, manifest.jobs.first().map(|job| job.url.as_str()))
+            .await
+            .unwrap();
+        let code = commands::batch(sub_matches, &manifest).await;
+        prepare_terminal_for_exit();
+        std::process::exit(code);
+    }
+
+    if let Some(("run", sub_matches)) = matches.subcommand() {
+        let path = sub_matches.get_one::<String>("manifest").expect("required argument");
+        let manifest = match pipeline::load_pipeline(std::path::Path::new(path)) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                eprintln!("{:#}", e);
+                std::process::exit(1);
+            }
+        };
+        let first_job_url = manifest
+            .stages
+            .first()
+            .and_then(|stage| stage.jobs.first())
+            .map(|job| job.url.as_str());
+        initialize_config(&matches, first_job_url).await.unwrap();
+        let code = commands::run(sub_matches, &manifest).await;
+        prepare_terminal_for_exit();
+        std::process::exit(code);
+    }
+
+    if let Some(("log-book", sub_matches)) = matches.subcommand() {
+        let (global_config, jenkins_configs) = config::load_file_config();
+        let code = commands::log_book(sub_matches, &global_config, &jenkins_configs);
+        prepare_terminal_for_exit();
+        std::process::exit(code);
+    }
+
+    if let Some(("presets", sub_matches)) = matches.subcommand() {
+        let (_, jenkins_configs) = config::load_file_config();
+        let code = match sub_matches.subcommand() {
+            Some(("export", export_matches)) => commands::presets_export(export_matches, &jenkins_configs),
+            Some(("import", import_matches)) => commands::presets_import(import_matches, &jenkins_configs),
+            _ => unreachable!("subcommand required"),
+        };
+        prepare_terminal_for_exit();
+        std::process::exit(code);
+    }
+
     precheck_update_status();
     notify_if_update_available(); // before loading config
 
-    let (global_config, service_step_enabled) = initialize_config(&matches).await.unwrap();
+    let builds_matches = matches.subcommand_matches("builds");
+    let job_url_arg = builds_matches.and_then(|m| m.get_one::<String>("job_url").map(|url| url.as_str()));
+    let (global_config, service_step_enabled) = initialize_config(&matches, job_url_arg).await.unwrap();
     let should_check_update = global_config.check_update.unwrap_or(true);
 
     clear_screen();
@@ -111,10 +395,18 @@ async fn main() {
     });
 
     // main logic - loop to allow returning to service selection
-    let preset_arg = matches.get_one::<String>("preset").cloned();
+    let options = MenuOptions {
+        preset: matches.get_one::<String>("preset").cloned(),
+        junit_report: matches.get_one::<String>("junit").cloned(),
+        browse_builds: builds_matches.is_some(),
+        rebuild: matches.get_one::<u64>("rebuild").copied(),
+        yes_i_mean_prod: matches.get_flag("yes_i_mean_prod"),
+        dry_run: matches.get_flag("dry_run"),
+        refresh: matches.get_flag("refresh"),
+    };
 
     loop {
-        if menu(service_step_enabled, preset_arg.as_deref()).await {
+        if menu(service_step_enabled, &options).await {
             clear_screen();
             if let Err(e) = config::select_jenkins_service().await {
                 eprintln!("Failed to select service: {}", e);
@@ -134,6 +426,18 @@ async fn main() {
     prepare_terminal_for_exit();
 }
 
+/// Command line options applied to each menu run
+#[derive(Debug, Default)]
+struct MenuOptions {
+    preset: Option<String>,       // --preset
+    junit_report: Option<String>, // --junit
+    browse_builds: bool,          // `builds` subcommand
+    rebuild: Option<u64>,         // --rebuild
+    yes_i_mean_prod: bool,        // --yes-i-mean-prod
+    dry_run: bool,                // --dry-run
+    refresh: bool,                // --refresh
+}
+
 // actions
 
 fn filter_projects(projects: Vec<jenkins::JenkinsJob>, config: &JenkinsConfig) -> Vec<jenkins::JenkinsJob> {
@@ -181,6 +485,8 @@ async fn resolve_user_parameters(
     source: ParameterSource,
     history_item: Option<&HistoryEntry>,
     current_parameters: Vec<jenkins::JenkinsJobParameter>,
+    branch_config: &BranchConfig,
+    validator: &ParamValidator,
 ) -> Option<(HashMap<String, jenkins::ParamInfo>, Option<String>)> {
     match source {
         ParameterSource::Preset(preset) => {
@@ -192,21 +498,25 @@ async fn resolve_user_parameters(
                 }
                 PresetBuildAction::Edit => {
                     let parameter_definitions = presets::apply_preset_defaults(&preset, current_parameters);
-                    let params = JenkinsClient::prompt_job_parameters(parameter_definitions).await?;
+                    let params =
+                        JenkinsClient::prompt_job_parameters(parameter_definitions, branch_config, validator).await?;
                     Some((params, None))
                 }
                 PresetBuildAction::EditAndUpdate => {
                     let parameter_definitions = presets::apply_preset_defaults(&preset, current_parameters);
-                    let params = JenkinsClient::prompt_job_parameters(parameter_definitions).await?;
+                    let params =
+                        JenkinsClient::prompt_job_parameters(parameter_definitions, branch_config, validator).await?;
                     handle_preset_save_action(presets, identity, &preset.name, params, PresetBuildAction::Update)
                 }
                 PresetBuildAction::EditAndSaveAs => {
                     let parameter_definitions = presets::apply_preset_defaults(&preset, current_parameters);
-                    let params = JenkinsClient::prompt_job_parameters(parameter_definitions).await?;
+                    let params =
+                        JenkinsClient::prompt_job_parameters(parameter_definitions, branch_config, validator).await?;
                     handle_preset_save_action(presets, identity, &preset.name, params, PresetBuildAction::SaveAs)
                 }
                 PresetBuildAction::Refill => {
-                    let params = JenkinsClient::prompt_job_parameters(current_parameters).await?;
+                    let params =
+                        JenkinsClient::prompt_job_parameters(current_parameters, branch_config, validator).await?;
                     let post_action = presets::select_after_edit_action().await?;
                     handle_preset_save_action(presets, identity, "", params, post_action)
                 }
@@ -226,7 +536,8 @@ async fn resolve_user_parameters(
             }
         }
         ParameterSource::LastBuild => {
-            let history_item = history_item?;
+            let selected_run = History::select_run(history_item?)?;
+            let history_item = &selected_run;
             let action = match history_item.params.as_ref() {
                 Some(params) => {
                     println!("{}:", t!("last-build-params").bold());
@@ -240,7 +551,8 @@ async fn resolve_user_parameters(
                 PresetBuildAction::Build => Some((History::merge_parameters(history_item, &current_parameters), None)),
                 PresetBuildAction::Edit => {
                     let parameter_definitions = History::apply_history_defaults(history_item, current_parameters);
-                    let params = JenkinsClient::prompt_job_parameters(parameter_definitions).await?;
+                    let params =
+                        JenkinsClient::prompt_job_parameters(parameter_definitions, branch_config, validator).await?;
                     let post_action = presets::select_after_edit_action().await?;
                     handle_preset_save_action(presets, identity, "", params, post_action)
                 }
@@ -249,7 +561,8 @@ async fn resolve_user_parameters(
                 }
                 PresetBuildAction::EditAndSaveAs => {
                     let parameter_definitions = History::apply_history_defaults(history_item, current_parameters);
-                    let params = JenkinsClient::prompt_job_parameters(parameter_definitions).await?;
+                    let params =
+                        JenkinsClient::prompt_job_parameters(parameter_definitions, branch_config, validator).await?;
                     handle_preset_save_action(presets, identity, "", params, PresetBuildAction::SaveAs)
                 }
                 PresetBuildAction::SaveAs => {
@@ -257,19 +570,38 @@ async fn resolve_user_parameters(
                     handle_preset_save_action(presets, identity, "", params, PresetBuildAction::SaveAs)
                 }
                 PresetBuildAction::Refill => {
-                    let params = JenkinsClient::prompt_job_parameters(current_parameters).await?;
+                    let params =
+                        JenkinsClient::prompt_job_parameters(current_parameters, branch_config, validator).await?;
                     let post_action = presets::select_after_edit_action().await?;
                     handle_preset_save_action(presets, identity, "", params, post_action)
                 }
                 PresetBuildAction::Update => Some((History::merge_parameters(history_item, &current_parameters), None)),
             }
         }
+        ParameterSource::Rebuild(build) => {
+            let params = builds::rebuild_parameters(&build, &current_parameters);
+            builds::print_rebuild_parameters(&build, &current_parameters, &params);
+            match presets::select_rebuild_action().await? {
+                PresetBuildAction::Edit => {
+                    let history_entry = HistoryEntry {
+                        params: Some(params),
+                        ..Default::default()
+                    };
+                    let parameter_definitions = History::apply_history_defaults(&history_entry, current_parameters);
+                    let params =
+                        JenkinsClient::prompt_job_parameters(parameter_definitions, branch_config, validator).await?;
+                    let post_action = presets::select_after_edit_action().await?;
+                    handle_preset_save_action(presets, identity, "", params, post_action)
+                }
+                _ => Some((params, None)),
+            }
+        }
         ParameterSource::JenkinsDefault => {
-            let params = JenkinsClient::prompt_job_parameters(current_parameters).await?;
+            let params = JenkinsClient::prompt_job_parameters(current_parameters, branch_config, validator).await?;
             let post_action = presets::select_after_edit_action().await?;
             handle_preset_save_action(presets, identity, "", params, post_action)
         }
-        ParameterSource::ManagePresets => None,
+        ParameterSource::BuildHistory | ParameterSource::ManagePresets => None,
     }
 }
 
@@ -319,17 +651,40 @@ fn handle_preset_save_action(
     }
 }
 
+/// Print the test summary of a finished build and optionally save it as JUnit XML
+async fn report_test_results(client: &JenkinsClient, build_url: &str, junit_path: Option<&str>) {
+    match client.get_test_report(build_url).await {
+        Ok(Some(report)) => {
+            test_report::print_test_report(&report);
+            if let Some(path) = junit_path {
+                match std::fs::write(path, report.to_junit_xml()) {
+                    Ok(_) => println!("{}", t!("test-report-saved", "path" => path)),
+                    Err(e) => eprintln!("{}", t!("test-report-save-failed", "error" => e.to_string())),
+                }
+            }
+        }
+        Ok(None) => {
+            if junit_path.is_some() {
+                println!("{}", t!("test-report-not-found").yellow());
+            }
+        }
+        Err(e) => eprintln!("{}", t!("get-test-report-failed", "error" => e.to_string())),
+    }
+}
+
 /// Main menu
-async fn menu(service_step_enabled: bool, preset_arg: Option<&str>) -> bool {
+async fn menu(service_step_enabled: bool, options: &MenuOptions) -> bool {
+    let preset_arg = options.preset.as_deref();
     let config = CONFIG.lock().await;
     // println!("runtime_config:\n{:?}\n{:?}", config.global, config.jenkins);
 
     let global_config = config.global.clone();
-    let jenkins_config = config
-        .jenkins
-        .as_ref()
-        .expect("Jenkins configuration not found")
-        .clone();
+    let Some(jenkins_config) = config.jenkins.clone() else {
+        eprintln!("{}", t!("fill-required-config").yellow());
+        println!("{}", t!("jenkins-login-instruction"));
+        std::process::exit(1);
+    };
+    let services = config.services.clone();
     drop(config);
 
     // Flow steps:
@@ -338,59 +693,59 @@ async fn menu(service_step_enabled: bool, preset_arg: Option<&str>) -> bool {
     // - Parameter selection
     let can_back_to_project = !jenkins_config.url.contains("/job/");
     let mut steps = StepTracker::new(service_step_enabled, can_back_to_project);
-    let auth = if jenkins_config.user.is_empty() || jenkins_config.token.is_empty() {
-        None
-    } else {
-        Some(format!("{}:{}", jenkins_config.user, jenkins_config.token))
-    };
-    let base_url = if jenkins_config.url.contains("/job/") {
-        jenkins_config
-            .url
-            .split("/job/")
-            .next()
-            .unwrap_or(&jenkins_config.url)
-            .to_string()
-    } else {
-        jenkins_config.url.clone()
-    };
+    let base_url = JenkinsClient::service_base_url(&jenkins_config.url);
     if preset_arg.is_some() && !jenkins_config.url.contains("/job/") {
         eprintln!("{}", t!("preset-requires-job-url"));
         std::process::exit(1);
     }
+    if options.rebuild.is_some() && !jenkins_config.url.contains("/job/") {
+        eprintln!("{}", t!("rebuild-requires-job-url"));
+        std::process::exit(1);
+    }
     // let mut client = JenkinsClient::new(&config.url, &auth);
     let (event_sender, mut event_receiver) = mpsc::channel::<Event>(100);
 
-    // Create client configuration
-    let client_config = global_config.as_ref().map(|g| ClientConfig {
-        timeout: g.timeout,
-        follow_downstream: g.follow_downstream.unwrap_or(false),
-    });
-
-    let client = std::sync::Arc::new(tokio::sync::RwLock::new(JenkinsClient::new(
-        &base_url,
-        auth.as_deref(),
-        if jenkins_config.cookie.is_empty() {
-            None
-        } else {
-            Some(jenkins_config.cookie.as_str())
-        },
-        jenkins_config.cookie_refresh.clone(),
-        client_config,
+    let client = std::sync::Arc::new(tokio::sync::RwLock::new(JenkinsClient::from_service(
+        &jenkins_config,
+        global_config.as_ref(),
     )));
     // println!("config.url: {}", config.url); // client.read().await.base_url
-    let mut history = History::new().unwrap();
-    let mut presets = PresetStore::new().unwrap();
+    let mut history = History::new().unwrap_or_else(|e| {
+        eprintln!("{}", format!("{:#}", e).red());
+        std::process::exit(1);
+    });
+    history.max_runs = global_config.as_ref().and_then(|global| global.history_runs);
+    let mut presets = PresetStore::new().unwrap_or_else(|e| {
+        eprintln!("{}", format!("{:#}", e).red());
+        std::process::exit(1);
+    });
+    if let Some(global) = &global_config {
+        presets.shared = preset_share::load_shared_presets(&global.preset_sources, &services);
+    }
     let enable_history = jenkins_config.enable_history.unwrap_or(true);
 
     CTRL_C
         .set_ctx(std::sync::Arc::clone(&client), event_sender.clone())
         .await;
 
+    // Only the first job list of --refresh is reloaded, going back uses the new cache
+    let mut refresh_jobs = options.refresh;
+
     // Main selection loop - allows going back from param selection to project selection
     let (job, job_url, user_params, preset_identity, used_preset_name) = loop {
         // Step 1: Select project
         steps.enter_project();
-        let job = match get_project(&client, &jenkins_config, &mut history, &mut presets).await {
+        let project = get_project(
+            &client,
+            &jenkins_config,
+            global_config.as_ref(),
+            &mut history,
+            &mut presets,
+            refresh_jobs,
+        )
+        .await;
+        refresh_jobs = false;
+        let job = match project {
             Some(j) => j,
             None => {
                 // Ctrl+C pressed
@@ -433,16 +788,38 @@ async fn menu(service_step_enabled: bool, preset_arg: Option<&str>) -> bool {
 
         // Select parameter source and build parameters
         steps.enter_params();
-        let parameter_source = if let Some(preset_name) = preset_arg {
-            match presets.find_preset(&preset_identity, preset_name) {
+        let parameter_source = if let Some(number) = options.rebuild {
+            let build = client.read().await.get_build(&job_url, number).await;
+            match build {
+                Ok(build) => ParameterSource::Rebuild(Box::new(build)),
+                Err(e) => {
+                    eprintln!(
+                        "{}",
+                        t!("get-build-failed", "number" => number.to_string(), "error" => e.to_string())
+                    );
+                    std::process::exit(1);
+                }
+            }
+        } else if let Some(preset_name) = preset_arg {
+            let preset = presets.find_preset(&preset_identity, preset_name).or_else(|| {
+                param_templates::matching_templates(global_config.as_ref(), &jenkins_config, &current_parameters)
+                    .into_iter()
+                    .find(|template| template.name == preset_name.trim())
+            });
+            match preset {
                 Some(preset) => ParameterSource::Preset(preset),
                 None => {
                     eprintln!("{}", t!("preset-not-found", "name" => preset_name.to_string()));
                     std::process::exit(1);
                 }
             }
+        } else if options.browse_builds {
+            ParameterSource::BuildHistory
         } else {
-            match presets::select_parameter_source(&presets, &preset_identity, history_item.is_some()).await {
+            let templates =
+                param_templates::matching_templates(global_config.as_ref(), &jenkins_config, &current_parameters);
+            match presets::select_parameter_source(&presets, &preset_identity, templates, history_item.is_some()).await
+            {
                 Some(source) => source,
                 None => {
                     // Ctrl+C pressed
@@ -454,8 +831,18 @@ async fn menu(service_step_enabled: bool, preset_arg: Option<&str>) -> bool {
             }
         };
 
+        let branch_config = git_check::resolve_branch_config(&jenkins_config, &job.name, &job.display_name);
+        let validator = ParamValidator::new(validation::resolve_rules(&jenkins_config, &job.name, &job.display_name));
         if matches!(parameter_source, ParameterSource::ManagePresets) {
-            if presets::manage_presets(&mut presets, &preset_identity).await.is_none() {
+            let managed = presets::manage_presets(
+                &mut presets,
+                &preset_identity,
+                &current_parameters,
+                &branch_config,
+                &validator,
+            )
+            .await;
+            if managed.is_none() {
                 if let Some(return_service) = handle_menu_back(&mut steps) {
                     return return_service;
                 }
@@ -463,22 +850,57 @@ async fn menu(service_step_enabled: bool, preset_arg: Option<&str>) -> bool {
             continue;
         }
 
+        let parameter_source = if matches!(parameter_source, ParameterSource::BuildHistory) {
+            let selected_build = {
+                let client_guard = client.read().await;
+                builds::browse_builds(&client_guard, &job_url, &current_parameters).await
+            };
+            match selected_build {
+                Some(build) => ParameterSource::Rebuild(Box::new(build)),
+                None => {
+                    if let Some(return_service) = handle_menu_back(&mut steps) {
+                        return return_service;
+                    }
+                    continue;
+                }
+            }
+        } else {
+            parameter_source
+        };
+
         let (user_params, used_preset_name) = if preset_arg.is_some() {
             match parameter_source {
-                ParameterSource::Preset(preset) => (
-                    presets::merge_preset_parameters(&preset, &current_parameters),
-                    Some(preset.name),
-                ),
+                ParameterSource::Preset(preset) => {
+                    let params = presets::merge_preset_parameters(&preset, &current_parameters);
+                    let params = match template::render_dynamic_params(&params, template::prompt_param_value) {
+                        Ok(Some(params)) => params,
+                        Ok(None) => std::process::exit(1), // Ctrl+C pressed
+                        Err(e) => {
+                            eprintln!("{}", t!("dynamic-value-failed", "error" => e.to_string()).red());
+                            std::process::exit(1);
+                        }
+                    };
+                    let violations = validator.check_all(&params);
+                    if !violations.is_empty() {
+                        eprintln!("{}", t!("params-invalid").red());
+                        validation::print_violations(&violations);
+                        std::process::exit(1);
+                    }
+                    (params, Some(preset.name))
+                }
                 ParameterSource::ManagePresets => unreachable!("--preset always resolves to a preset source"),
                 _ => unreachable!("--preset always resolves to a preset source"),
             }
         } else {
-            match resolve_user_parameters(
+            let definitions = current_parameters.clone();
+            let resolved = match resolve_user_parameters(
                 &mut presets,
                 &preset_identity,
                 parameter_source,
                 history_item.as_ref(),
                 current_parameters,
+                &branch_config,
+                &validator,
             )
             .await
             {
@@ -489,6 +911,45 @@ async fn menu(service_step_enabled: bool, preset_arg: Option<&str>) -> bool {
                     }
                     continue;
                 }
+            };
+            // Dynamic values of presets, e.g. {{git.current_branch}}, are resolved when triggering
+            let resolved = match template::render_dynamic_params(&resolved.0, template::prompt_param_value) {
+                Ok(Some(params)) => (params, resolved.1),
+                Ok(None) => {
+                    if let Some(return_service) = handle_menu_back(&mut steps) {
+                        return return_service;
+                    }
+                    continue;
+                }
+                Err(e) => {
+                    eprintln!("{}", t!("dynamic-value-failed", "error" => e.to_string()).red());
+                    if let Some(return_service) = handle_menu_back(&mut steps) {
+                        return return_service;
+                    }
+                    continue;
+                }
+            };
+            // Presets, history and rebuilds are merged without prompting; fix invalid values before triggering
+            let violations = validator.check_all(&resolved.0);
+            if violations.is_empty() {
+                resolved
+            } else {
+                eprintln!("{}", t!("params-invalid-reprompt").yellow());
+                validation::print_violations(&violations);
+                let history_entry = HistoryEntry {
+                    params: Some(resolved.0),
+                    ..Default::default()
+                };
+                let parameter_definitions = History::apply_history_defaults(&history_entry, definitions);
+                match JenkinsClient::prompt_job_parameters(parameter_definitions, &branch_config, &validator).await {
+                    Some(params) => (params, resolved.1),
+                    None => {
+                        if let Some(return_service) = handle_menu_back(&mut steps) {
+                            return return_service;
+                        }
+                        continue;
+                    }
+                }
             }
         };
 
@@ -501,8 +962,99 @@ async fn menu(service_step_enabled: bool, preset_arg: Option<&str>) -> bool {
     // println!("user_params: {:?}", user_params);
     // std::process::exit(1); // debug params
 
+    if options.dry_run {
+        let definitions = client
+            .write()
+            .await
+            .get_job_parameters(&job_url)
+            .await
+            .unwrap_or_default();
+        let last_build = history
+            .get_history(
+                &HistoryEntry {
+                    name: job.name.clone(),
+                    job_url: job_url.clone(),
+                    ..Default::default()
+                },
+                &jenkins_config.url,
+            )
+            .and_then(|entry| entry.params);
+        dry_run::print_dry_run(&DryRunReport {
+            job_url: &job_url,
+            params: &user_params,
+            defaults: &History::merge_parameters(&HistoryEntry::default(), &definitions),
+            last_build: last_build.as_ref(),
+            preset_name: used_preset_name.as_deref(),
+            protected: policy::is_protected(&jenkins_config, &job.name, &job.display_name),
+        });
+        return false;
+    }
+
     notify_if_update_available(); // before trigger build
 
+    if policy::is_protected(&jenkins_config, &job.name, &job.display_name) {
+        if preset_arg.is_some() {
+            // Non-interactive: the flag stands in for the typed confirmation
+            if !options.yes_i_mean_prod {
+                eprintln!("{}", t!("protected-requires-flag", "name" => job.name.clone()).red());
+                std::process::exit(1);
+            }
+        } else {
+            let last_build = client
+                .read()
+                .await
+                .get_builds(&job_url, 1)
+                .await
+                .ok()
+                .and_then(|builds| builds.into_iter().next());
+            policy::print_protected_summary(&jenkins_config.name, &job_url, &user_params, last_build.as_ref());
+            if !policy::confirm_job_name(&job.name) {
+                println!("{}", t!("build-aborted").yellow());
+                return false;
+            }
+        }
+    }
+
+    let check_git_push = global_config
+        .as_ref()
+        .and_then(|global| global.check_git_push)
+        .unwrap_or(false);
+    let branch_config = git_check::resolve_branch_config(&jenkins_config, &job.name, &job.display_name);
+    if check_git_push && !git_check::confirm_branches_pushed(&user_params, &branch_config, preset_arg.is_none()) {
+        if preset_arg.is_some() {
+            std::process::exit(1);
+        }
+        println!("{}", t!("build-aborted").yellow());
+        return false;
+    }
+
+    if !policy::check_freeze(global_config.as_ref(), &jenkins_config, &job.name, &job.display_name) {
+        if preset_arg.is_some() {
+            std::process::exit(1);
+        }
+        println!("{}", t!("build-aborted").yellow());
+        return false;
+    }
+
+    let job_hooks = hooks::resolve_hooks(&jenkins_config, &job.name, &job.display_name);
+    if let Some(command) = job_hooks.pre_build.as_deref() {
+        println!("{}", t!("running-hook", "command" => command).dimmed());
+        let context = HookContext {
+            job_url: &job_url,
+            job_name: &job.name,
+            params: Some(&user_params),
+            ..Default::default()
+        };
+        if let Err(e) = hooks::run_hook(command, &context) {
+            eprintln!("{}", t!("pre-build-hook-failed", "error" => e.to_string()));
+            if preset_arg.is_some() {
+                std::process::exit(1);
+            }
+            println!("{}", t!("build-aborted").yellow());
+            return false;
+        }
+    }
+
     if enable_history {
         let mut history_param = HistoryEntry {
             job_url: job_url.clone(),
@@ -511,6 +1063,7 @@ async fn menu(service_step_enabled: bool, preset_arg: Option<&str>) -> bool {
             params: Some(user_params.clone()),
             created_at: Some(0),
             completed_at: Some(0),
+            runs: Vec::new(),
         };
         if let Err(e) = history.upsert_history(&mut history_param) {
             eprintln!("{}", t!("update-history-failed", "error" => e.to_string()));
@@ -525,7 +1078,7 @@ async fn menu(service_step_enabled: bool, preset_arg: Option<&str>) -> bool {
 
     let queue_location = {
         let client_guard = client.read().await;
-        match client_guard.trigger_build(&job_url, user_params).await {
+        match client_guard.trigger_build(&job_url, user_params.clone()).await {
             Ok(location) => location,
             Err(e) => {
                 eprintln!("{}: {}", t!("trigger-build-failed"), e);
@@ -557,20 +1110,9 @@ async fn menu(service_step_enabled: bool, preset_arg: Option<&str>) -> bool {
     match client_guard.poll_build_status(&build_url, &mut event_receiver).await {
         Ok(_) => {
             CTRL_C.finish_polling();
-            // stop loop
-            if enable_history {
-                if let Err(e) = history.update_field(
-                    &HistoryEntry {
-                        name: job.name.clone(),
-                        job_url: job_url.clone(),
-                        ..Default::default()
-                    },
-                    |entry| {
-                        entry.completed_at = Some(current_timestamp());
-                    },
-                ) {
-                    eprintln!("Failed to update completed_at: {}", e);
-                }
+            report_test_results(&client_guard, &build_url, options.junit_report.as_deref()).await;
+            if preset_arg.is_none() {
+                artifacts::prompt_download_artifacts(&client_guard, &build_url).await;
             }
         }
         Err(e) => {
@@ -578,6 +1120,7 @@ async fn menu(service_step_enabled: bool, preset_arg: Option<&str>) -> bool {
             if e.to_string().contains("cancelled!") {
                 return false;
             }
+            report_test_results(&client_guard, &build_url, options.junit_report.as_deref()).await;
 
             // // get full build log
             // flush_stdin();
@@ -597,15 +1140,190 @@ async fn menu(service_step_enabled: bool, preset_arg: Option<&str>) -> bool {
         }
     }
 
+    let finished_build = client_guard.get_build_by_url(&build_url).await.ok();
+    if enable_history {
+        let info = HistoryEntry {
+            name: job.name.clone(),
+            job_url: job_url.clone(),
+            ..Default::default()
+        };
+        if let Err(e) = history.complete_run(&info, &build_url, finished_build.as_ref(), used_preset_name.as_deref()) {
+            eprintln!("{}", t!("update-history-failed", "error" => e.to_string()));
+        }
+    }
+
+    if let Some(command) = job_hooks.post_build.as_deref() {
+        let result = finished_build
+            .as_ref()
+            .map_or("UNKNOWN".to_string(), |build| build.status().to_string());
+        println!("{}", t!("running-hook", "command" => command).dimmed());
+        let context = HookContext {
+            job_url: &job_url,
+            job_name: &job.name,
+            params: Some(&user_params),
+            build_url: Some(&build_url),
+            result: Some(&result),
+        };
+        if let Err(e) = hooks::run_hook(command, &context) {
+            eprintln!("{}", t!("post-build-hook-failed", "error" => e.to_string()));
+        }
+    }
+
     false
 }
 
+/// Remove the history and presets of jobs that no longer exist.
+fn cleanup_obsolete_projects(
+    project_names: &[String],
+    jenkins_config: &JenkinsConfig,
+    history: &mut History,
+    presets: &mut PresetStore,
+) {
+    match history.cleanup_obsolete_projects(project_names, &jenkins_config.url) {
+        Ok(removed) => {
+            if !removed.is_empty() {
+                println!(
+                    "{}",
+                    t!("history-cleanup", "count" => removed.len().to_string(), "names" => removed.join(", "))
+                );
+            }
+        }
+        Err(e) => {
+            eprintln!("{}", t!("history-cleanup-error", "error" => e.to_string()));
+        }
+    }
+
+    match presets.cleanup_obsolete_projects(project_names, &jenkins_config.url) {
+        Ok(removed) => {
+            if !removed.is_empty() {
+                println!(
+                    "{}",
+                    t!("preset-cleanup", "count" => removed.len().to_string(), "names" => removed.join(", "))
+                );
+            }
+        }
+        Err(e) => {
+            eprintln!("{}", t!("preset-cleanup-error", "error" => e.to_string()));
+        }
+    }
+}
+
+/// Jobs of the service, and whether they were fetched now. A cached list is used right away;
+/// once it is older than `job_cache_ttl` it is refreshed in the background, see [`refresh_projects_in_background`].
+async fn load_projects(
+    client: &std::sync::Arc<tokio::sync::RwLock<JenkinsClient>>,
+    jenkins_config: &JenkinsConfig,
+    global_config: Option<&GlobalConfig>,
+    refresh: bool,
+) -> (Vec<jenkins::JenkinsJob>, bool) {
+    let ttl = global_config
+        .and_then(|global| global.job_cache_ttl)
+        .unwrap_or(job_cache::DEFAULT_JOB_CACHE_TTL);
+    let cache_path = job_cache::cache_path(&DATA_DIR.join(job_cache::JOB_CACHE_DIR), &jenkins_config.url);
+    if ttl > 0 && !refresh {
+        if let Some(cache) = job_cache::load_cache(&cache_path) {
+            if !cache.is_fresh(ttl, utils::current_timestamp()) {
+                refresh_projects_in_background(jenkins_config, global_config, cache_path);
+            }
+            return (cache.jobs, false);
+        }
+    }
+
+    let projects = {
+        let client_guard = client.read().await;
+        match client_guard.get_projects().await {
+            Ok(projects) => projects,
+            Err(e) => {
+                eprintln!("{}: {}", t!("get-projects-failed"), e);
+                std::process::exit(1);
+            }
+        }
+    };
+    if ttl > 0 {
+        let cache = JobCache {
+            service_url: jenkins_config.url.clone(),
+            fetched_at: utils::current_timestamp(),
+            jobs: projects.clone(),
+        };
+        if let Err(e) = job_cache::save_cache(&cache_path, &cache) {
+            utils::debug_line(&format!("[debug] job cache: {:#}", e));
+        }
+    }
+    (projects, true)
+}
+
+/// Background job list refreshes and the job picker they update.
+#[derive(Default)]
+struct JobListRefresh {
+    refreshing: std::collections::HashSet<String>, // service urls
+    completed: HashMap<String, u64>,               // refreshes saved per service url
+    picker: Option<String>,                        // service url of the open job picker
+    landed: bool,                                  // a refresh for `picker` was saved while it was open
+}
+
+static JOB_LIST_REFRESH: once_cell::sync::Lazy<std::sync::Mutex<JobListRefresh>> =
+    once_cell::sync::Lazy::new(Default::default);
+
+fn job_list_refresh() -> std::sync::MutexGuard<'static, JobListRefresh> {
+    JOB_LIST_REFRESH.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Fetch the job list into the cache without blocking the selection. Uses its own client,
+/// so the selected job's requests do not wait for the list. An open job picker of the service
+/// is interrupted to show the new list.
+fn refresh_projects_in_background(
+    jenkins_config: &JenkinsConfig,
+    global_config: Option<&GlobalConfig>,
+    cache_path: std::path::PathBuf,
+) {
+    let service_url = jenkins_config.url.clone();
+    if !job_list_refresh().refreshing.insert(service_url.clone()) {
+        return;
+    }
+    let client = JenkinsClient::from_service(jenkins_config, global_config);
+    tokio::spawn(async move {
+        let result = match client.get_projects().await {
+            Ok(jobs) => job_cache::save_cache(
+                &cache_path,
+                &JobCache {
+                    service_url: service_url.clone(),
+                    fetched_at: utils::current_timestamp(),
+                    jobs,
+                },
+            ),
+            Err(e) => Err(e),
+        };
+        let saved = result.is_ok();
+        if let Err(e) = result {
+            utils::debug_line(&format!("[debug] job cache refresh: {:#}", e));
+        }
+        {
+            let mut state = job_list_refresh();
+            state.refreshing.remove(&service_url);
+            if saved {
+                *state.completed.entry(service_url.clone()).or_default() += 1;
+                state.landed = state.picker.as_deref() == Some(service_url.as_str());
+            }
+        }
+        // Retry until the picker is closed: an interrupt sent just before it reads a key is lost
+        while {
+            let state = job_list_refresh();
+            state.landed && state.picker.as_deref() == Some(service_url.as_str())
+        } {
+            prompt::interrupt_prompt(prompt::PromptKind::FuzzySelectVim);
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+    });
+}
+
 /// Get project information from URL or selection
 async fn get_project(
     client: &std::sync::Arc<tokio::sync::RwLock<JenkinsClient>>,
     jenkins_config: &JenkinsConfig,
+    global_config: Option<&GlobalConfig>,
     history: &mut History,
     presets: &mut PresetStore,
+    refresh: bool,
 ) -> Option<jenkins::JenkinsJob> {
     if jenkins_config.url.contains("/job/") {
         match client.read().await.get_project(&jenkins_config.url).await {
@@ -616,90 +1334,80 @@ async fn get_project(
             }
         }
     } else {
-        let projects: Vec<jenkins::JenkinsJob> = {
-            let client_guard = client.read().await;
-            match client_guard.get_projects().await {
-                Ok(projects) => projects,
-                Err(e) => {
-                    eprintln!("{}: {}", t!("get-projects-failed"), e);
-                    std::process::exit(1);
-                }
-            }
-        };
-        let mut projects = filter_projects(projects, jenkins_config);
-
-        // Clean up obsolete history entries
-        let project_names: Vec<String> = projects.iter().map(|p| p.name.clone()).collect();
-        match history.cleanup_obsolete_projects(&project_names, &jenkins_config.url) {
-            Ok(removed) => {
-                if !removed.is_empty() {
-                    println!(
-                        "{}",
-                        t!("history-cleanup", "count" => removed.len().to_string(), "names" => removed.join(", "))
-                    );
-                }
-            }
-            Err(e) => {
-                eprintln!("{}", t!("history-cleanup-error", "error" => e.to_string()));
-            }
-        }
+        let mut refresh = refresh;
+        loop {
+            let completed_refreshes = job_list_refresh().completed.get(&jenkins_config.url).copied();
+            let (projects, fetched) = load_projects(client, jenkins_config, global_config, refresh).await;
+            refresh = false;
+            let mut projects = filter_projects(projects, jenkins_config);
 
-        match presets.cleanup_obsolete_projects(&project_names, &jenkins_config.url) {
-            Ok(removed) => {
-                if !removed.is_empty() {
-                    println!(
-                        "{}",
-                        t!("preset-cleanup", "count" => removed.len().to_string(), "names" => removed.join(", "))
-                    );
-                }
+            // Clean up obsolete history entries; a cached list may miss new jobs, so only with a fetched one
+            let project_names: Vec<String> = projects.iter().map(|p| p.name.clone()).collect();
+            if fetched {
+                cleanup_obsolete_projects(&project_names, jenkins_config, history, presets);
             }
-            Err(e) => {
-                eprintln!("{}", t!("preset-cleanup-error", "error" => e.to_string()));
-            }
-        }
 
-        // Get recent histories and reorder projects based on them
-        let recent_histories = history.get_recent_histories(&jenkins_config.url, Some(5));
+            // Get recent histories and reorder projects based on them
+            let recent_histories = history.get_recent_histories(&jenkins_config.url, Some(5));
 
-        // Promote recent projects to the front
-        for history_entry in recent_histories.iter().rev() {
-            if let Some(position) = projects.iter().position(|p| p.name == history_entry.name) {
-                if position > 0 {
-                    // Remove the project and insert it at the front
-                    let project = projects.remove(position);
-                    projects.insert(0, project);
+            // Promote recent projects to the front
+            for history_entry in recent_histories.iter().rev() {
+                if let Some(position) = projects.iter().position(|p| p.name == history_entry.name) {
+                    if position > 0 {
+                        // Remove the project and insert it at the front
+                        let project = projects.remove(position);
+                        projects.insert(0, project);
+                    }
                 }
             }
-        }
-        // println!("latest build: {}, {:?}", latest_index, latest_history);
+            // println!("latest build: {}, {:?}", latest_index, latest_history);
 
-        // Select project
-        let project_names: Vec<String> = projects
-            .iter()
-            .map(|p| format!("{} ({})", p.display_name, p.name))
-            .collect();
+            // Select project
+            let project_names: Vec<String> = projects
+                .iter()
+                .map(|p| format!("{} ({})", p.display_name, p.name))
+                .collect();
 
-        notify_if_update_available(); // before select project
+            notify_if_update_available(); // before select project
 
-        let selection =
-            prompt::handle_selection_opt(prompt::with_prompt_kind(prompt::PromptKind::FuzzySelectVim, || {
-                FuzzySelect::with_theme(&ColorfulTheme::default())
-                    .with_prompt(t!("select-project-prompt"))
-                    .items(&project_names)
-                    .default(0)
-                    // .report(false) // Display the selected project
-                    .vim_mode(true) // Esc, j|k
-                    .with_initial_text("")
-                    .interact_opt()
-            }));
+            {
+                let mut state = job_list_refresh();
+                if state.completed.get(&jenkins_config.url).copied() != completed_refreshes {
+                    continue; // refreshed while loading, show the new list
+                }
+                state.picker = Some(jenkins_config.url.clone());
+                state.landed = false;
+            }
+            let selection =
+                prompt::handle_selection_opt(prompt::with_prompt_kind(prompt::PromptKind::FuzzySelectVim, || {
+                    FuzzySelect::with_theme(&ColorfulTheme::default())
+                        .with_prompt(t!("select-project-prompt"))
+                        .items(&project_names)
+                        .default(0)
+                        // .report(false) // Display the selected project
+                        .vim_mode(true) // Esc, j|k
+                        .with_initial_text("")
+                        .interact_opt()
+                }));
+            let landed = {
+                let mut state = job_list_refresh();
+                state.picker = None;
+                std::mem::take(&mut state.landed)
+            };
 
-        // Check if user pressed Ctrl+C
-        match selection {
-            Some(idx) => {
-                let job = projects.get(idx).expect(&t!("select-project-failed"));
-                Some(job.clone())
+            // Check if user pressed Ctrl+C
+            match selection {
+                Some(idx) => {
+                    let job = projects.get(idx).expect(&t!("select-project-failed"));
+                    return Some(job.clone());
+                }
+                None if landed => {
+                    // Interrupted by the background refresh: show the new list
+                    prompt::clear_interrupted_fuzzy_select(project_names.len());
+                    println!("{}", t!("job-list-refreshed").dimmed());
+                }
+                None => return None, // Ctrl+C pressed - go back
             }
-            None => None, // Ctrl+C pressed - go back
         }
     }
 }
@@ -2,17 +2,191 @@ use crate::config::DATA_DIR;
 use crate::constants::ParamType;
 use crate::i18n::macros::t;
 use crate::jenkins::history::HISTORY_FILE;
-use anyhow::{Context, Result};
+use crate::storage;
+use anyhow::{anyhow, Context, Result};
+use colored::Colorize;
 use dirs::home_dir;
 use serde_json::json;
 use serde_json::Value as JsonValue;
 use serde_yaml::Value as YamlValue;
 use std::collections::HashMap;
 use std::fs;
-use std::io::Read;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use toml_edit::DocumentMut;
 
 pub const CURRENT_HISTORY_VERSION: u32 = 1; // latest version
+pub const CURRENT_PRESETS_VERSION: u32 = 1;
+pub const CURRENT_CONFIG_VERSION: u32 = 1;
+
+/// A store file with a top-level `version`, migrated as a whole document.
+pub trait VersionedDocument: Sized {
+    fn parse(content: &str) -> Result<Self>;
+    fn version(&self) -> Option<u32>;
+    fn set_version(&mut self, version: u32);
+    fn serialize(&self) -> Result<String>;
+}
+
+/// Data stores (history, presets), rewritten without comments.
+impl VersionedDocument for JsonValue {
+    fn parse(content: &str) -> Result<Self> {
+        toml::from_str(content).context("Failed to parse TOML")
+    }
+
+    fn version(&self) -> Option<u32> {
+        self.get("version")
+            .and_then(JsonValue::as_u64)
+            .and_then(|version| u32::try_from(version).ok())
+    }
+
+    fn set_version(&mut self, version: u32) {
+        self["version"] = json!(version);
+    }
+
+    fn serialize(&self) -> Result<String> {
+        toml::to_string(self).context("Failed to convert JSON to TOML")
+    }
+}
+
+/// The configuration file, edited in place to keep the user's comments and layout.
+impl VersionedDocument for DocumentMut {
+    fn parse(content: &str) -> Result<Self> {
+        content.parse::<DocumentMut>().context("Failed to parse TOML")
+    }
+
+    fn version(&self) -> Option<u32> {
+        self.get("version")
+            .and_then(|item| item.as_integer())
+            .and_then(|version| u32::try_from(version).ok())
+    }
+
+    fn set_version(&mut self, version: u32) {
+        self["version"] = toml_edit::value(i64::from(version));
+    }
+
+    fn serialize(&self) -> Result<String> {
+        Ok(self.to_string())
+    }
+}
+
+/// One step of a version ladder: migrates a document from `version - 1` to `version`.
+pub struct Migration<D> {
+    pub version: u32,
+    pub apply: fn(&mut D) -> Result<()>,
+}
+
+/// The version ladder of a store.
+pub struct Migrations<D: 'static> {
+    pub current_version: u32,
+    pub unversioned: u32, // version of files without a `version` key
+    pub steps: &'static [Migration<D>],
+}
+
+pub const HISTORY_MIGRATIONS: Migrations<JsonValue> = Migrations {
+    current_version: CURRENT_HISTORY_VERSION,
+    unversioned: 0,
+    steps: &[Migration {
+        version: 1,
+        apply: migrate_to_v1,
+    }],
+};
+
+/// Presets were versioned from the start.
+pub const PRESETS_MIGRATIONS: Migrations<JsonValue> = Migrations {
+    current_version: CURRENT_PRESETS_VERSION,
+    unversioned: CURRENT_PRESETS_VERSION,
+    steps: &[],
+};
+
+/// Configuration files without a `version` are the first version.
+pub const CONFIG_MIGRATIONS: Migrations<DocumentMut> = Migrations {
+    current_version: CURRENT_CONFIG_VERSION,
+    unversioned: 1,
+    steps: &[],
+};
+
+/// The file was written by a newer jenkins-cli; it is left untouched instead of being downgraded.
+#[derive(Debug)]
+pub struct NewerVersionError {
+    pub file: PathBuf,
+    pub version: u32,
+    pub supported: u32,
+}
+
+impl std::fmt::Display for NewerVersionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            t!("migration-newer-version", "file" => self.file.display().to_string(), "version" => self.version.to_string(), "supported" => self.supported.to_string())
+        )
+    }
+}
+
+impl std::error::Error for NewerVersionError {}
+
+/// Backup of a store before migrating it, e.g. `presets.toml.v1.bak`.
+pub fn backup_path(path: &Path, version: u32) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".v{}.bak", version));
+    path.with_file_name(file_name)
+}
+
+/// Migrate a store file to the current version of its ladder. The file is copied to
+/// [`backup_path`] first, and the new version is written to the file with the migrated data.
+/// Returns the previous version when the file was migrated; missing and empty files are skipped.
+pub fn run_migrations<D: VersionedDocument>(path: &Path, migrations: &Migrations<D>) -> Result<Option<u32>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    if content.trim().is_empty() {
+        return Ok(None);
+    }
+    let mut document = D::parse(&content).with_context(|| format!("Failed to parse {}", path.display()))?;
+    let version = document.version().unwrap_or(migrations.unversioned);
+    if version > migrations.current_version {
+        return Err(NewerVersionError {
+            file: path.to_path_buf(),
+            version,
+            supported: migrations.current_version,
+        }
+        .into());
+    }
+    if version == migrations.current_version {
+        return Ok(None);
+    }
+
+    for target in version + 1..=migrations.current_version {
+        let step = migrations
+            .steps
+            .iter()
+            .find(|step| step.version == target)
+            .ok_or_else(|| anyhow!("no migration to version {} for {}", target, path.display()))?;
+        (step.apply)(&mut document)
+            .with_context(|| format!("Failed to migrate {} to version {}", path.display(), target))?;
+    }
+    document.set_version(migrations.current_version);
+
+    let _lock = storage::lock_file(path)?;
+    let backup = backup_path(path, version);
+    fs::copy(path, &backup).with_context(|| format!("Failed to back up {}", path.display()))?;
+    storage::write_atomic(path, &document.serialize()?)?;
+    eprintln!(
+        "{}",
+        t!("migration-applied", "file" => path.display().to_string(), "from" => version.to_string(), "to" => migrations.current_version.to_string(), "backup" => backup.display().to_string()).dimmed()
+    );
+    Ok(Some(version))
+}
+
+/// Migrate the configuration file to the current version.
+pub fn migrate_config(config_path: &Path) -> Result<()> {
+    run_migrations(config_path, &CONFIG_MIGRATIONS).map(|_| ())
+}
+
+/// Migrate the presets file to the current version.
+pub fn migrate_presets(presets_path: &Path) -> Result<()> {
+    run_migrations(presets_path, &PRESETS_MIGRATIONS).map(|_| ())
+}
 
 /// Migrate config from yaml to toml
 pub fn migrate_config_yaml_to_toml(config_path: &PathBuf) -> Result<()> {
@@ -147,39 +321,7 @@ pub fn migrate_history() -> Result<()> {
     migrate_history_yaml_to_toml(&yaml_path, &history_path)?;
     migrate_history_location(&history_path)?;
 
-    if !history_path.exists() {
-        return Ok(());
-    }
-
-    let file = std::fs::File::open(&history_path).context("Failed to open history file")?;
-    let mut reader = std::io::BufReader::new(file);
-    let mut content = String::new();
-    reader
-        .read_to_string(&mut content)
-        .context("Failed to read file content")?;
-
-    if content.trim().is_empty() {
-        return Ok(());
-    }
-
-    // TOML => JSON
-    let mut json_value: JsonValue = toml::from_str(&content).context("Failed to parse TOML")?;
-    let version = json_value.get("version").and_then(JsonValue::as_u64).unwrap_or(0);
-    // println!("migrate_history: version = {}", version);
-
-    if version < CURRENT_HISTORY_VERSION as u64 {
-        for v in version..CURRENT_HISTORY_VERSION as u64 {
-            match v {
-                0 => migrate_to_v1(&mut json_value)?,
-                // 1 => migrate_to_v2(&mut json_value)?,
-                _ => break,
-            }
-        }
-
-        let migrated_toml = toml::to_string(&json_value).context("Failed to convert JSON to TOML")?;
-        fs::write(&history_path, migrated_toml).context("Failed to write migrated history")?;
-    }
-
+    run_migrations(&history_path, &HISTORY_MIGRATIONS)?;
     Ok(())
 }
 
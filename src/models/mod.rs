@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+
+use crate::constants::{DEFAULT_GIT_REMOTE, GIT_BRANCH_PARAM_NAMES};
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct Config {
@@ -25,6 +28,70 @@ pub struct GlobalConfig {
     pub timeout: Option<u64>, // HTTP request timeout in seconds, default 30
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub follow_downstream: Option<bool>, // follow detected downstream builds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_git_push: Option<bool>, // check that branch parameters are pushed before triggering, default false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify: Option<NotifyConfig>, // notifications when a build finishes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub freeze: Vec<FreezeWindow>, // times when triggering is blocked or warned, for all services
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub preset_sources: Vec<String>, // read-only preset files or directories shared by the team
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub templates: Vec<ParamTemplate>, // parameter templates for all services
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job_cache_ttl: Option<u64>, // seconds the cached job list is used before a background refresh, 0 disables the cache
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_runs: Option<usize>, // builds kept per job in history and the log book, default 10
+}
+
+/// Parameter values offered for every job that defines all of them, e.g. `ENV` and `REGION`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct ParamTemplate {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub params: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FreezeAction {
+    #[default]
+    Block, // refuse to trigger
+    Warn, // trigger after printing a warning
+}
+
+/// A deployment freeze, e.g. Friday after 16:00 or a date range around holidays.
+/// All set conditions must hold; times are local.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct FreezeWindow {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<String>, // weekdays, e.g. ["fri", "sat", "sun"]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>, // HH:MM, default 00:00
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>, // HH:MM (exclusive), default end of day; earlier than `from` wraps past midnight
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<String>, // YYYY-MM-DD, inclusive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>, // YYYY-MM-DD, inclusive
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub jobs: Vec<String>, // regex patterns of job names, default all jobs
+    #[serde(default)]
+    pub action: FreezeAction,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct NotifyConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bell: Option<bool>, // ring the terminal bell, default false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desktop: Option<bool>, // desktop notification (notify-send on Linux, osascript on macOS), default false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>, // POST a JSON payload to this URL
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -47,6 +114,133 @@ pub struct JenkinsConfig {
     pub excludes: Vec<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub enable_history: Option<bool>, // override global setting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<HooksConfig>, // shell hooks around triggering a build
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branches: Option<BranchConfig>, // branch parameter detection and branch sources
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub jobs: Vec<JobConfig>, // per-job settings, override the service settings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protected: Option<bool>, // require a typed confirmation before triggering, default false
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub freeze: Vec<FreezeWindow>, // in addition to the global freeze windows
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub templates: Vec<ParamTemplate>, // override global templates with the same name
+}
+
+impl JenkinsConfig {
+    /// Per-job settings matching a job name or display name, in configuration order.
+    pub fn job_configs<'a>(&'a self, job_name: &'a str, display_name: &'a str) -> impl Iterator<Item = &'a JobConfig> {
+        self.jobs
+            .iter()
+            .filter(move |job_config| match regex::Regex::new(&job_config.job) {
+                Ok(re) => re.is_match(job_name) || re.is_match(display_name),
+                Err(_) => job_config.job == job_name,
+            })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct HooksConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_build: Option<String>, // runs before triggering; a non-zero exit aborts the build
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_build: Option<String>, // runs after the build finishes
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BranchSource {
+    #[default]
+    Branches, // remote branches
+    Tags,    // tags, newest version first
+    Commits, // recent commits of HEAD
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct BranchConfig {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<String>, // case-insensitive regex patterns of branch parameter names, default GIT_BRANCH/gitBranch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>, // default origin
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<BranchSource>, // offered in this order, default ["branches"]
+}
+
+impl BranchConfig {
+    /// Whether a parameter holds a git branch (or tag/commit), e.g. `GIT_BRANCH`, `deploy_gitBranch`
+    pub fn is_branch_param(&self, name: &str) -> bool {
+        let default_patterns = GIT_BRANCH_PARAM_NAMES.map(|name| name.to_string());
+        let patterns = if self.params.is_empty() {
+            &default_patterns[..]
+        } else {
+            &self.params[..]
+        };
+        patterns
+            .iter()
+            .any(|pattern| match regex::Regex::new(&format!("(?i){}", pattern)) {
+                Ok(re) => re.is_match(name),
+                Err(_) => name.eq_ignore_ascii_case(pattern),
+            })
+    }
+
+    pub fn remote(&self) -> &str {
+        self.remote.as_deref().unwrap_or(DEFAULT_GIT_REMOTE)
+    }
+
+    pub fn sources(&self) -> Vec<BranchSource> {
+        if self.sources.is_empty() {
+            vec![BranchSource::Branches]
+        } else {
+            self.sources.clone()
+        }
+    }
+
+    /// Settings of `other` that are set override these.
+    pub fn merge(&self, other: &BranchConfig) -> BranchConfig {
+        BranchConfig {
+            params: if other.params.is_empty() {
+                self.params.clone()
+            } else {
+                other.params.clone()
+            },
+            remote: other.remote.clone().or_else(|| self.remote.clone()),
+            sources: if other.sources.is_empty() {
+                self.sources.clone()
+            } else {
+                other.sources.clone()
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct JobConfig {
+    #[serde(default)]
+    pub job: String, // regex matched against the job name or display name, like `includes`
+    #[serde(flatten)]
+    pub hooks: HooksConfig,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branches: Option<BranchConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protected: Option<bool>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub rules: BTreeMap<String, ParamRule>, // validation rules by parameter name
+}
+
+/// Validation rule of a build parameter; all set checks must pass.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct ParamRule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>, // value must not be empty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>, // value must match, e.g. '^\d+\.\d+\.\d+$'
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed: Vec<String>, // value must be one of these
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_tag: Option<bool>, // value must be an existing git tag
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>, // shown instead of the default error
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -7,6 +7,11 @@ static PROMPTING: AtomicBool = AtomicBool::new(false);
 static PROMPT_KIND: AtomicU8 = AtomicU8::new(PromptKind::Other as u8);
 #[cfg(windows)]
 static INPUT_INTERRUPT_REQUESTED: AtomicBool = AtomicBool::new(false);
+// Set by `interrupt_prompt`, so the resulting interrupt is not taken for a Ctrl+C press.
+static PROMPT_INTERRUPT_REQUESTED: AtomicBool = AtomicBool::new(false);
+// Thread showing the active prompt (`pthread_t`), signalled by `interrupt_prompt`.
+#[cfg(unix)]
+static PROMPT_THREAD: std::sync::Mutex<Option<usize>> = std::sync::Mutex::new(None);
 
 #[derive(Copy, Clone, PartialEq, Eq)]
 #[repr(u8)]
@@ -34,28 +39,99 @@ impl PromptKind {
 struct PromptStateGuard {
     prev_prompting: bool,
     prev_kind: u8,
+    #[cfg(unix)]
+    prev_thread: Option<usize>,
 }
 
 impl PromptStateGuard {
     fn enter(kind: PromptKind) -> Self {
         #[cfg(windows)]
         INPUT_INTERRUPT_REQUESTED.store(false, Ordering::SeqCst);
+        PROMPT_INTERRUPT_REQUESTED.store(false, Ordering::SeqCst);
         let prev_prompting = PROMPTING.swap(true, Ordering::SeqCst);
         let prev_kind = PROMPT_KIND.swap(kind as u8, Ordering::SeqCst);
         Self {
             prev_prompting,
             prev_kind,
+            #[cfg(unix)]
+            prev_thread: prompt_thread().replace(unsafe { libc::pthread_self() } as usize),
         }
     }
 }
 
 impl Drop for PromptStateGuard {
     fn drop(&mut self) {
+        #[cfg(unix)]
+        {
+            *prompt_thread() = self.prev_thread;
+        }
         PROMPT_KIND.store(self.prev_kind, Ordering::SeqCst);
         PROMPTING.store(self.prev_prompting, Ordering::SeqCst);
     }
 }
 
+#[cfg(unix)]
+fn prompt_thread() -> std::sync::MutexGuard<'static, Option<usize>> {
+    PROMPT_THREAD.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(unix)]
+extern "C" fn wake_prompt(_: libc::c_int) {}
+
+/// Make the active prompt of `kind` return as if it was backed out of (`None`),
+/// e.g. to show it again with new items. Returns `false` if no such prompt is active.
+/// On Unix the blocked terminal read is interrupted with `SIGUSR1`; a signal arriving just
+/// before the read is lost, so callers retry while the prompt is still active.
+pub fn interrupt_prompt(kind: PromptKind) -> bool {
+    if !is_prompting() || PROMPT_KIND.load(Ordering::SeqCst) != kind as u8 {
+        return false;
+    }
+    #[cfg(unix)]
+    {
+        static HANDLER: std::sync::Once = std::sync::Once::new();
+        HANDLER.call_once(|| unsafe {
+            // Without SA_RESTART, so the terminal poll returns EINTR
+            let mut action: libc::sigaction = std::mem::zeroed();
+            action.sa_sigaction = wake_prompt as *const () as usize;
+            libc::sigemptyset(&mut action.sa_mask);
+            libc::sigaction(libc::SIGUSR1, &action, std::ptr::null_mut());
+        });
+        let thread = prompt_thread();
+        let Some(thread) = *thread else {
+            return false;
+        };
+        PROMPT_INTERRUPT_REQUESTED.store(true, Ordering::SeqCst);
+        unsafe {
+            libc::pthread_kill(thread as libc::pthread_t, libc::SIGUSR1);
+        }
+    }
+    #[cfg(windows)]
+    if !PROMPT_INTERRUPT_REQUESTED.swap(true, Ordering::SeqCst) {
+        request_prompt_back();
+    }
+    true
+}
+
+/// Whether an interrupt seen by the Ctrl+C handler came from `interrupt_prompt` (clears the request).
+pub fn take_prompt_interrupt() -> bool {
+    PROMPT_INTERRUPT_REQUESTED.swap(false, Ordering::SeqCst)
+}
+
+/// Remove a `FuzzySelect` of `item_count` items left on screen by `interrupt_prompt`.
+/// On Windows the prompt quits normally and clears itself.
+pub fn clear_interrupted_fuzzy_select(item_count: usize) {
+    #[cfg(unix)]
+    {
+        // dialoguer shows the prompt line and up to the terminal height - 2 items
+        let term = console::Term::stderr();
+        let rows = (term.size().0 as usize).max(3) - 2;
+        let _ = term.clear_last_lines(1 + item_count.min(rows));
+        let _ = term.show_cursor();
+    }
+    #[cfg(not(unix))]
+    let _ = item_count;
+}
+
 /// Run a prompt while marking user input as active (prevents other readers from stealing input).
 pub fn with_prompt<F, R>(f: F) -> R
 where
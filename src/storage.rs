@@ -0,0 +1,114 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::hash::Hash;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::i18n::macros::t;
+
+/// Sidecar lock file of a store, e.g. `history.toml.lock`.
+/// The store itself is replaced on every write, so it cannot hold the lock.
+pub fn lock_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".lock");
+    path.with_file_name(file_name)
+}
+
+/// Take the advisory write lock of a store, blocking until other jenkins-cli processes release it.
+/// The lock is released when the returned file is dropped.
+pub fn lock_file(path: &Path) -> Result<File> {
+    let lock_path = lock_path(path);
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("Failed to open {}", lock_path.display()))?;
+    file.lock()
+        .with_context(|| format!("Failed to lock {}", lock_path.display()))?;
+    Ok(file)
+}
+
+/// Write a file through a temporary file in the same directory and a rename,
+/// so readers see either the old or the new content, never a partial one.
+pub fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".tmp-{}", std::process::id()));
+    let temp_path = path.with_file_name(file_name);
+    let result = (|| {
+        let mut file = File::create(&temp_path).with_context(|| format!("Failed to create {}", temp_path.display()))?;
+        file.write_all(content.as_bytes())
+            .with_context(|| format!("Failed to write {}", temp_path.display()))?;
+        file.sync_all()
+            .with_context(|| format!("Failed to write {}", temp_path.display()))?;
+        fs::rename(&temp_path, path).with_context(|| format!("Failed to replace {}", path.display()))
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Move a file that cannot be parsed aside, e.g. to `presets.toml.corrupted-20261018153000`,
+/// and warn about it. Returns the new location.
+pub fn quarantine_corrupted(path: &Path) -> Result<PathBuf> {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".corrupted-{}", chrono::Local::now().format("%Y%m%d%H%M%S")));
+    let corrupted_path = path.with_file_name(file_name);
+    fs::rename(path, &corrupted_path).with_context(|| format!("Failed to move {}", path.display()))?;
+    eprintln!(
+        "{}",
+        t!("store-corrupted", "file" => path.display().to_string(), "backup" => corrupted_path.display().to_string())
+            .yellow()
+    );
+    Ok(corrupted_path)
+}
+
+/// Three-way merge of keyed records: `base` is the file content this process loaded (or last saved),
+/// `ours` the records in memory and `theirs` the file content written since by other processes.
+///
+/// Changes of one side are kept; a record deleted on one side and unchanged on the other is removed.
+/// `resolve(base, ours, theirs)` merges a record changed on both sides. The order of `theirs` is kept,
+/// records only added by us are appended.
+pub fn merge_records<T, K, F, R>(base: &[T], ours: &[T], theirs: &[T], key: F, resolve: R) -> Vec<T>
+where
+    T: Clone + PartialEq,
+    K: Eq + Hash,
+    F: Fn(&T) -> K,
+    R: Fn(Option<&T>, &T, &T) -> T,
+{
+    let base_records: HashMap<K, &T> = base.iter().map(|record| (key(record), record)).collect();
+    let our_records: HashMap<K, &T> = ours.iter().map(|record| (key(record), record)).collect();
+    let their_keys: HashSet<K> = theirs.iter().map(&key).collect();
+
+    let mut merged = Vec::new();
+    for their in theirs {
+        let record_key = key(their);
+        let base_record = base_records.get(&record_key).copied();
+        match (our_records.get(&record_key).copied(), base_record) {
+            (Some(our), _) if Some(our) == base_record || our == their => merged.push(their.clone()),
+            (Some(our), Some(base_record)) if their == base_record => merged.push(our.clone()),
+            (Some(our), base_record) => merged.push(resolve(base_record, our, their)),
+            // Deleted by us; kept when they changed it meanwhile
+            (None, Some(base_record)) if their == base_record => {}
+            (None, _) => merged.push(their.clone()),
+        }
+    }
+    for our in ours {
+        let record_key = key(our);
+        if their_keys.contains(&record_key) {
+            continue;
+        }
+        // Deleted by them; kept when we changed it meanwhile
+        if base_records
+            .get(&record_key)
+            .is_some_and(|base_record| *base_record == our)
+        {
+            continue;
+        }
+        merged.push(our.clone());
+    }
+    merged
+}
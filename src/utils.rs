@@ -8,6 +8,8 @@ use url::Url;
 use once_cell::sync::Lazy;
 use std::io::stdout;
 
+use crate::constants::DEFAULT_GIT_REMOTE;
+
 static PATH_SLASHES_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"/{2,}|/+$").unwrap());
 static PROTOCOL_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^https?://").unwrap());
 
@@ -38,6 +40,41 @@ pub fn simplify_url(url: &str) -> String {
     PROTOCOL_RE.replace(url.trim_end_matches('/'), "").to_string()
 }
 
+/// Convert a glob pattern to an anchored regex.
+/// - `**` matches any characters including `/`
+/// - `*` matches any characters except `/`
+/// - `?` matches a single character except `/`
+/// # Examples
+/// ```rust
+/// use jenkins::utils::glob_to_regex;
+///
+/// assert!(glob_to_regex("target/*.jar").is_match("target/app.jar"));
+/// assert!(!glob_to_regex("*.jar").is_match("target/app.jar"));
+/// assert!(glob_to_regex("**/*.jar").is_match("a/b/app.jar"));
+/// ```
+pub fn glob_to_regex(pattern: &str) -> Regex {
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?"); // `**/` also matches zero directories
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).expect("escaped glob is a valid regex")
+}
+
 /// Enable debug logging when JENKINS_DEBUG is set.
 pub fn debug_enabled() -> bool {
     std::env::var("JENKINS_DEBUG").is_ok()
@@ -188,31 +225,81 @@ fn flush_stdin_impl() -> std::io::Result<()> {
     Ok(())
 }
 
+/// Run git and return its trimmed stdout, `None` if it fails.
+/// Credential prompts are disabled so network commands fail instead of blocking.
+pub fn git_output(args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
 /// get git repository (remote) branch list
+#[allow(dead_code)] // library API, the branch picker uses `get_remote_branches`
 pub fn get_git_branches() -> Vec<String> {
-    use std::process::Command;
+    get_remote_branches(DEFAULT_GIT_REMOTE)
+}
 
-    let output = Command::new("git").arg("branch").arg("-r").output();
+/// Branches of a remote (`git branch -r`), without the `<remote>/` prefix
+pub fn get_remote_branches(remote: &str) -> Vec<String> {
+    let prefix = format!("{}/", remote);
+    let Some(branches) = git_output(&["branch", "-r"]) else {
+        return vec![];
+    };
+    branches
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix(&prefix))
+        // exclude the default branch (origin/HEAD -> origin/main)
+        .filter(|branch| !branch.is_empty() && !branch.starts_with("HEAD"))
+        .map(|branch| branch.to_string())
+        .collect()
+}
 
-    if let Ok(output) = output {
-        let branches = String::from_utf8_lossy(&output.stdout);
-        // println!("{}", branches);
-        let excludes = [
-            // "/feature/", // feature branch
-            "/HEAD", // exclude the default branch (origin/HEAD)
-        ];
-        branches
-            .lines()
-            .filter(|line| {
-                let trimmed = line.trim();
-                // only need the branches of the remote repository (origin)
-                trimmed.starts_with("origin/") && !trimmed.is_empty() && !excludes.iter().any(|&s| trimmed.contains(s))
-            })
-            .map(|line| line.trim().replace("origin/", ""))
-            .collect()
-    } else {
-        vec![]
-    }
+/// get git tags
+pub fn get_git_tags() -> Vec<String> {
+    git_output(&["tag", "--list"])
+        .map(|tags| {
+            tags.lines()
+                .map(|tag| tag.trim().to_string())
+                .filter(|tag| !tag.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Recent commits of HEAD as `<short sha> <subject>`
+pub fn get_recent_commits(limit: usize) -> Vec<String> {
+    git_output(&["log", &format!("-n{}", limit), "--format=%h %s"])
+        .map(|log| log.lines().map(|line| line.to_string()).collect())
+        .unwrap_or_default()
+}
+
+/// Sort tags by semantic version, newest first (`v` prefixes allowed).
+/// Tags that are not versions follow in their original order.
+/// # Examples
+/// ```rust
+/// use jenkins::utils::sort_tags_by_semver;
+///
+/// let tags = vec!["v1.2.0".to_string(), "latest".to_string(), "v1.10.0".to_string(), "1.9.3".to_string()];
+/// assert_eq!(sort_tags_by_semver(tags), vec!["v1.10.0", "1.9.3", "v1.2.0", "latest"]);
+/// ```
+pub fn sort_tags_by_semver(tags: Vec<String>) -> Vec<String> {
+    let (mut versions, others): (Vec<(semver::Version, String)>, Vec<String>) =
+        tags.into_iter()
+            .fold((Vec::new(), Vec::new()), |(mut versions, mut others), tag| {
+                match semver::Version::parse(tag.trim_start_matches(['v', 'V'])) {
+                    Ok(version) => versions.push((version, tag)),
+                    Err(_) => others.push(tag),
+                }
+                (versions, others)
+            });
+    versions.sort_by(|(a, _), (b, _)| b.cmp(a));
+    versions.into_iter().map(|(_, tag)| tag).chain(others).collect()
 }
 
 /// get current git branch
@@ -0,0 +1,61 @@
+use jenkins::jenkins::artifacts::{artifact_dest_path, filter_artifacts, parse_build_artifacts, BuildArtifact};
+use serde_json::json;
+use std::path::Path;
+
+fn artifact(relative_path: &str) -> BuildArtifact {
+    BuildArtifact {
+        file_name: relative_path.rsplit('/').next().unwrap().to_string(),
+        relative_path: relative_path.to_string(),
+    }
+}
+
+#[test]
+fn parse_artifacts_from_build_api() {
+    let json_data = json!({
+        "_class": "hudson.model.FreeStyleBuild",
+        "artifacts": [
+            { "fileName": "app.jar", "relativePath": "target/app.jar" },
+            { "fileName": "report.html", "relativePath": "report.html" }
+        ]
+    });
+
+    let artifacts = parse_build_artifacts(&json_data);
+    assert_eq!(artifacts, vec![artifact("target/app.jar"), artifact("report.html")]);
+    assert!(parse_build_artifacts(&json!({})).is_empty());
+}
+
+#[test]
+fn filter_artifacts_by_glob() {
+    let artifacts = vec![
+        artifact("target/app.jar"),
+        artifact("target/app-sources.jar"),
+        artifact("dist/web/index.html"),
+        artifact("report.html"),
+    ];
+    let names = |pattern: &str| {
+        filter_artifacts(&artifacts, pattern)
+            .iter()
+            .map(|a| a.relative_path.clone())
+            .collect::<Vec<_>>()
+    };
+
+    // no slash: match the file name in any directory
+    assert_eq!(names("*.jar"), vec!["target/app.jar", "target/app-sources.jar"]);
+    assert_eq!(names("app.???"), vec!["target/app.jar"]);
+    // with slash: match the relative path
+    assert_eq!(names("target/*.jar"), vec!["target/app.jar", "target/app-sources.jar"]);
+    assert_eq!(names("dist/*"), Vec::<String>::new());
+    assert_eq!(names("dist/**"), vec!["dist/web/index.html"]);
+    assert_eq!(names("**/*.html"), vec!["dist/web/index.html", "report.html"]);
+}
+
+#[test]
+fn artifact_dest_path_stays_in_dest() {
+    let dest = Path::new("out");
+    assert_eq!(
+        artifact_dest_path(dest, &artifact("target/app.jar")),
+        Some(Path::new("out/target/app.jar").to_path_buf())
+    );
+    assert_eq!(artifact_dest_path(dest, &artifact("../app.jar")), None);
+    assert_eq!(artifact_dest_path(dest, &artifact("/etc/passwd")), None);
+}
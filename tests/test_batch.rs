@@ -0,0 +1,132 @@
+use jenkins::constants::ParamType;
+use jenkins::jenkins::batch::{apply_manifest_params, join_outcomes, load_manifest, service_for_job, BatchOutcome};
+use jenkins::jenkins::history::{History, HistoryEntry};
+use jenkins::jenkins::JenkinsJobParameter;
+use jenkins::models::JenkinsConfig;
+use std::fs;
+use tempfile::tempdir;
+
+const MANIFEST: &str = r#"
+[params]
+GIT_BRANCH = "release/1.2"
+DRY_RUN = false
+
+[[jobs]]
+url = "http://jenkins.example.com/job/api/"
+preset = "prod"
+
+[[jobs]]
+url = "http://jenkins.example.com/job/web/"
+params = { REPLICAS = 3, TYPO = "x" }
+"#;
+
+fn definitions() -> Vec<JenkinsJobParameter> {
+    vec![
+        JenkinsJobParameter {
+            param_type: Some(ParamType::String),
+            name: "GIT_BRANCH".to_string(),
+            default_value: Some("main".to_string()),
+            ..Default::default()
+        },
+        JenkinsJobParameter {
+            param_type: Some(ParamType::String),
+            name: "REPLICAS".to_string(),
+            default_value: Some("1".to_string()),
+            ..Default::default()
+        },
+    ]
+}
+
+#[test]
+fn load_batch_manifest() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("release.toml");
+    fs::write(&path, MANIFEST).unwrap();
+
+    let manifest = load_manifest(&path).unwrap();
+    assert_eq!(manifest.params.len(), 2);
+    assert_eq!(manifest.jobs.len(), 2);
+    assert_eq!(manifest.jobs[0].preset.as_deref(), Some("prod"));
+    assert!(manifest.jobs[0].params.is_empty());
+    assert_eq!(manifest.jobs[1].params.len(), 2);
+
+    fs::write(&path, "[params]\nA = 1\n").unwrap();
+    assert!(load_manifest(&path).is_err());
+}
+
+#[test]
+fn apply_shared_and_job_params() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("release.toml");
+    fs::write(&path, MANIFEST).unwrap();
+    let manifest = load_manifest(&path).unwrap();
+
+    let definitions = definitions();
+    let base = History::merge_parameters(&HistoryEntry::default(), &definitions);
+    let (params, unknown) = apply_manifest_params(base, &definitions, &manifest.params, &manifest.jobs[1].params);
+
+    assert_eq!(params["GIT_BRANCH"].value, "release/1.2");
+    assert_eq!(params["REPLICAS"].value, "3");
+    // shared DRY_RUN is not defined by the job and silently skipped
+    assert!(!params.contains_key("DRY_RUN"));
+    assert_eq!(unknown, vec!["TYPO".to_string()]);
+}
+
+#[tokio::test]
+async fn join_outcomes_reports_panicked_tasks() {
+    let success = |name: &str| {
+        let mut outcome = BatchOutcome::new(name);
+        outcome.result = "SUCCESS".to_string();
+        outcome
+    };
+    let first = success("api");
+    let last = success("worker");
+    let tasks = vec![
+        ("api".to_string(), tokio::spawn(async move { first })),
+        (
+            "web".to_string(),
+            tokio::spawn(async move {
+                if true {
+                    panic!("monitor failed");
+                }
+                BatchOutcome::new("web")
+            }),
+        ),
+        ("worker".to_string(), tokio::spawn(async move { last })),
+    ];
+
+    let outcomes = join_outcomes(tasks).await;
+    let names: Vec<&str> = outcomes.iter().map(|outcome| outcome.name.as_str()).collect();
+    assert_eq!(names, vec!["api", "web", "worker"]);
+    assert!(outcomes[0].is_success());
+    assert_eq!(outcomes[1].result, "ERROR");
+    assert!(outcomes[1].error.as_deref().unwrap().contains("panic"));
+    assert!(outcomes[2].is_success());
+}
+
+#[test]
+fn test_service_for_job_uses_the_service_of_each_job() {
+    let service = |name: &str, url: &str| JenkinsConfig {
+        name: name.to_string(),
+        url: url.to_string(),
+        ..Default::default()
+    };
+    let services = vec![
+        service("prod", "https://jenkins.example.com"),
+        service("team", "https://jenkins.example.com/team"),
+        service("staging", "https://staging.example.com"),
+    ];
+    // Selected with command line credentials
+    let selected = JenkinsConfig {
+        token: "cli-token".to_string(),
+        ..services[0].clone()
+    };
+
+    let find = |url: &str| service_for_job(&selected, &services, url);
+    let api = find("https://jenkins.example.com/job/api/");
+    assert_eq!((api.name.as_str(), api.token.as_str()), ("prod", "cli-token"));
+    assert_eq!(find("https://staging.example.com/job/api/").name, "staging");
+    assert_eq!(find("https://jenkins.example.com/team/job/web/").name, "team");
+    // Unknown services fall back to the selected one
+    assert_eq!(find("https://ci.example.org/job/api/").name, "prod");
+}
@@ -0,0 +1,132 @@
+use jenkins::constants::{ParamType, DEFAULT_PARAM_VALUE};
+use jenkins::jenkins::builds::{format_duration, obsolete_parameters, parse_build, parse_builds, rebuild_parameters};
+use jenkins::jenkins::JenkinsJobParameter;
+use serde_json::json;
+
+fn definitions() -> Vec<JenkinsJobParameter> {
+    vec![
+        JenkinsJobParameter {
+            param_type: Some(ParamType::String),
+            name: "BRANCH".to_string(),
+            default_value: Some("main".to_string()),
+            ..Default::default()
+        },
+        JenkinsJobParameter {
+            param_type: Some(ParamType::Boolean),
+            name: "DEPLOY".to_string(),
+            default_value: Some("false".to_string()),
+            ..Default::default()
+        },
+        JenkinsJobParameter {
+            param_type: Some(ParamType::Password),
+            name: "SECRET".to_string(),
+            default_value: Some(DEFAULT_PARAM_VALUE.to_string()),
+            ..Default::default()
+        },
+        JenkinsJobParameter {
+            param_type: Some(ParamType::Choice),
+            name: "ENV".to_string(),
+            default_value: Some("sit".to_string()),
+            choices: Some(vec!["sit".to_string(), "uat".to_string()]),
+            ..Default::default()
+        },
+    ]
+}
+
+fn builds_json() -> serde_json::Value {
+    json!({
+        "_class": "hudson.model.FreeStyleProject",
+        "builds": [
+            {
+                "_class": "hudson.model.FreeStyleBuild",
+                "number": 42,
+                "url": "http://jenkins.example.com/job/app/42/",
+                "result": "FAILURE",
+                "building": false,
+                "duration": 125000,
+                "timestamp": 1760000000000i64,
+                "actions": [
+                    {
+                        "_class": "hudson.model.ParametersAction",
+                        "parameters": [
+                            { "name": "BRANCH", "value": "feature/login" },
+                            { "name": "DEPLOY", "value": true },
+                            { "name": "SECRET" },
+                            { "name": "OLD_PARAM", "value": "x" }
+                        ]
+                    },
+                    {
+                        "_class": "hudson.model.CauseAction",
+                        "causes": [
+                            { "shortDescription": "Started by user Alice", "userId": "alice", "userName": "Alice" }
+                        ]
+                    },
+                    {}
+                ]
+            },
+            {
+                "number": 43,
+                "url": "http://jenkins.example.com/job/app/43/",
+                "result": null,
+                "building": true,
+                "actions": [{ "causes": [{ "shortDescription": "Started by timer" }] }]
+            }
+        ]
+    })
+}
+
+#[test]
+fn parse_builds_reads_result_cause_and_parameters() {
+    let builds = parse_builds(&builds_json());
+    assert_eq!(builds.len(), 2);
+
+    let build = &builds[0];
+    assert_eq!(build.number, 42);
+    assert_eq!(build.status(), "FAILURE");
+    assert_eq!(build.duration, 125000);
+    assert_eq!(build.triggered_by.as_deref(), Some("Alice"));
+    assert_eq!(build.causes, vec!["Started by user Alice"]);
+    assert_eq!(
+        build.parameters[0],
+        ("BRANCH".to_string(), Some("feature/login".to_string()))
+    );
+    assert_eq!(build.parameters[1], ("DEPLOY".to_string(), Some("true".to_string())));
+    assert_eq!(build.parameters[2], ("SECRET".to_string(), None));
+
+    assert_eq!(builds[1].status(), "BUILDING");
+    assert_eq!(builds[1].triggered_by, None);
+    assert_eq!(builds[1].causes, vec!["Started by timer"]);
+}
+
+#[test]
+fn rebuild_parameters_use_current_definitions() {
+    let builds = parse_builds(&builds_json());
+    let params = rebuild_parameters(&builds[0], &definitions());
+
+    assert_eq!(params["BRANCH"].value, "feature/login");
+    assert_eq!(params["DEPLOY"].value, "true");
+    assert_eq!(params["DEPLOY"].r#type, ParamType::Boolean);
+    // hidden password value keeps the Jenkins default
+    assert_eq!(params["SECRET"].value, DEFAULT_PARAM_VALUE);
+    // new parameter uses its default, removed parameter is dropped
+    assert_eq!(params["ENV"].value, "sit");
+    assert!(!params.contains_key("OLD_PARAM"));
+}
+
+#[test]
+fn format_build_duration() {
+    assert_eq!(format_duration(8_400), "8s");
+    assert_eq!(format_duration(125_000), "2m 05s");
+    assert_eq!(format_duration(3_723_000), "1h 02m 03s");
+}
+
+#[test]
+fn parse_single_build_and_find_obsolete_parameters() {
+    let build = parse_build(&builds_json()["builds"][0]);
+    assert_eq!(build.number, 42);
+    assert_eq!(build.parameters.len(), 4);
+    assert_eq!(
+        obsolete_parameters(&build, &definitions()),
+        vec!["OLD_PARAM".to_string()]
+    );
+}
@@ -0,0 +1,149 @@
+use jenkins::constants::ParamType;
+use jenkins::jenkins::batch::BatchJob;
+use jenkins::jenkins::captures::{capture_from_text, parse_injected_env_vars, CaptureSource};
+use jenkins::jenkins::template::{has_captures, placeholders, render_captures, render_params};
+use jenkins::jenkins::ParamInfo;
+use serde_json::json;
+use std::collections::HashMap;
+
+fn captured() -> HashMap<String, String> {
+    HashMap::from([
+        ("IMAGE_TAG".to_string(), "1.2.3-abc".to_string()),
+        ("VERSION".to_string(), "1.2.3".to_string()),
+    ])
+}
+
+#[test]
+fn render_capture_placeholders() {
+    let captured = captured();
+    assert_eq!(
+        render_captures("registry/app:{{capture.IMAGE_TAG}}", &captured).unwrap(),
+        "registry/app:1.2.3-abc"
+    );
+    assert_eq!(
+        render_captures("v{{ capture.VERSION }}-{{capture.VERSION}}", &captured).unwrap(),
+        "v1.2.3-1.2.3"
+    );
+    assert_eq!(render_captures("plain", &captured).unwrap(), "plain");
+
+    assert!(render_captures("{{capture.MISSING}}", &captured).is_err());
+    // Other placeholders are not ours to resolve
+    assert_eq!(render_captures("{{env:USER}}", &captured).unwrap(), "{{env:USER}}");
+    assert_eq!(
+        render_captures("{{capture.VERSION", &captured).unwrap(),
+        "{{capture.VERSION"
+    );
+}
+
+#[test]
+fn list_placeholders() {
+    assert_eq!(
+        placeholders("{{capture.A}}/{{ capture.B }}/{{open"),
+        vec!["capture.A", "capture.B"]
+    );
+    assert!(placeholders("no templates").is_empty());
+}
+
+#[test]
+fn detect_capture_placeholders() {
+    assert!(has_captures("registry/app:{{ capture.IMAGE_TAG }}"));
+    assert!(!has_captures("{{git.sha}}"));
+    assert!(!has_captures("1.2.3"));
+}
+
+#[test]
+fn render_captures_into_params() {
+    let params = HashMap::from([
+        (
+            "TAG".to_string(),
+            ParamInfo {
+                value: "{{capture.IMAGE_TAG}}".to_string(),
+                r#type: ParamType::String,
+            },
+        ),
+        (
+            "DRY_RUN".to_string(),
+            ParamInfo {
+                value: "false".to_string(),
+                r#type: ParamType::Boolean,
+            },
+        ),
+    ]);
+    let rendered = render_params(&params, &captured()).unwrap();
+    assert_eq!(rendered["TAG"].value, "1.2.3-abc");
+    assert_eq!(rendered["DRY_RUN"].value, "false");
+    assert_eq!(rendered["DRY_RUN"].r#type, ParamType::Boolean);
+
+    let error = render_params(&params, &HashMap::new()).unwrap_err();
+    assert!(error.to_string().starts_with("TAG:"));
+}
+
+#[test]
+fn render_params_keeps_other_templates() {
+    // e.g. a Jinja template passed to the job as it is
+    let params = HashMap::from([
+        (
+            "MESSAGE".to_string(),
+            ParamInfo {
+                value: "Deployed {{ version }} with {{capture.IMAGE_TAG}}".to_string(),
+                r#type: ParamType::Text,
+            },
+        ),
+        (
+            "TAG".to_string(),
+            ParamInfo {
+                value: "{{capture.IMAGE_TAG}}".to_string(),
+                r#type: ParamType::String,
+            },
+        ),
+    ]);
+    let rendered = render_params(&params, &captured()).unwrap();
+    assert_eq!(rendered["MESSAGE"].value, "Deployed {{ version }} with 1.2.3-abc");
+    assert_eq!(rendered["TAG"].value, "1.2.3-abc");
+}
+
+#[test]
+fn capture_values_from_console_text() {
+    let log = "Step 5/5\nSuccessfully tagged registry/app:1.2.3-abc\nversion=1.2.3 \nFinished: SUCCESS";
+    assert_eq!(
+        capture_from_text(log, r"tagged \S+:(\S+)").unwrap().as_deref(),
+        Some("1.2.3-abc")
+    );
+    // without a group the whole match is used
+    assert_eq!(
+        capture_from_text(log, r"version=\S+").unwrap().as_deref(),
+        Some("version=1.2.3")
+    );
+    assert_eq!(capture_from_text(log, "not in log").unwrap(), None);
+    assert!(capture_from_text(log, "(unclosed").is_err());
+}
+
+#[test]
+fn parse_env_inject_variables() {
+    let json_data = json!({
+        "_class": "org.jenkinsci.plugins.envinject.EnvInjectVarList",
+        "envMap": { "GIT_COMMIT": "abc123", "BUILD_NUMBER": "42" }
+    });
+    let env_vars = parse_injected_env_vars(&json_data);
+    assert_eq!(env_vars["GIT_COMMIT"], "abc123");
+    assert_eq!(env_vars.len(), 2);
+    assert!(parse_injected_env_vars(&json!({})).is_empty());
+}
+
+#[test]
+fn deserialize_capture_sources() {
+    let job: BatchJob = toml::from_str(
+        r#"
+url = "http://jenkins.example.com/job/api/"
+captures = { TAG = { log = "tag: (\\S+)" }, NOTE = "description", COMMIT = { env = "GIT_COMMIT" }, VERSION = { artifact = "dist/version.txt" } }
+"#,
+    )
+    .unwrap();
+    assert_eq!(job.captures["TAG"], CaptureSource::Log(r"tag: (\S+)".to_string()));
+    assert_eq!(job.captures["NOTE"], CaptureSource::Description);
+    assert_eq!(job.captures["COMMIT"], CaptureSource::Env("GIT_COMMIT".to_string()));
+    assert_eq!(
+        job.captures["VERSION"],
+        CaptureSource::Artifact("dist/version.txt".to_string())
+    );
+}
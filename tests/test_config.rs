@@ -56,3 +56,30 @@ includes = ["后端", "backend"]
 
     assert_eq!(toml_content.trim(), expected_toml.trim());
 }
+
+#[test]
+fn test_find_service_for_url() {
+    use jenkins::config::find_service_for_url;
+    use jenkins::models::JenkinsConfig;
+
+    let service = |name: &str, url: &str| JenkinsConfig {
+        name: name.to_string(),
+        url: url.to_string(),
+        ..Default::default()
+    };
+    let services = vec![
+        service("root", "https://jenkins.example.com"),
+        service("team", "https://jenkins.example.com/team/"),
+        service("other", "http://ci.example.org"),
+    ];
+
+    let find = |url: &str| find_service_for_url(&services, url).map(|s| s.name);
+    assert_eq!(find("http://jenkins.example.com/").as_deref(), Some("root"));
+    assert_eq!(find("https://jenkins.example.com/job/app/42/").as_deref(), Some("root"));
+    assert_eq!(
+        find("https://jenkins.example.com/team/job/app/42/").as_deref(),
+        Some("team")
+    );
+    assert_eq!(find("https://jenkins.example.community/job/app/").as_deref(), None);
+    assert_eq!(find("https://unknown.example.com/job/app/").as_deref(), None);
+}
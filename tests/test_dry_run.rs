@@ -0,0 +1,107 @@
+use jenkins::constants::{ParamType, DEFAULT_PARAM_VALUE, MASKED_PASSWORD};
+use jenkins::jenkins::client::JenkinsClient;
+use jenkins::jenkins::dry_run::{
+    curl_command, curl_env_vars, form_body, masked_form_fields, preset_command, shell_quote,
+};
+use jenkins::jenkins::ParamInfo;
+use std::collections::HashMap;
+
+fn param(value: &str, r#type: ParamType) -> ParamInfo {
+    ParamInfo {
+        value: value.to_string(),
+        r#type,
+    }
+}
+
+fn params() -> HashMap<String, ParamInfo> {
+    HashMap::from([
+        ("GIT_BRANCH".to_string(), param("release/1.2", ParamType::String)),
+        ("SKIP_TESTS".to_string(), param("true", ParamType::Boolean)),
+        ("DB_PASSWORD".to_string(), param("s3cret", ParamType::Password)),
+        ("NOTES".to_string(), param(DEFAULT_PARAM_VALUE, ParamType::Text)),
+    ])
+}
+
+#[test]
+fn trigger_request_skips_default_values() {
+    let (url, form) = JenkinsClient::trigger_request("http://jenkins.example.com/job/app/", &params());
+    assert_eq!(url, "http://jenkins.example.com/job/app/buildWithParameters");
+    assert_eq!(form.len(), 3);
+    assert!(!form.contains_key("NOTES"));
+
+    let (url, form) = JenkinsClient::trigger_request("http://jenkins.example.com/job/app/", &HashMap::new());
+    assert_eq!(url, "http://jenkins.example.com/job/app/build");
+    assert!(form.is_empty());
+}
+
+#[test]
+fn form_fields_mask_passwords() {
+    let fields = masked_form_fields(&params());
+    assert_eq!(
+        fields,
+        vec![
+            ("DB_PASSWORD".to_string(), MASKED_PASSWORD.to_string()),
+            ("GIT_BRANCH".to_string(), "release/1.2".to_string()),
+            ("SKIP_TESTS".to_string(), "true".to_string()),
+        ]
+    );
+    assert_eq!(
+        form_body(&fields),
+        "DB_PASSWORD=*******&GIT_BRANCH=release%2F1.2&SKIP_TESTS=true"
+    );
+}
+
+#[test]
+fn commands_are_shell_quoted() {
+    assert_eq!(shell_quote("release/1.2"), "release/1.2");
+    assert_eq!(shell_quote("two words"), "'two words'");
+    assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    assert_eq!(shell_quote(""), "''");
+
+    assert_eq!(
+        preset_command("http://jenkins.example.com/job/app/", "prod deploy", true),
+        "jenkins -U http://jenkins.example.com/job/app/ --preset 'prod deploy' --yes-i-mean-prod"
+    );
+    assert_eq!(
+        curl_command(
+            "http://jenkins.example.com/job/app/buildWithParameters",
+            &HashMap::from([("MESSAGE".to_string(), param("hello world", ParamType::Text))])
+        ),
+        "curl -X POST --user \"$JENKINS_USER:$JENKINS_TOKEN\" http://jenkins.example.com/job/app/buildWithParameters \\\n  --data-urlencode 'MESSAGE=hello world'"
+    );
+}
+
+#[test]
+fn curl_command_reads_passwords_from_the_environment() {
+    let params = HashMap::from([
+        ("DB_PASSWORD".to_string(), param("s3cret", ParamType::Password)),
+        ("api.key".to_string(), param("k3y", ParamType::Password)),
+        ("GIT_BRANCH".to_string(), param("main", ParamType::String)),
+    ]);
+    let command = curl_command("http://jenkins.example.com/job/app/buildWithParameters", &params);
+    assert_eq!(
+        command,
+        "curl -X POST --user \"$JENKINS_USER:$JENKINS_TOKEN\" http://jenkins.example.com/job/app/buildWithParameters \\\n  --data-urlencode \"DB_PASSWORD=$DB_PASSWORD\" \\\n  --data-urlencode GIT_BRANCH=main \\\n  --data-urlencode api.key=\"$API_KEY\""
+    );
+    assert!(!command.contains("s3cret") && !command.contains(MASKED_PASSWORD));
+    assert_eq!(
+        curl_env_vars(&params),
+        vec!["JENKINS_USER", "JENKINS_TOKEN", "DB_PASSWORD", "API_KEY"]
+    );
+}
+
+#[test]
+fn password_env_vars_are_valid_shell_names() {
+    let params = HashMap::from([
+        ("db_pass".to_string(), param("s3cret", ParamType::Password)),
+        ("deploy-token".to_string(), param("t0ken", ParamType::Password)),
+        ("clé".to_string(), param("k3y", ParamType::Password)),
+    ]);
+    assert_eq!(
+        curl_env_vars(&params),
+        vec!["JENKINS_USER", "JENKINS_TOKEN", "CL_", "DB_PASS", "DEPLOY_TOKEN"]
+    );
+    let command = curl_command("http://jenkins.example.com/job/app/buildWithParameters", &params);
+    assert!(command.contains("--data-urlencode db_pass=\"$DB_PASS\""));
+    assert!(command.contains("--data-urlencode deploy-token=\"$DEPLOY_TOKEN\""));
+}
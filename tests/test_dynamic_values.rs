@@ -0,0 +1,90 @@
+use jenkins::constants::ParamType;
+use jenkins::jenkins::template::{dynamic_value, render_dynamic_params};
+use jenkins::jenkins::ParamInfo;
+use std::collections::HashMap;
+
+fn param(value: &str, r#type: ParamType) -> ParamInfo {
+    ParamInfo {
+        value: value.to_string(),
+        r#type,
+    }
+}
+
+#[test]
+fn resolves_env_and_date_values() {
+    std::env::set_var("JENKINS_CLI_TEST_USER", "alice");
+    assert_eq!(dynamic_value("env:JENKINS_CLI_TEST_USER").unwrap().unwrap(), "alice");
+    assert!(dynamic_value("env:JENKINS_CLI_TEST_MISSING").unwrap().is_err());
+
+    let date = dynamic_value("date:%Y%m%d").unwrap().unwrap();
+    assert_eq!(date, chrono::Local::now().format("%Y%m%d").to_string());
+    assert!(dynamic_value("date:%Q").unwrap().is_err());
+
+    assert!(dynamic_value("capture.TAG").is_none());
+    assert!(dynamic_value("prompt").is_none());
+}
+
+#[test]
+fn renders_dynamic_params_and_keeps_other_placeholders() {
+    std::env::set_var("JENKINS_CLI_TEST_DEPLOYER", "bob");
+    let params = HashMap::from([
+        (
+            "DEPLOYER".to_string(),
+            param("{{ env:JENKINS_CLI_TEST_DEPLOYER }}", ParamType::String),
+        ),
+        (
+            "IMAGE".to_string(),
+            param("api:{{capture.IMAGE_TAG}}", ParamType::String),
+        ),
+        ("NOTES".to_string(), param("{{unknown}}", ParamType::Text)),
+        ("TICKET".to_string(), param("OPS-{{prompt}}", ParamType::String)),
+        ("TOKEN".to_string(), param("{{prompt}}", ParamType::Password)),
+    ]);
+
+    let mut asked = Vec::new();
+    let rendered = render_dynamic_params(&params, |name, param| {
+        asked.push((name.to_string(), param.r#type.clone()));
+        Some("42".to_string())
+    })
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        asked,
+        vec![
+            ("TICKET".to_string(), ParamType::String),
+            ("TOKEN".to_string(), ParamType::Password)
+        ]
+    );
+    assert_eq!(rendered["DEPLOYER"].value, "bob");
+    assert_eq!(rendered["IMAGE"].value, "api:{{capture.IMAGE_TAG}}");
+    assert_eq!(rendered["NOTES"].value, "{{unknown}}");
+    assert_eq!(rendered["TICKET"].value, "OPS-42");
+    assert_eq!(rendered["TOKEN"].r#type, ParamType::Password);
+}
+
+#[test]
+fn keeps_other_placeholders_as_written() {
+    std::env::set_var("JENKINS_CLI_TEST_ENV", "sit");
+    let params = HashMap::from([(
+        "IMAGE".to_string(),
+        param(
+            "{{ capture.TAG }}-{{env:JENKINS_CLI_TEST_ENV}}-{{ other }}",
+            ParamType::String,
+        ),
+    )]);
+    let rendered = render_dynamic_params(&params, |_, _| None).unwrap().unwrap();
+    assert_eq!(rendered["IMAGE"].value, "{{ capture.TAG }}-sit-{{ other }}");
+}
+
+#[test]
+fn cancelled_prompt_and_errors() {
+    let params = HashMap::from([("TICKET".to_string(), param("{{prompt}}", ParamType::String))]);
+    assert_eq!(render_dynamic_params(&params, |_, _| None).unwrap(), None);
+
+    let params = HashMap::from([(
+        "USER".to_string(),
+        param("{{env:JENKINS_CLI_TEST_UNSET}}", ParamType::String),
+    )]);
+    let error = render_dynamic_params(&params, |_, _| None).unwrap_err();
+    assert!(error.to_string().starts_with("USER: "));
+}
@@ -0,0 +1,152 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use jenkins::jenkins::policy::{active_freezes, freeze_active, freeze_applies_to, freeze_label};
+use jenkins::models::{FreezeAction, FreezeWindow, GlobalConfig, JenkinsConfig};
+
+fn at(date: &str, time: &str) -> NaiveDateTime {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .unwrap()
+        .and_time(chrono::NaiveTime::parse_from_str(time, "%H:%M").unwrap())
+}
+
+fn window(toml_str: &str) -> FreezeWindow {
+    toml::from_str(toml_str).unwrap()
+}
+
+#[test]
+fn weekday_and_time_window() {
+    // 2026-10-16 is a Friday
+    let friday_afternoon = window(
+        r#"
+days = ["fri"]
+from = "16:00"
+"#,
+    );
+    assert!(!freeze_active(&friday_afternoon, at("2026-10-16", "15:59")).unwrap());
+    assert!(freeze_active(&friday_afternoon, at("2026-10-16", "16:00")).unwrap());
+    assert!(freeze_active(&friday_afternoon, at("2026-10-16", "23:59")).unwrap());
+    assert!(!freeze_active(&friday_afternoon, at("2026-10-15", "17:00")).unwrap());
+    assert!(!freeze_active(&friday_afternoon, at("2026-10-17", "17:00")).unwrap());
+}
+
+#[test]
+fn time_window_wraps_past_midnight() {
+    // allowed hours 09:00-18:00
+    let outside_hours = window(
+        r#"
+from = "18:00"
+to = "09:00"
+"#,
+    );
+    assert!(freeze_active(&outside_hours, at("2026-10-14", "22:00")).unwrap());
+    assert!(freeze_active(&outside_hours, at("2026-10-14", "08:59")).unwrap());
+    assert!(!freeze_active(&outside_hours, at("2026-10-14", "09:00")).unwrap());
+    assert!(!freeze_active(&outside_hours, at("2026-10-14", "17:59")).unwrap());
+}
+
+#[test]
+fn wrapping_window_belongs_to_its_start_day() {
+    // 2026-10-16 is a Friday
+    let friday_night = window(
+        r#"
+days = ["fri"]
+from = "22:00"
+to = "06:00"
+"#,
+    );
+    assert!(!freeze_active(&friday_night, at("2026-10-16", "21:59")).unwrap());
+    assert!(freeze_active(&friday_night, at("2026-10-16", "22:00")).unwrap());
+    assert!(freeze_active(&friday_night, at("2026-10-17", "02:00")).unwrap());
+    assert!(!freeze_active(&friday_night, at("2026-10-17", "06:00")).unwrap());
+    // Friday 02:00 belongs to Thursday night
+    assert!(!freeze_active(&friday_night, at("2026-10-16", "02:00")).unwrap());
+
+    let new_year_nights = window(
+        r#"
+start = "2026-12-31"
+end = "2026-12-31"
+from = "20:00"
+to = "04:00"
+"#,
+    );
+    assert!(freeze_active(&new_year_nights, at("2026-12-31", "23:00")).unwrap());
+    assert!(freeze_active(&new_year_nights, at("2027-01-01", "03:59")).unwrap());
+    assert!(!freeze_active(&new_year_nights, at("2026-12-31", "03:00")).unwrap());
+    assert!(!freeze_active(&new_year_nights, at("2027-01-01", "23:00")).unwrap());
+}
+
+#[test]
+fn date_range_is_inclusive() {
+    let holidays = window(
+        r#"
+name = "Holidays"
+start = "2026-12-24"
+end = "2027-01-02"
+"#,
+    );
+    assert!(!freeze_active(&holidays, at("2026-12-23", "23:59")).unwrap());
+    assert!(freeze_active(&holidays, at("2026-12-24", "00:00")).unwrap());
+    assert!(freeze_active(&holidays, at("2027-01-02", "23:59")).unwrap());
+    assert!(!freeze_active(&holidays, at("2027-01-03", "00:00")).unwrap());
+}
+
+#[test]
+fn invalid_values_are_errors() {
+    for toml_str in [r#"from = "4pm""#, r#"days = ["friyay"]"#, r#"start = "24.12.2026""#] {
+        assert!(
+            freeze_active(&window(toml_str), at("2026-10-16", "12:00")).is_err(),
+            "{}",
+            toml_str
+        );
+    }
+}
+
+#[test]
+fn job_patterns_and_labels() {
+    let deploys = window(
+        r#"
+days = ["fri", "sat"]
+from = "16:00"
+jobs = ["^deploy-"]
+action = "warn"
+"#,
+    );
+    assert_eq!(deploys.action, FreezeAction::Warn);
+    assert!(freeze_applies_to(&deploys, "deploy-api", "API"));
+    assert!(!freeze_applies_to(&deploys, "test-api", "API"));
+    assert!(freeze_applies_to(&window(""), "test-api", "API"));
+    assert_eq!(freeze_label(&deploys), "fri,sat 16:00-");
+    assert_eq!(freeze_label(&window(r#"start = "2026-12-24""#)), "2026-12-24..");
+}
+
+#[test]
+fn global_and_service_windows_apply() {
+    let global: GlobalConfig =
+        toml::from_str(r#"freeze = [{ name = "Holidays", start = "2026-12-24", end = "2027-01-02" }]"#).unwrap();
+    let service: JenkinsConfig = toml::from_str(
+        r#"
+name = "PROD"
+url = "https://jenkins.example.com"
+freeze = [{ name = "Friday", days = ["fri"], from = "16:00", jobs = ["^deploy-"] }]
+"#,
+    )
+    .unwrap();
+    let names = |job: &str, now| -> Vec<String> {
+        active_freezes(Some(&global), &service, job, job, now)
+            .unwrap()
+            .into_iter()
+            .map(freeze_label)
+            .collect()
+    };
+    assert_eq!(names("deploy-api", at("2026-10-16", "17:00")), vec!["Friday"]);
+    assert!(names("test-api", at("2026-10-16", "17:00")).is_empty());
+    // 2026-12-25 is a Friday
+    assert_eq!(
+        names("deploy-api", at("2026-12-25", "17:00")),
+        vec!["Holidays", "Friday"]
+    );
+    assert!(
+        active_freezes(None, &service, "deploy-api", "API", at("2026-12-25", "12:00"))
+            .unwrap()
+            .is_empty()
+    );
+}
@@ -0,0 +1,182 @@
+use jenkins::constants::ParamType;
+use jenkins::jenkins::git_check::{
+    branch_issues, branch_params, can_push, find_local_remote, issue_message, option_value, resolve_branch_config,
+    BranchIssue, BranchState,
+};
+use jenkins::jenkins::ParamInfo;
+use jenkins::models::{BranchConfig, BranchSource, JenkinsConfig};
+use std::collections::HashMap;
+
+fn param(value: &str) -> ParamInfo {
+    ParamInfo {
+        value: value.to_string(),
+        r#type: ParamType::String,
+    }
+}
+
+fn state(local: Option<&str>, remote: Option<&str>) -> BranchState {
+    BranchState {
+        branch: "feature/login".to_string(),
+        is_current: true,
+        local_sha: local.map(|sha| sha.to_string()),
+        remote_sha: remote.map(|sha| sha.to_string()),
+        dirty: false,
+    }
+}
+
+#[test]
+fn detect_branch_params() {
+    let config = BranchConfig::default();
+    assert!(config.is_branch_param("GIT_BRANCH"));
+    assert!(config.is_branch_param("deploy_git_branch"));
+    assert!(config.is_branch_param("gitBranch"));
+    assert!(!config.is_branch_param("BRANCH"));
+
+    let params = HashMap::from([
+        ("GIT_BRANCH".to_string(), param("main")),
+        ("FRONTEND_GIT_BRANCH".to_string(), param("main")),
+        ("BACKEND_GIT_BRANCH".to_string(), param(" release/1.2 ")),
+        ("OTHER_GIT_BRANCH".to_string(), param("")),
+        ("ENV".to_string(), param("sit")),
+    ]);
+    assert_eq!(
+        branch_params(&params, &config),
+        vec!["release/1.2".to_string(), "main".to_string()]
+    );
+}
+
+#[test]
+fn configured_branch_params() {
+    let config = BranchConfig {
+        params: vec!["^(BRANCH|REF|TAG)$".to_string()],
+        ..Default::default()
+    };
+    assert!(config.is_branch_param("branch"));
+    assert!(config.is_branch_param("REF"));
+    assert!(!config.is_branch_param("GIT_BRANCH"));
+    assert_eq!(config.remote(), "origin");
+    assert_eq!(config.sources(), vec![BranchSource::Branches]);
+}
+
+#[test]
+fn job_branch_config_overrides_service() {
+    let config: JenkinsConfig = toml::from_str(
+        r#"
+name = "SIT"
+url = "https://jenkins.example.com"
+branches = { params = ["BRANCH"], remote = "upstream" }
+
+[[jobs]]
+job = "^release-"
+branches = { sources = ["tags", "commits"] }
+
+[[jobs]]
+job = "^release-web"
+branches = { remote = "gitlab", sources = ["branches"] }
+"#,
+    )
+    .unwrap();
+
+    let branches = resolve_branch_config(&config, "release-web", "Release Web");
+    assert_eq!(branches.params, vec!["BRANCH".to_string()]);
+    assert_eq!(branches.remote(), "gitlab");
+    // the first matching job wins
+    assert_eq!(branches.sources(), vec![BranchSource::Tags, BranchSource::Commits]);
+
+    let branches = resolve_branch_config(&config, "build-api", "");
+    assert_eq!(branches.remote(), "upstream");
+    assert_eq!(branches.sources(), vec![BranchSource::Branches]);
+}
+
+#[test]
+fn picker_option_values() {
+    assert_eq!(option_value("release/1.2"), "release/1.2");
+    assert_eq!(option_value("1a2b3c4 Fix login redirect"), "1a2b3c4");
+}
+
+#[test]
+fn pushed_branch_has_no_issues() {
+    let state = state(Some("0123456789abcdef"), Some("0123456789abcdef"));
+    assert!(branch_issues(&state).is_empty());
+}
+
+#[test]
+fn unpushed_and_uncommitted_changes() {
+    let mut state = state(Some("0123456789abcdef"), Some("fedcba9876543210"));
+    state.dirty = true;
+    let issues = branch_issues(&state);
+    assert_eq!(
+        issues,
+        vec![
+            BranchIssue::HeadMismatch {
+                local: "01234567".to_string(),
+                remote: "fedcba98".to_string(),
+            },
+            BranchIssue::Uncommitted,
+        ]
+    );
+    assert!(can_push(&state, &issues));
+
+    // uncommitted changes on another branch do not matter
+    state.is_current = false;
+    assert_eq!(branch_issues(&state).len(), 1);
+}
+
+#[test]
+fn branch_missing_on_remote() {
+    let state = state(Some("0123456789abcdef"), None);
+    let issues = branch_issues(&state);
+    assert_eq!(issues, vec![BranchIssue::NotOnRemote]);
+    assert!(can_push(&state, &issues));
+
+    // nothing to push without a local branch (e.g. a typo)
+    let mut state = state;
+    state.local_sha = None;
+    state.is_current = false;
+    let issues = branch_issues(&state);
+    assert_eq!(issues, vec![BranchIssue::NotOnRemote]);
+    assert!(!can_push(&state, &issues));
+}
+
+#[test]
+fn remote_only_branch_is_fine() {
+    let mut state = state(None, Some("0123456789abcdef"));
+    state.is_current = false;
+    assert!(branch_issues(&state).is_empty());
+}
+
+#[test]
+fn push_check_remote_must_exist_locally() {
+    let remotes = vec![
+        ("origin".to_string(), "git@github.com:team/app.git".to_string()),
+        ("fork".to_string(), "https://github.com/me/app".to_string()),
+    ];
+    assert_eq!(find_local_remote("origin", &remotes).as_deref(), Some("origin"));
+    assert_eq!(
+        find_local_remote("git@github.com:team/app", &remotes).as_deref(),
+        Some("origin")
+    );
+    assert_eq!(
+        find_local_remote("https://github.com/me/app.git", &remotes).as_deref(),
+        Some("fork")
+    );
+    // Another project's repository
+    assert_eq!(find_local_remote("upstream", &remotes), None);
+    assert_eq!(find_local_remote("git@github.com:team/api.git", &remotes), None);
+    assert_eq!(find_local_remote("origin", &[]), None);
+}
+
+#[test]
+fn head_mismatch_message_names_the_remote() {
+    let state = state(Some("1111111122222222"), Some("3333333344444444"));
+    let issue = BranchIssue::HeadMismatch {
+        local: "11111111".to_string(),
+        remote: "33333333".to_string(),
+    };
+    // Remove fluent's bidi isolates
+    let message = issue_message(&state, &issue, "upstream").replace(['\u{2068}', '\u{2069}'], "");
+    assert_eq!(
+        message,
+        "Local feature/login (11111111) differs from upstream/feature/login (33333333), the build uses the remote commit"
+    );
+}
@@ -16,6 +16,8 @@ fn setup_test_history() -> (History, tempfile::TempDir) {
         entries: vec![],
         file_path,
         version: None,
+        saved: vec![],
+        max_runs: None,
     };
     (history, temp_dir)
 }
@@ -37,6 +39,7 @@ fn test_upsert_history() {
         params: None,
         created_at: None,
         completed_at: None,
+        runs: Vec::new(),
     };
 
     history.upsert_history(&mut entry).unwrap();
@@ -60,6 +63,7 @@ fn test_get_history() {
         params: None,
         created_at: Some(1000),
         completed_at: None,
+        runs: Vec::new(),
     };
     history.upsert_history(&mut entry.clone()).unwrap();
 
@@ -116,6 +120,124 @@ fn test_update_field() {
     assert_eq!(updated.completed_at, Some(3000));
 }
 
+fn string_params(value: &str) -> HashMap<String, ParamInfo> {
+    HashMap::from([(
+        "VERSION".to_string(),
+        ParamInfo {
+            value: value.to_string(),
+            r#type: ParamType::String,
+        },
+    )])
+}
+
+#[test]
+fn test_upsert_history_keeps_recent_runs() {
+    let (mut history, _temp_dir) = setup_test_history();
+    for i in 0..MAX_RUNS_PER_JOB + 2 {
+        let mut entry = HistoryEntry {
+            job_url: format!("{}/job1", BASE_URL),
+            name: "Job1".to_string(),
+            params: Some(string_params(&format!("1.0.{}", i))),
+            ..Default::default()
+        };
+        history.upsert_history(&mut entry).unwrap();
+    }
+
+    assert_eq!(history.entries.len(), 1);
+    let entry = &history.entries[0];
+    assert_eq!(entry.runs.len(), MAX_RUNS_PER_JOB);
+    let newest = format!("1.0.{}", MAX_RUNS_PER_JOB + 1);
+    assert_eq!(entry.runs[0].params["VERSION"].value, newest);
+    assert_eq!(entry.params.as_ref().unwrap()["VERSION"].value, newest);
+    assert_eq!(entry.runs[MAX_RUNS_PER_JOB - 1].params["VERSION"].value, "1.0.2");
+
+    // the file round-trips
+    history.load_history().unwrap();
+    assert_eq!(history.entries[0].runs.len(), MAX_RUNS_PER_JOB);
+}
+
+#[test]
+fn test_upsert_history_keeps_configured_runs() {
+    let (mut history, _temp_dir) = setup_test_history();
+    history.max_runs = Some(3);
+    for i in 0..5 {
+        let mut entry = HistoryEntry {
+            job_url: format!("{}/job1", BASE_URL),
+            name: "Job1".to_string(),
+            params: Some(string_params(&format!("1.0.{}", i))),
+            ..Default::default()
+        };
+        history.upsert_history(&mut entry).unwrap();
+    }
+
+    let entry = &history.entries[0];
+    assert_eq!(entry.runs.len(), 3);
+    assert_eq!(entry.runs[2].params["VERSION"].value, "1.0.2");
+}
+
+#[test]
+fn test_complete_run() {
+    let (mut history, _temp_dir) = setup_test_history();
+    let mut entry = HistoryEntry {
+        job_url: format!("{}/job1", BASE_URL),
+        name: "Job1".to_string(),
+        params: Some(string_params("1.0.0")),
+        ..Default::default()
+    };
+    history.upsert_history(&mut entry).unwrap();
+    let build = jenkins::jenkins::builds::BuildSummary {
+        number: 42,
+        result: Some("FAILURE".to_string()),
+        duration: 65_000,
+        ..Default::default()
+    };
+    let build_url = format!("{}/job1/42/", BASE_URL);
+    history
+        .complete_run(&entry, &build_url, Some(&build), Some("release"))
+        .unwrap();
+
+    let run = &history.get_history(&entry, BASE_URL).unwrap().runs[0];
+    assert_eq!(run.build_url.as_deref(), Some(build_url.as_str()));
+    assert_eq!(run.number, Some(42));
+    assert_eq!(run.result.as_deref(), Some("FAILURE"));
+    assert_eq!(run.duration, Some(65_000));
+    assert_eq!(run.preset.as_deref(), Some("release"));
+    assert!(run.completed_at.is_some());
+    assert!(run.display_line().contains("#42"));
+    assert!(run.display_line().contains("VERSION=1.0.0"));
+}
+
+#[test]
+fn test_recent_runs_of_entries_without_runs() {
+    // entries saved before runs were recorded
+    let entry = HistoryEntry {
+        job_url: format!("{}/job1", BASE_URL),
+        name: "Job1".to_string(),
+        params: Some(string_params("0.9.0")),
+        created_at: Some(1000),
+        ..Default::default()
+    };
+    let runs = entry.recent_runs();
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0].params["VERSION"].value, "0.9.0");
+    assert_eq!(runs[0].created_at, Some(1000));
+    assert!(HistoryEntry::default().recent_runs().is_empty());
+
+    let (mut history, _temp_dir) = setup_test_history();
+    history.entries.push(entry.clone());
+    let mut next = HistoryEntry {
+        params: Some(string_params("1.0.0")),
+        ..entry.clone()
+    };
+    history.upsert_history(&mut next).unwrap();
+    let runs = &history.entries[0].runs;
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[1].params["VERSION"].value, "0.9.0");
+
+    let older = history.entries[0].with_run(&runs[1]);
+    assert_eq!(older.params.unwrap()["VERSION"].value, "0.9.0");
+}
+
 #[test]
 fn test_apply_history_defaults() {
     let mut params = HashMap::new();
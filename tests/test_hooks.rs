@@ -0,0 +1,130 @@
+use jenkins::constants::{ParamType, MASKED_PASSWORD};
+use jenkins::jenkins::hooks::{hook_env, param_env_name, resolve_hooks, run_hook, HookContext};
+use jenkins::jenkins::ParamInfo;
+use jenkins::models::{HooksConfig, JenkinsConfig, JobConfig};
+use std::collections::HashMap;
+
+fn service() -> JenkinsConfig {
+    toml::from_str(
+        r#"
+name = "PROD"
+url = "https://jenkins.example.com"
+hooks = { pre_build = "./check-clean-tree.sh", post_build = "./announce.sh" }
+
+[[jobs]]
+job = "^deploy-"
+pre_build = "./check-pushed.sh"
+"#,
+    )
+    .unwrap()
+}
+
+fn params() -> HashMap<String, ParamInfo> {
+    HashMap::from([(
+        "git-branch".to_string(),
+        ParamInfo {
+            value: "release/1.2".to_string(),
+            r#type: ParamType::String,
+        },
+    )])
+}
+
+#[test]
+fn job_hooks_override_service_hooks() {
+    let config = service();
+    assert_eq!(
+        config.jobs,
+        vec![JobConfig {
+            job: "^deploy-".to_string(),
+            hooks: HooksConfig {
+                pre_build: Some("./check-pushed.sh".to_string()),
+                post_build: None,
+            },
+            ..Default::default()
+        }]
+    );
+
+    let hooks = resolve_hooks(&config, "deploy-api", "Deploy API");
+    assert_eq!(hooks.pre_build.as_deref(), Some("./check-pushed.sh"));
+    assert_eq!(hooks.post_build.as_deref(), Some("./announce.sh"));
+
+    let hooks = resolve_hooks(&config, "build-api", "Build API");
+    assert_eq!(hooks.pre_build.as_deref(), Some("./check-clean-tree.sh"));
+
+    assert_eq!(
+        resolve_hooks(&JenkinsConfig::default(), "deploy-api", ""),
+        HooksConfig::default()
+    );
+}
+
+#[test]
+fn hook_environment() {
+    assert_eq!(param_env_name("git-branch"), "JENKINS_PARAM_GIT_BRANCH");
+    assert_eq!(param_env_name("Env"), "JENKINS_PARAM_ENV");
+
+    let params = params();
+    let env: HashMap<String, String> = hook_env(&HookContext {
+        job_url: "https://jenkins.example.com/job/deploy-api",
+        job_name: "deploy-api",
+        params: Some(&params),
+        build_url: Some("https://jenkins.example.com/job/deploy-api/42/"),
+        result: Some("SUCCESS"),
+    })
+    .into_iter()
+    .collect();
+    assert_eq!(env["JENKINS_JOB_URL"], "https://jenkins.example.com/job/deploy-api");
+    assert_eq!(env["JENKINS_JOB_NAME"], "deploy-api");
+    assert_eq!(env["JENKINS_PARAM_GIT_BRANCH"], "release/1.2");
+    assert_eq!(env["JENKINS_PARAMS"], r#"{"git-branch":"release/1.2"}"#);
+    assert_eq!(
+        env["JENKINS_BUILD_URL"],
+        "https://jenkins.example.com/job/deploy-api/42/"
+    );
+    assert_eq!(env["JENKINS_BUILD_RESULT"], "SUCCESS");
+
+    // before the build there is no build URL or result
+    let env = hook_env(&HookContext {
+        job_url: "https://jenkins.example.com/job/deploy-api",
+        job_name: "deploy-api",
+        ..Default::default()
+    });
+    assert!(!env.iter().any(|(name, _)| name == "JENKINS_BUILD_URL"));
+}
+
+#[test]
+fn hook_env_masks_passwords() {
+    let mut params = params();
+    params.insert(
+        "DB_PASSWORD".to_string(),
+        ParamInfo {
+            value: "s3cret".to_string(),
+            r#type: ParamType::Password,
+        },
+    );
+    let env: HashMap<String, String> = hook_env(&HookContext {
+        job_url: "https://jenkins.example.com/job/deploy-api",
+        job_name: "deploy-api",
+        params: Some(&params),
+        ..Default::default()
+    })
+    .into_iter()
+    .collect();
+    assert_eq!(env["JENKINS_PARAM_DB_PASSWORD"], MASKED_PASSWORD);
+    assert!(!env["JENKINS_PARAMS"].contains("s3cret"));
+    assert_eq!(env["JENKINS_PARAM_GIT_BRANCH"], "release/1.2");
+}
+
+#[cfg(unix)]
+#[test]
+fn run_hook_exit_status() {
+    let params = params();
+    let context = HookContext {
+        job_url: "https://jenkins.example.com/job/deploy-api",
+        job_name: "deploy-api",
+        params: Some(&params),
+        ..Default::default()
+    };
+    assert!(run_hook(r#"test "$JENKINS_PARAM_GIT_BRANCH" = "release/1.2""#, &context).is_ok());
+    let error = run_hook("exit 3", &context).unwrap_err();
+    assert!(error.to_string().contains("exit code 3"));
+}
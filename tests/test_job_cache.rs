@@ -0,0 +1,68 @@
+use jenkins::jenkins::job_cache::*;
+use jenkins::jenkins::JenkinsJob;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn job(name: &str) -> JenkinsJob {
+    JenkinsJob {
+        name: name.to_string(),
+        display_name: name.to_string(),
+        url: format!("http://jenkins.example.com:8080/job/{}/", name),
+        _class: "hudson.model.FreeStyleProject".to_string(),
+        jobs: None,
+    }
+}
+
+#[test]
+fn test_cache_path() {
+    let dir = Path::new("/data/job-cache");
+    assert_eq!(
+        cache_path(dir, "http://jenkins.example.com:8080/"),
+        dir.join("jenkins.example.com_8080.toml")
+    );
+    assert_eq!(
+        cache_path(dir, "https://ci.example.com/jenkins"),
+        dir.join("ci.example.com_jenkins.toml")
+    );
+}
+
+#[test]
+fn test_is_fresh() {
+    let cache = JobCache {
+        fetched_at: 1_000,
+        ..Default::default()
+    };
+    assert!(cache.is_fresh(60, 1_059));
+    assert!(!cache.is_fresh(60, 1_060));
+    assert!(!cache.is_fresh(0, 1_000));
+    // A clock set back does not keep the cache forever
+    assert!(!cache.is_fresh(60, 900));
+}
+
+#[test]
+fn test_save_and_load_cache() {
+    let temp_dir = tempdir().unwrap();
+    let path = cache_path(&temp_dir.path().join(JOB_CACHE_DIR), "http://jenkins.example.com:8080");
+    assert!(load_cache(&path).is_none());
+
+    let cache = JobCache {
+        service_url: "http://jenkins.example.com:8080".to_string(),
+        fetched_at: 1_000,
+        jobs: vec![job("deploy-api"), job("folder/deploy-web")],
+    };
+    save_cache(&path, &cache).unwrap();
+
+    let loaded = load_cache(&path).unwrap();
+    assert_eq!(loaded.service_url, cache.service_url);
+    assert_eq!(loaded.fetched_at, 1_000);
+    let names: Vec<&str> = loaded.jobs.iter().map(|job| job.name.as_str()).collect();
+    assert_eq!(names, vec!["deploy-api", "folder/deploy-web"]);
+}
+
+#[test]
+fn test_unreadable_cache_is_ignored() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("jenkins.toml");
+    std::fs::write(&path, "jobs = [ broken").unwrap();
+    assert!(load_cache(&path).is_none());
+}
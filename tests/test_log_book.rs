@@ -0,0 +1,171 @@
+use chrono::{Local, NaiveDate, TimeZone};
+use jenkins::constants::ParamType;
+use jenkins::jenkins::history::{History, HistoryEntry, HistoryRun};
+use jenkins::jenkins::log_book::{compute_stats, find_runs, parse_date, parse_param_filter, LogBookFilter};
+use jenkins::jenkins::ParamInfo;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+fn timestamp(date: &str) -> i64 {
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap();
+    Local
+        .from_local_datetime(&date.and_hms_opt(12, 0, 0).unwrap())
+        .unwrap()
+        .timestamp()
+}
+
+fn run(branch: &str, result: Option<&str>, duration: Option<u64>, preset: Option<&str>, date: &str) -> HistoryRun {
+    HistoryRun {
+        params: HashMap::from([(
+            "GIT_BRANCH".to_string(),
+            ParamInfo {
+                value: branch.to_string(),
+                r#type: ParamType::String,
+            },
+        )]),
+        result: result.map(str::to_string),
+        duration,
+        preset: preset.map(str::to_string),
+        created_at: Some(timestamp(date)),
+        ..Default::default()
+    }
+}
+
+fn entry(job_url: &str, name: &str, runs: Vec<HistoryRun>) -> HistoryEntry {
+    HistoryEntry {
+        job_url: job_url.to_string(),
+        name: name.to_string(),
+        display_name: None,
+        params: None,
+        created_at: runs.first().and_then(|run| run.created_at),
+        completed_at: None,
+        runs,
+    }
+}
+
+fn history() -> History {
+    History {
+        entries: vec![
+            entry(
+                "jenkins.example.com/job/deploy-api",
+                "deploy-api",
+                vec![
+                    run(
+                        "release/1.3",
+                        Some("FAILURE"),
+                        Some(120_000),
+                        Some("release"),
+                        "2026-10-03",
+                    ),
+                    run(
+                        "release/1.2",
+                        Some("SUCCESS"),
+                        Some(60_000),
+                        Some("release"),
+                        "2026-10-02",
+                    ),
+                    run("main", Some("SUCCESS"), Some(30_000), None, "2026-09-20"),
+                ],
+            ),
+            entry(
+                "ci.example.com/job/deploy-web",
+                "deploy-web",
+                vec![run("release/2.0", None, None, Some("web"), "2026-10-04")],
+            ),
+        ],
+        file_path: PathBuf::new(),
+        version: None,
+        saved: vec![],
+        max_runs: None,
+    }
+}
+
+#[test]
+fn finds_all_runs_newest_first() {
+    let history = history();
+    let records = find_runs(&history, &LogBookFilter::default());
+    let branches: Vec<&str> = records
+        .iter()
+        .map(|record| record.run.params["GIT_BRANCH"].value.as_str())
+        .collect();
+    assert_eq!(branches, vec!["release/2.0", "release/1.3", "release/1.2", "main"]);
+}
+
+#[test]
+fn filters_by_param_glob_and_result() {
+    let history = history();
+    let filter = LogBookFilter {
+        params: vec![parse_param_filter("GIT_BRANCH=release/*").unwrap()],
+        result: Some("success".to_string()),
+        ..Default::default()
+    };
+    let records = find_runs(&history, &filter);
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].run.params["GIT_BRANCH"].value, "release/1.2");
+}
+
+#[test]
+fn filters_by_service_job_and_dates() {
+    let history = history();
+    let filter = LogBookFilter {
+        service_url: Some("https://jenkins.example.com/".to_string()),
+        since: Some(parse_date("2026-10-01").unwrap()),
+        until: Some(parse_date("2026-10-02").unwrap()),
+        ..Default::default()
+    };
+    let records = find_runs(&history, &filter);
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].entry.name, "deploy-api");
+
+    let filter = LogBookFilter {
+        job: Some(Regex::new("web$").unwrap()),
+        ..Default::default()
+    };
+    assert_eq!(find_runs(&history, &filter).len(), 1);
+}
+
+#[test]
+fn service_filter_does_not_match_longer_hosts() {
+    let mut history = history();
+    history.entries.push(entry(
+        "https://jenkins.example.com2/job/deploy-api",
+        "deploy-api",
+        vec![run("main", Some("SUCCESS"), None, None, "2026-10-05")],
+    ));
+    let filter = LogBookFilter {
+        service_url: Some("https://jenkins.example.com".to_string()),
+        ..Default::default()
+    };
+    let records = find_runs(&history, &filter);
+    assert_eq!(records.len(), 3);
+    assert!(records
+        .iter()
+        .all(|record| record.entry.job_url.starts_with("jenkins.example.com/")));
+}
+
+#[test]
+fn rejects_invalid_filters() {
+    assert!(parse_param_filter("GIT_BRANCH").is_err());
+    assert!(parse_param_filter("=main").is_err());
+    assert!(parse_date("10/02/2026").is_err());
+}
+
+#[test]
+fn computes_stats() {
+    let history = history();
+    let stats = compute_stats(&find_runs(&history, &LogBookFilter::default()));
+    assert_eq!(stats.runs, 4);
+    assert_eq!(stats.finished, 3);
+    assert_eq!(stats.succeeded, 2);
+    assert!((stats.success_rate().unwrap() - 66.666).abs() < 0.01);
+    assert_eq!(stats.median_duration, Some(60_000));
+    assert_eq!(
+        stats.top_presets,
+        vec![("release".to_string(), 2), ("web".to_string(), 1)]
+    );
+
+    let stats = compute_stats(&[]);
+    assert_eq!(stats.success_rate(), None);
+    assert_eq!(stats.median_duration, None);
+}
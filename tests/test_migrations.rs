@@ -0,0 +1,192 @@
+use anyhow::Result;
+use jenkins::migrations::*;
+use serde_json::Value as JsonValue;
+use std::fs;
+use tempfile::tempdir;
+use toml_edit::DocumentMut;
+
+fn add_enabled(json: &mut JsonValue) -> Result<()> {
+    json["enabled"] = JsonValue::Bool(true);
+    Ok(())
+}
+
+fn rename_name(json: &mut JsonValue) -> Result<()> {
+    let name = json.as_object_mut().unwrap().remove("name").unwrap_or_default();
+    json["title"] = name;
+    Ok(())
+}
+
+const TEST_MIGRATIONS: Migrations<JsonValue> = Migrations {
+    current_version: 2,
+    unversioned: 0,
+    steps: &[
+        Migration {
+            version: 2,
+            apply: rename_name,
+        },
+        Migration {
+            version: 1,
+            apply: add_enabled,
+        },
+    ],
+};
+
+fn read_json(path: &std::path::Path) -> JsonValue {
+    toml::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn test_backup_path() {
+    assert_eq!(
+        backup_path(std::path::Path::new("/data/presets.toml"), 1),
+        std::path::PathBuf::from("/data/presets.toml.v1.bak")
+    );
+}
+
+#[test]
+fn test_run_migrations_applies_ladder_and_backs_up() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("store.toml");
+    fs::write(&path, "name = \"app\"\n").unwrap();
+
+    assert_eq!(run_migrations(&path, &TEST_MIGRATIONS).unwrap(), Some(0));
+
+    let migrated = read_json(&path);
+    assert_eq!(migrated["version"], 2);
+    assert_eq!(migrated["enabled"], true);
+    assert_eq!(migrated["title"], "app");
+    assert!(migrated.get("name").is_none());
+    let backup = fs::read_to_string(backup_path(&path, 0)).unwrap();
+    assert_eq!(backup, "name = \"app\"\n");
+
+    // Already current: nothing to do
+    assert_eq!(run_migrations(&path, &TEST_MIGRATIONS).unwrap(), None);
+}
+
+#[test]
+fn test_run_migrations_starts_at_file_version() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("store.toml");
+    fs::write(&path, "version = 1\nname = \"app\"\n").unwrap();
+
+    assert_eq!(run_migrations(&path, &TEST_MIGRATIONS).unwrap(), Some(1));
+    let migrated = read_json(&path);
+    assert_eq!(migrated["version"], 2);
+    assert!(migrated.get("enabled").is_none());
+    assert!(backup_path(&path, 1).exists());
+}
+
+#[test]
+fn test_run_migrations_refuses_newer_version() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("store.toml");
+    let content = "version = 3\nname = \"app\"\n";
+    fs::write(&path, content).unwrap();
+
+    let error = run_migrations(&path, &TEST_MIGRATIONS).unwrap_err();
+    let newer = error.downcast_ref::<NewerVersionError>().unwrap();
+    assert_eq!((newer.version, newer.supported), (3, 2));
+    assert_eq!(fs::read_to_string(&path).unwrap(), content);
+    assert!(!backup_path(&path, 3).exists());
+}
+
+#[test]
+fn test_run_migrations_skips_missing_and_empty_files() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("store.toml");
+    assert_eq!(run_migrations(&path, &TEST_MIGRATIONS).unwrap(), None);
+    fs::write(&path, "\n").unwrap();
+    assert_eq!(run_migrations(&path, &TEST_MIGRATIONS).unwrap(), None);
+    assert!(!backup_path(&path, 0).exists());
+}
+
+#[test]
+fn test_run_migrations_missing_step() {
+    const INCOMPLETE: Migrations<JsonValue> = Migrations {
+        current_version: 2,
+        unversioned: 0,
+        steps: &[Migration {
+            version: 1,
+            apply: add_enabled,
+        }],
+    };
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("store.toml");
+    let content = "name = \"app\"\n";
+    fs::write(&path, content).unwrap();
+
+    assert!(run_migrations(&path, &INCOMPLETE).is_err());
+    assert_eq!(fs::read_to_string(&path).unwrap(), content);
+}
+
+#[test]
+fn test_history_migrations_v0() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("history.toml");
+    fs::write(
+        &path,
+        r#"version = 0
+
+[[entries]]
+job_url = "http://jenkins/job/app"
+name = "app"
+
+[entries.user_params]
+BRANCH = "main"
+DB_PASSWORD = "s3cret"
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(run_migrations(&path, &HISTORY_MIGRATIONS).unwrap(), Some(0));
+    let migrated = read_json(&path);
+    assert_eq!(migrated["version"], CURRENT_HISTORY_VERSION);
+    let params = &migrated["entries"][0]["params"];
+    assert_eq!(params["BRANCH"]["value"], "main");
+    assert_eq!(params["DB_PASSWORD"]["type"], "password");
+    assert!(migrated["entries"][0].get("user_params").is_none());
+}
+
+#[test]
+fn test_presets_without_version_are_current() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("presets.toml");
+    fs::write(&path, "jobs = []\n").unwrap();
+    assert_eq!(run_migrations(&path, &PRESETS_MIGRATIONS).unwrap(), None);
+}
+
+fn add_timeout(document: &mut DocumentMut) -> Result<()> {
+    document["config"]["timeout"] = toml_edit::value(30);
+    Ok(())
+}
+
+#[test]
+fn test_config_migrations_keep_comments() {
+    const CONFIG_V2: Migrations<DocumentMut> = Migrations {
+        current_version: 2,
+        unversioned: 1,
+        steps: &[Migration {
+            version: 2,
+            apply: add_timeout,
+        }],
+    };
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join(".jenkins.toml");
+    fs::write(
+        &path,
+        "[config]\n# locale = \"en-US\"\n\n[[jenkins]]\nname = \"ci\" # main server\n",
+    )
+    .unwrap();
+
+    assert_eq!(run_migrations(&path, &CONFIG_V2).unwrap(), Some(1));
+    let content = fs::read_to_string(&path).unwrap();
+    assert!(content.starts_with("version = 2\n"));
+    assert!(content.contains("# locale = \"en-US\""));
+    assert!(content.contains("name = \"ci\" # main server"));
+    assert!(content.contains("timeout = 30"));
+    assert!(backup_path(&path, 1).exists());
+
+    // The current ladder has no steps yet: unversioned files are left as they are
+    fs::write(&path, "[config]\n").unwrap();
+    assert_eq!(run_migrations(&path, &CONFIG_MIGRATIONS).unwrap(), None);
+}
@@ -0,0 +1,115 @@
+use jenkins::jenkins::builds::BuildSummary;
+use jenkins::jenkins::notify::{job_name_from_build_url, send_webhook, BuildNotification};
+use jenkins::models::{GlobalConfig, NotifyConfig};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+fn notification() -> BuildNotification {
+    let build = BuildSummary {
+        number: 42,
+        url: "http://jenkins.example.com/job/folder/job/app/42/".to_string(),
+        result: Some("FAILURE".to_string()),
+        duration: 125_000,
+        triggered_by: Some("alice".to_string()),
+        ..Default::default()
+    };
+    BuildNotification::from_build("http://jenkins.example.com/job/folder/job/app/42/", &build)
+}
+
+#[test]
+fn job_name_of_build_url() {
+    assert_eq!(
+        job_name_from_build_url("http://jenkins.example.com/job/folder/job/app/42/"),
+        "folder/app"
+    );
+    assert_eq!(job_name_from_build_url("http://jenkins.example.com/job/app/7"), "app");
+    assert_eq!(job_name_from_build_url("http://jenkins.example.com/"), "");
+}
+
+#[test]
+fn notification_from_build() {
+    let notification = notification();
+    assert_eq!(notification.job, "folder/app");
+    assert_eq!(notification.result, "FAILURE");
+    assert_eq!(notification.title(), "folder/app #42");
+    assert_eq!(notification.summary(), "FAILURE in 2m 05s");
+}
+
+#[test]
+fn parse_notify_config() {
+    let config: GlobalConfig = toml::from_str(
+        r#"
+timeout = 30
+
+[notify]
+bell = true
+webhook = "http://127.0.0.1:9000/hook"
+"#,
+    )
+    .unwrap();
+    let notify = config.notify.unwrap();
+    assert_eq!(
+        notify,
+        NotifyConfig {
+            bell: Some(true),
+            desktop: None,
+            webhook: Some("http://127.0.0.1:9000/hook".to_string()),
+        }
+    );
+}
+
+/// Accept one request on a local receiver and return its body.
+async fn receive_one(listener: TcpListener, status_line: &str) -> String {
+    let (mut socket, _) = listener.accept().await.unwrap();
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 4096];
+    loop {
+        let read = socket.read(&mut buffer).await.unwrap();
+        request.extend_from_slice(&buffer[..read]);
+        let text = String::from_utf8_lossy(&request).to_string();
+        if let Some(header_end) = text.find("\r\n\r\n") {
+            let content_length = text[..header_end]
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().ok())?
+                })
+                .unwrap_or(0);
+            if request.len() >= header_end + 4 + content_length {
+                let response = format!("{}\r\ncontent-length: 0\r\n\r\n", status_line);
+                socket.write_all(response.as_bytes()).await.unwrap();
+                return text[header_end + 4..].to_string();
+            }
+        }
+        if read == 0 {
+            panic!("connection closed before the request was complete");
+        }
+    }
+}
+
+#[tokio::test]
+async fn post_webhook_payload() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let receiver = tokio::spawn(receive_one(listener, "HTTP/1.1 200 OK"));
+
+    send_webhook(&url, &notification()).await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&receiver.await.unwrap()).unwrap();
+    assert_eq!(body["job"], "folder/app");
+    assert_eq!(body["build_url"], "http://jenkins.example.com/job/folder/job/app/42/");
+    assert_eq!(body["number"], 42);
+    assert_eq!(body["result"], "FAILURE");
+    assert_eq!(body["duration_ms"], 125_000);
+    assert_eq!(body["triggered_by"], "alice");
+}
+
+#[tokio::test]
+async fn webhook_error_status_fails() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let receiver = tokio::spawn(receive_one(listener, "HTTP/1.1 500 Internal Server Error"));
+
+    assert!(send_webhook(&url, &notification()).await.is_err());
+    receiver.await.unwrap();
+}
@@ -0,0 +1,76 @@
+use jenkins::constants::ParamType;
+use jenkins::jenkins::param_templates::{matching_templates, template_applies, template_preset};
+use jenkins::jenkins::presets::PresetSource;
+use jenkins::jenkins::JenkinsJobParameter;
+use jenkins::models::{GlobalConfig, JenkinsConfig, ParamTemplate};
+use std::collections::BTreeMap;
+
+fn template(name: &str, params: &[(&str, &str)]) -> ParamTemplate {
+    ParamTemplate {
+        name: name.to_string(),
+        description: None,
+        params: params
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<BTreeMap<_, _>>(),
+    }
+}
+
+fn definition(name: &str, param_type: ParamType) -> JenkinsJobParameter {
+    JenkinsJobParameter {
+        name: name.to_string(),
+        param_type: Some(param_type),
+        ..Default::default()
+    }
+}
+
+fn definitions() -> Vec<JenkinsJobParameter> {
+    vec![
+        definition("ENV", ParamType::Choice),
+        definition("REGION", ParamType::String),
+        definition("GIT_BRANCH", ParamType::String),
+    ]
+}
+
+#[test]
+fn applies_when_job_defines_all_params() {
+    assert!(template_applies(
+        &template("eu", &[("ENV", "prod"), ("REGION", "eu")]),
+        &definitions()
+    ));
+    assert!(!template_applies(
+        &template("eu", &[("ENV", "prod"), ("ZONE", "a")]),
+        &definitions()
+    ));
+    assert!(!template_applies(&template("empty", &[]), &definitions()));
+}
+
+#[test]
+fn template_preset_uses_job_param_types() {
+    let preset = template_preset(&template("eu", &[("ENV", "prod"), ("REGION", "eu")]), &definitions());
+    assert_eq!(preset.source, Some(PresetSource::Template));
+    assert_eq!(preset.params["ENV"].r#type, ParamType::Choice);
+    assert_eq!(preset.params["REGION"].value, "eu");
+}
+
+#[test]
+fn service_templates_override_global_ones() {
+    let global = GlobalConfig {
+        templates: vec![
+            template("prod-eu", &[("ENV", "prod"), ("REGION", "eu")]),
+            template("dev", &[("ENV", "dev")]),
+            template("zone", &[("ZONE", "a")]),
+        ],
+        ..Default::default()
+    };
+    let service = JenkinsConfig {
+        templates: vec![template("prod-eu", &[("ENV", "prod"), ("REGION", "eu-west-1")])],
+        ..Default::default()
+    };
+    let presets = matching_templates(Some(&global), &service, &definitions());
+    let names: Vec<&str> = presets.iter().map(|preset| preset.name.as_str()).collect();
+    assert_eq!(names, vec!["dev", "prod-eu"]);
+    assert_eq!(presets[1].params["REGION"].value, "eu-west-1");
+
+    assert!(matching_templates(None, &JenkinsConfig::default(), &definitions()).is_empty());
+}
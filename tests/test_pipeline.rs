@@ -0,0 +1,130 @@
+use jenkins::jenkins::batch::BatchOutcome;
+use jenkins::jenkins::pipeline::{load_pipeline, StageCondition, StageStatus};
+use std::fs;
+use tempfile::tempdir;
+
+const PIPELINE: &str = r#"
+name = "release 1.2"
+
+[params]
+GIT_BRANCH = "release/1.2"
+
+[[stages]]
+name = "build"
+jobs = [
+  { url = "http://jenkins.example.com/job/api/", preset = "prod" },
+  { url = "http://jenkins.example.com/job/web/" },
+]
+
+[[stages]]
+name = "deploy"
+jobs = [{ url = "http://jenkins.example.com/job/deploy/", params = { ENV = "sit" } }]
+
+[[stages]]
+name = "rollback"
+when = "failure"
+jobs = [{ url = "http://jenkins.example.com/job/rollback/" }]
+"#;
+
+fn outcome(result: &str) -> BatchOutcome {
+    let mut outcome = BatchOutcome::new("job");
+    outcome.result = result.to_string();
+    outcome
+}
+
+#[test]
+fn load_pipeline_manifest() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("release.toml");
+    fs::write(&path, PIPELINE).unwrap();
+
+    let manifest = load_pipeline(&path).unwrap();
+    assert_eq!(manifest.name.as_deref(), Some("release 1.2"));
+    assert_eq!(manifest.params.len(), 1);
+    assert_eq!(manifest.stages.len(), 3);
+    assert_eq!(manifest.stages[0].jobs.len(), 2);
+    assert_eq!(manifest.stages[0].when, StageCondition::Success);
+    assert_eq!(manifest.stages[1].jobs[0].params.len(), 1);
+    assert_eq!(manifest.stages[2].when, StageCondition::Failure);
+
+    fs::write(&path, "[params]\nA = 1\n").unwrap();
+    assert!(load_pipeline(&path).is_err());
+    fs::write(&path, "[[stages]]\nname = \"empty\"\n").unwrap();
+    assert!(load_pipeline(&path).is_err());
+    fs::write(
+        &path,
+        "[[stages]]\nname = \"x\"\nwhen = \"sometimes\"\njobs = [{ url = \"u\" }]\n",
+    )
+    .unwrap();
+    assert!(load_pipeline(&path).is_err());
+}
+
+#[test]
+fn captures_must_come_from_earlier_stages() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("release.toml");
+    let stage = |name: &str, job: &str| format!("[[stages]]\nname = \"{}\"\njobs = [{}]\n", name, job);
+    let build = stage(
+        "build",
+        r#"{ url = "http://jenkins.example.com/job/api/", captures = { TAG = { log = "tag: (\\S+)" } } }"#,
+    );
+    let deploy = stage(
+        "deploy",
+        r#"{ url = "http://jenkins.example.com/job/deploy/", params = { IMAGE = "app:{{capture.TAG}}" } }"#,
+    );
+
+    fs::write(&path, format!("{}{}", build, deploy)).unwrap();
+    assert!(load_pipeline(&path).is_ok());
+
+    fs::write(&path, format!("{}{}", deploy, build)).unwrap();
+    let error = load_pipeline(&path).unwrap_err();
+    assert!(error.to_string().contains("\"TAG\""));
+
+    // shared parameters may use any captured value
+    fs::write(&path, format!("[params]\nIMAGE = \"{{{{capture.TAG}}}}\"\n{}", build)).unwrap();
+    assert!(load_pipeline(&path).is_ok());
+    fs::write(&path, format!("[params]\nIMAGE = \"{{{{capture.NOPE}}}}\"\n{}", build)).unwrap();
+    assert!(load_pipeline(&path).is_err());
+}
+
+#[test]
+fn stage_status_from_outcomes() {
+    assert_eq!(
+        StageStatus::from_outcomes(&[outcome("SUCCESS"), outcome("SUCCESS")]),
+        StageStatus::Success
+    );
+    assert_eq!(
+        StageStatus::from_outcomes(&[outcome("SUCCESS"), outcome("UNSTABLE")]),
+        StageStatus::Unstable
+    );
+    assert_eq!(
+        StageStatus::from_outcomes(&[outcome("UNSTABLE"), outcome("FAILURE")]),
+        StageStatus::Failure
+    );
+    // jobs that could not be triggered fail the stage
+    assert_eq!(StageStatus::from_outcomes(&[outcome("ERROR")]), StageStatus::Failure);
+    // so do values that could not be captured from a successful build
+    let mut capture_failed = outcome("SUCCESS");
+    capture_failed.error = Some("Captured value TAG not found".to_string());
+    assert_eq!(StageStatus::from_outcomes(&[capture_failed]), StageStatus::Failure);
+}
+
+#[test]
+fn stage_condition_gates_on_previous_stage() {
+    use StageStatus::*;
+
+    assert!(StageCondition::Success.allows(None));
+    assert!(StageCondition::Success.allows(Some(Success)));
+    assert!(!StageCondition::Success.allows(Some(Unstable)));
+    assert!(!StageCondition::Success.allows(Some(Skipped)));
+
+    assert!(StageCondition::Unstable.allows(Some(Unstable)));
+    assert!(!StageCondition::Unstable.allows(Some(Failure)));
+
+    assert!(!StageCondition::Failure.allows(None));
+    assert!(StageCondition::Failure.allows(Some(Failure)));
+    assert!(!StageCondition::Failure.allows(Some(Success)));
+
+    assert!(StageCondition::Always.allows(Some(Failure)));
+    assert!(StageCondition::Always.allows(Some(Skipped)));
+}
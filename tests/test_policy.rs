@@ -0,0 +1,112 @@
+use jenkins::constants::ParamType;
+use jenkins::jenkins::builds::BuildSummary;
+use jenkins::jenkins::policy::{is_protected, last_build_params, typed_name_matches};
+use jenkins::jenkins::ParamInfo;
+use jenkins::models::JenkinsConfig;
+use std::collections::HashMap;
+
+fn service(toml_str: &str) -> JenkinsConfig {
+    toml::from_str(toml_str).unwrap()
+}
+
+fn param(value: &str, r#type: ParamType) -> ParamInfo {
+    ParamInfo {
+        value: value.to_string(),
+        r#type,
+    }
+}
+
+#[test]
+fn service_protection_applies_to_all_jobs() {
+    let config = service(
+        r#"
+name = "PROD"
+url = "https://jenkins.example.com"
+protected = true
+"#,
+    );
+    assert!(is_protected(&config, "deploy-api", "Deploy API"));
+
+    let config = service(
+        r#"
+name = "DEV"
+url = "https://jenkins.example.com"
+"#,
+    );
+    assert!(!is_protected(&config, "deploy-api", "Deploy API"));
+}
+
+#[test]
+fn job_protection_overrides_service() {
+    let config = service(
+        r#"
+name = "MIXED"
+url = "https://jenkins.example.com"
+protected = true
+
+[[jobs]]
+job = "^sandbox-"
+protected = false
+
+[[jobs]]
+job = "-prod$"
+protected = true
+
+[[jobs]]
+job = ".*"
+pre_build = "./check.sh"
+"#,
+    );
+    assert!(!is_protected(&config, "sandbox-api", "Sandbox API"));
+    assert!(is_protected(&config, "web-prod", "Web"));
+    // entries without `protected` fall through to the service setting
+    assert!(is_protected(&config, "deploy-api", "Deploy API"));
+
+    let config = service(
+        r#"
+name = "DEV"
+url = "https://jenkins.example.com"
+
+[[jobs]]
+job = "-prod$"
+protected = true
+"#,
+    );
+    assert!(is_protected(&config, "web-prod", "Web"));
+    assert!(!is_protected(&config, "web-dev", "Web"));
+}
+
+#[test]
+fn typed_name_must_match_exactly() {
+    assert!(typed_name_matches("deploy-api", "deploy-api"));
+    assert!(typed_name_matches("  deploy-api\n", "deploy-api"));
+    assert!(!typed_name_matches("deploy-ap", "deploy-api"));
+    assert!(!typed_name_matches("Deploy-API", "deploy-api"));
+    assert!(!typed_name_matches("", "deploy-api"));
+}
+
+#[test]
+fn last_build_params_use_current_types() {
+    let build = BuildSummary {
+        number: 7,
+        parameters: vec![
+            ("GIT_BRANCH".to_string(), Some("release/1.1".to_string())),
+            ("SKIP_TESTS".to_string(), Some("false".to_string())),
+            ("DB_PASSWORD".to_string(), None),
+            ("REMOVED".to_string(), Some("x".to_string())),
+        ],
+        ..Default::default()
+    };
+    let params = HashMap::from([
+        ("GIT_BRANCH".to_string(), param("release/1.2", ParamType::String)),
+        ("SKIP_TESTS".to_string(), param("false", ParamType::Boolean)),
+        ("DB_PASSWORD".to_string(), param("secret", ParamType::Password)),
+    ]);
+
+    let last = last_build_params(&build, &params);
+    assert_eq!(last["GIT_BRANCH"], param("release/1.1", ParamType::String));
+    assert_eq!(last["SKIP_TESTS"], param("false", ParamType::Boolean));
+    // hidden password values count as unchanged
+    assert_eq!(last["DB_PASSWORD"], param("secret", ParamType::Password));
+    assert_eq!(last["REMOVED"], param("x", ParamType::String));
+}
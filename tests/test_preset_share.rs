@@ -0,0 +1,227 @@
+use jenkins::constants::ParamType;
+use jenkins::jenkins::preset_share::*;
+use jenkins::jenkins::presets::{JobPresetIdentity, ParameterPreset, PresetSource, PresetStore, PRESETS_FILE};
+use jenkins::jenkins::ParamInfo;
+use jenkins::models::JenkinsConfig;
+use regex::Regex;
+use std::collections::HashMap;
+use tempfile::tempdir;
+
+fn param(value: &str, r#type: ParamType) -> ParamInfo {
+    ParamInfo {
+        value: value.to_string(),
+        r#type,
+    }
+}
+
+fn preset(name: &str, branch: &str) -> ParameterPreset {
+    ParameterPreset {
+        name: name.to_string(),
+        params: HashMap::from([
+            ("BRANCH".to_string(), param(branch, ParamType::String)),
+            ("DB_PASSWORD".to_string(), param("s3cret", ParamType::Password)),
+        ]),
+        last_used_at: Some(1),
+        ..Default::default()
+    }
+}
+
+fn service(name: &str, url: &str) -> JenkinsConfig {
+    JenkinsConfig {
+        name: name.to_string(),
+        url: url.to_string(),
+        ..Default::default()
+    }
+}
+
+fn identity(service_url: &str, job_name: &str) -> JobPresetIdentity {
+    JobPresetIdentity {
+        service_url: service_url.to_string(),
+        job_url: format!("{}/job/{}", service_url, job_name),
+        job_name: job_name.to_string(),
+        display_name: None,
+    }
+}
+
+fn store() -> (PresetStore, tempfile::TempDir) {
+    let temp_dir = tempdir().unwrap();
+    let mut store = PresetStore {
+        file_path: temp_dir.path().join(PRESETS_FILE),
+        ..Default::default()
+    };
+    store.insert_preset(
+        &identity("http://jenkins.corp:8080", "api"),
+        preset("release", "release/1.2"),
+    );
+    store.insert_preset(&identity("http://jenkins.corp:8080", "web"), preset("main", "main"));
+    store.insert_preset(&identity("http://other.corp", "api"), preset("other", "main"));
+    (store, temp_dir)
+}
+
+#[test]
+fn export_strips_passwords_and_filters() {
+    let (store, _dir) = store();
+    let services = [service("corp", "http://jenkins.corp:8080/")];
+    let filter = ExportFilter {
+        service_url: Some("http://jenkins.corp:8080".to_string()),
+        job: Some(Regex::new("^api$").unwrap()),
+    };
+    let export = export_presets(&store, &services, &filter);
+    assert_eq!(export.jobs.len(), 1);
+    let job = &export.jobs[0];
+    assert_eq!(job.service_name.as_deref(), Some("corp"));
+    assert_eq!(job.presets[0].params.len(), 1);
+    assert!(!job.presets[0].params.contains_key("DB_PASSWORD"));
+    assert_eq!(job.presets[0].last_used_at, None);
+
+    let content = toml::to_string(&export).unwrap();
+    assert!(!content.contains("s3cret"));
+    let parsed: PresetExport = toml::from_str(&content).unwrap();
+    assert_eq!(parsed.jobs[0].presets[0].name, "release");
+
+    assert_eq!(
+        export_presets(&store, &services, &ExportFilter::default()).jobs.len(),
+        3
+    );
+}
+
+#[test]
+fn rewrites_service_by_name() {
+    assert_eq!(
+        rewrite_job_url(
+            "jenkins.corp:8080/job/api",
+            "jenkins.corp:8080",
+            "http://10.0.0.5:8080/"
+        ),
+        "10.0.0.5:8080/job/api"
+    );
+
+    let (store, _dir) = store();
+    let export = export_presets(
+        &store,
+        &[service("corp", "http://jenkins.corp:8080")],
+        &ExportFilter::default(),
+    );
+    let local = [service("corp", "http://10.0.0.5:8080")];
+    let (mut target, _target_dir) = {
+        let temp_dir = tempdir().unwrap();
+        let target = PresetStore {
+            file_path: temp_dir.path().join(PRESETS_FILE),
+            ..Default::default()
+        };
+        (target, temp_dir)
+    };
+
+    // other.corp has no configured service
+    assert!(import_presets(&mut target, &export, &local, None, |_, _| None).is_err());
+    assert!(target.jobs.is_empty());
+
+    let summary = import_presets(&mut target, &export, &local, Some(&local[0]), |_, _| None)
+        .unwrap()
+        .unwrap();
+    assert_eq!(summary.added, 3);
+    assert!(target.preset_exists(&identity("http://10.0.0.5:8080", "api"), "release"));
+    assert!(target.preset_exists(&identity("http://10.0.0.5:8080", "api"), "other"));
+}
+
+#[test]
+fn resolves_conflicts() {
+    let (mut store, _dir) = store();
+    let services = [service("corp", "http://jenkins.corp:8080")];
+    let api = identity("http://jenkins.corp:8080", "api");
+    let export = PresetExport {
+        version: Some(1),
+        jobs: vec![ExportedJob {
+            service_name: Some("corp".to_string()),
+            service_url: "jenkins.corp:8080".to_string(),
+            job_url: "jenkins.corp:8080/job/api".to_string(),
+            job_name: "api".to_string(),
+            display_name: None,
+            presets: vec![preset("release", "release/1.2"), preset("release", "release/2.0")],
+        }],
+    };
+
+    // The first one is identical apart from the password
+    let summary = import_presets(&mut store, &export, &services, None, |_, _| Some(ConflictAction::Skip))
+        .unwrap()
+        .unwrap();
+    assert_eq!((summary.unchanged, summary.skipped), (1, 1));
+    assert_eq!(
+        store.find_preset(&api, "release").unwrap().params["BRANCH"].value,
+        "release/1.2"
+    );
+
+    let summary = import_presets(&mut store, &export, &services, None, |_, _| {
+        Some(ConflictAction::Rename)
+    })
+    .unwrap()
+    .unwrap();
+    assert_eq!(summary.renamed, 1);
+    assert_eq!(
+        store.find_preset(&api, "release-2").unwrap().params["BRANCH"].value,
+        "release/2.0"
+    );
+
+    let summary = import_presets(&mut store, &export, &services, None, |_, _| {
+        Some(ConflictAction::Overwrite)
+    })
+    .unwrap()
+    .unwrap();
+    assert_eq!(summary.overwritten, 1);
+    let release = store.find_preset(&api, "release").unwrap();
+    assert_eq!(release.params["BRANCH"].value, "release/2.0");
+    assert_eq!(release.params["DB_PASSWORD"].value, "s3cret");
+
+    assert_eq!(
+        import_presets(&mut store, &export, &services, None, |_, _| None).unwrap(),
+        None
+    );
+    assert_eq!("rename".parse::<ConflictAction>().unwrap(), ConflictAction::Rename);
+    assert!("merge".parse::<ConflictAction>().is_err());
+}
+
+#[test]
+fn reads_preset_source_directories() {
+    let temp_dir = tempdir().unwrap();
+    std::fs::write(temp_dir.path().join("b.toml"), "").unwrap();
+    std::fs::write(temp_dir.path().join("a.toml"), "").unwrap();
+    std::fs::write(temp_dir.path().join("notes.md"), "").unwrap();
+    let files = preset_source_files(temp_dir.path().to_str().unwrap()).unwrap();
+    let names: Vec<_> = files.iter().map(|file| file.file_name().unwrap().to_owned()).collect();
+    assert_eq!(names, vec!["a.toml", "b.toml"]);
+
+    let file = temp_dir.path().join("a.toml");
+    assert_eq!(preset_source_files(file.to_str().unwrap()).unwrap(), vec![file]);
+}
+
+#[test]
+fn shared_presets_are_listed_after_personal_ones() {
+    let (mut store, _dir) = store();
+    let export = export_presets(
+        &store,
+        &[service("corp", "http://jenkins.corp:8080")],
+        &ExportFilter::default(),
+    );
+    let local = [service("corp", "http://10.0.0.5:8080")];
+    store.shared = shared_job_presets(&export, "ci/jenkins-presets.toml", &local);
+
+    // Mapped to the local hostname of the service
+    let api = identity("http://10.0.0.5:8080", "api");
+    let shared = store.find_preset(&api, "release").unwrap();
+    assert_eq!(
+        shared.source,
+        Some(PresetSource::Shared("ci/jenkins-presets.toml".to_string()))
+    );
+    assert!(store.get_job_presets(&api).is_none());
+
+    // Jobs of services that are not configured keep their URLs
+    let other = identity("http://other.corp", "api");
+    let presets = store.sorted_presets(&other);
+    assert_eq!(presets.len(), 2);
+    assert_eq!(presets[0].source, None);
+    assert_eq!(
+        presets[1].source,
+        Some(PresetSource::Shared("ci/jenkins-presets.toml".to_string()))
+    );
+    assert_eq!(store.find_preset(&other, "other").unwrap().source, None);
+}
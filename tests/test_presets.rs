@@ -1,6 +1,6 @@
 use jenkins::constants::ParamType;
 use jenkins::jenkins::presets::*;
-use jenkins::jenkins::ParamInfo;
+use jenkins::jenkins::{JenkinsJobParameter, ParamInfo};
 use std::collections::HashMap;
 use tempfile::tempdir;
 
@@ -14,6 +14,8 @@ fn setup_store() -> (PresetStore, tempfile::TempDir) {
         jobs: vec![],
         file_path,
         version: Some(1),
+        shared: vec![],
+        saved: vec![],
     };
     (store, temp_dir)
 }
@@ -134,6 +136,8 @@ fn persists_preset_names_and_params_with_spaces_and_special_chars() {
         jobs: vec![],
         file_path: store.file_path.clone(),
         version: None,
+        shared: vec![],
+        saved: vec![],
     };
     loaded.load_presets().unwrap();
 
@@ -216,3 +220,110 @@ fn cleanup_obsolete_projects_removes_matching_service_only() {
     assert!(store.get_job_presets(&backend).is_none());
     assert!(store.get_job_presets(&other_service).is_some());
 }
+
+#[test]
+fn diff_params_reports_changed_added_and_removed() {
+    let old = params("main");
+    let mut new = params("release");
+    new.remove("ENV");
+    new.insert(
+        "TAG".to_string(),
+        ParamInfo {
+            value: "v1.0.0".to_string(),
+            r#type: ParamType::String,
+        },
+    );
+
+    let changes = diff_params(&old, &new);
+    let names: Vec<&str> = changes.iter().map(|change| change.name.as_str()).collect();
+    assert_eq!(names, vec!["BRANCH", "ENV", "TAG"]);
+    assert_eq!(changes[0].old.as_ref().unwrap().value, "main");
+    assert_eq!(changes[0].new.as_ref().unwrap().value, "release");
+    assert!(changes[1].new.is_none());
+    assert!(changes[2].old.is_none());
+    assert!(diff_params(&old, &params("main")).is_empty());
+}
+
+#[test]
+fn update_preset_sets_description_tags_and_values() {
+    let (mut store, _temp_dir) = setup_store();
+    let id = identity(JOB_URL, "frontend");
+    store.upsert_preset(&id, "release", params("main")).unwrap();
+
+    let updated = store
+        .update_preset(&id, "release", |preset| {
+            preset.description = Some("Release to UAT".to_string());
+            preset.tags = parse_tags("uat, release, ,uat");
+            preset.params.get_mut("BRANCH").unwrap().value = "release/1.0".to_string();
+        })
+        .unwrap();
+    assert!(updated);
+    assert!(!store.update_preset(&id, "missing", |_| {}).unwrap());
+
+    let preset = store.find_preset(&id, "release").unwrap();
+    assert_eq!(preset.tags, vec!["uat", "release"]);
+    assert_eq!(preset.params["BRANCH"].value, "release/1.0");
+    assert_eq!(preset_details(&preset), " [uat, release] - Release to UAT");
+
+    let mut loaded = PresetStore {
+        file_path: store.file_path.clone(),
+        ..Default::default()
+    };
+    loaded.load_presets().unwrap();
+    assert_eq!(loaded.find_preset(&id, "release").unwrap().tags, vec!["uat", "release"]);
+}
+
+#[test]
+fn duplicate_preset_copies_params_and_rejects_conflicts() {
+    let (mut store, _temp_dir) = setup_store();
+    let id = identity(JOB_URL, "frontend");
+    store.upsert_preset(&id, "release", params("main")).unwrap();
+    store.mark_preset_used(&id, "release").unwrap();
+
+    assert!(store.duplicate_preset(&id, "release", "release-copy").unwrap());
+    let copy = store.find_preset(&id, "release-copy").unwrap();
+    assert_eq!(copy.params, params("main"));
+    assert_eq!(copy.last_used_at, None);
+
+    assert!(store.duplicate_preset(&id, "release", "release-copy").is_err());
+    assert!(!store.duplicate_preset(&id, "missing", "other").unwrap());
+}
+
+#[test]
+fn compare_with_definitions_flags_obsolete_and_new_params() {
+    let preset = ParameterPreset {
+        name: "release".to_string(),
+        params: params("main"),
+        ..Default::default()
+    };
+    let definitions = vec![
+        JenkinsJobParameter {
+            name: "BRANCH".to_string(),
+            default_value: Some("develop".to_string()),
+            ..Default::default()
+        },
+        JenkinsJobParameter {
+            name: "SKIP_TESTS".to_string(),
+            param_type: Some(ParamType::Boolean),
+            default_value: Some("false".to_string()),
+            ..Default::default()
+        },
+    ];
+
+    let comparisons = compare_with_definitions(&preset, &definitions);
+    let summary: Vec<(&str, Option<&str>, ParamStatus)> = comparisons
+        .iter()
+        .map(|c| (c.name.as_str(), c.preset_value.as_deref(), c.status))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("BRANCH", Some("main"), ParamStatus::Current),
+            ("SKIP_TESTS", None, ParamStatus::New),
+            ("ENV", Some("sit"), ParamStatus::Obsolete),
+        ]
+    );
+    assert_eq!(comparisons[0].job_default.as_deref(), Some("develop"));
+    assert_eq!(comparisons[1].r#type, ParamType::Boolean);
+    assert_eq!(comparisons[2].r#type, ParamType::Choice);
+}
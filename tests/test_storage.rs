@@ -0,0 +1,191 @@
+use jenkins::constants::ParamType;
+use jenkins::jenkins::history::{History, HistoryEntry, HISTORY_FILE};
+use jenkins::jenkins::presets::{JobPresetIdentity, PresetStore, PRESETS_FILE};
+use jenkins::jenkins::ParamInfo;
+use jenkins::storage::*;
+use std::collections::HashMap;
+use std::fs;
+use tempfile::tempdir;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Record {
+    key: &'static str,
+    value: u32,
+}
+
+fn record(key: &'static str, value: u32) -> Record {
+    Record { key, value }
+}
+
+fn merge(base: &[Record], ours: &[Record], theirs: &[Record]) -> Vec<Record> {
+    merge_records(base, ours, theirs, |record| record.key, |_, ours, _| ours.clone())
+}
+
+#[test]
+fn test_merge_records_keeps_changes_of_both_sides() {
+    let base = vec![record("a", 1), record("b", 1), record("c", 1)];
+    let ours = vec![record("a", 2), record("b", 1), record("c", 1), record("d", 1)];
+    let theirs = vec![record("a", 1), record("b", 3), record("c", 1), record("e", 1)];
+    assert_eq!(
+        merge(&base, &ours, &theirs),
+        vec![
+            record("a", 2),
+            record("b", 3),
+            record("c", 1),
+            record("e", 1),
+            record("d", 1)
+        ]
+    );
+}
+
+#[test]
+fn test_merge_records_deletions() {
+    let base = vec![record("a", 1), record("b", 1), record("c", 1), record("d", 1)];
+    // We deleted a and c, they deleted b and d; they changed c, we changed d meanwhile
+    let ours = vec![record("b", 1), record("d", 2)];
+    let theirs = vec![record("a", 1), record("c", 2)];
+    assert_eq!(merge(&base, &ours, &theirs), vec![record("c", 2), record("d", 2)]);
+}
+
+#[test]
+fn test_merge_records_resolves_conflicts() {
+    let base = vec![record("a", 1)];
+    let ours = vec![record("a", 2), record("b", 2)];
+    let theirs = vec![record("a", 3), record("b", 5)];
+    let merged = merge_records(
+        &base,
+        &ours,
+        &theirs,
+        |record| record.key,
+        |_, ours, theirs| record(ours.key, ours.value.max(theirs.value)),
+    );
+    assert_eq!(merged, vec![record("a", 3), record("b", 5)]);
+}
+
+#[test]
+fn test_write_atomic_replaces_file() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("store.toml");
+    fs::write(&path, "old").unwrap();
+    write_atomic(&path, "new").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+    // No temporary file is left behind
+    assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn test_lock_file_is_exclusive() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("store.toml");
+    let lock = lock_file(&path).unwrap();
+    assert!(lock_path(&path).exists());
+    let other = fs::File::open(lock_path(&path)).unwrap();
+    assert!(other.try_lock().is_err());
+    drop(lock);
+    assert!(other.try_lock().is_ok());
+}
+
+fn history_entry(name: &str, branch: &str) -> HistoryEntry {
+    HistoryEntry {
+        job_url: format!("http://jenkins/job/{}", name),
+        name: name.to_string(),
+        params: Some(HashMap::from([(
+            "BRANCH".to_string(),
+            ParamInfo {
+                value: branch.to_string(),
+                r#type: ParamType::String,
+            },
+        )])),
+        ..Default::default()
+    }
+}
+
+fn open_history(path: &std::path::Path) -> History {
+    let mut history = History {
+        file_path: path.to_path_buf(),
+        ..Default::default()
+    };
+    history.load_history().unwrap();
+    history
+}
+
+#[test]
+fn test_history_concurrent_saves_are_merged() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join(HISTORY_FILE);
+    let mut first = open_history(&path);
+    let mut second = open_history(&path);
+
+    first.upsert_history(&mut history_entry("app", "main")).unwrap();
+    second.upsert_history(&mut history_entry("api", "develop")).unwrap();
+
+    let loaded = open_history(&path);
+    let mut names: Vec<&str> = loaded.entries.iter().map(|entry| entry.name.as_str()).collect();
+    names.sort();
+    assert_eq!(names, vec!["api", "app"]);
+    // The saving process sees the other's changes too
+    assert_eq!(second.entries.len(), 2);
+}
+
+#[test]
+fn test_history_corrupted_file_is_moved_aside() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join(HISTORY_FILE);
+    fs::write(&path, "entries = [ not toml").unwrap();
+
+    let history = open_history(&path);
+    assert!(history.entries.is_empty());
+    assert!(!path.exists());
+    let moved: Vec<String> = fs::read_dir(temp_dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with("history.toml.corrupted-"))
+        .collect();
+    assert_eq!(moved.len(), 1);
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join(&moved[0])).unwrap(),
+        "entries = [ not toml"
+    );
+}
+
+fn identity(job_name: &str) -> JobPresetIdentity {
+    JobPresetIdentity {
+        service_url: "http://jenkins".to_string(),
+        job_url: format!("http://jenkins/job/{}", job_name),
+        job_name: job_name.to_string(),
+        display_name: None,
+    }
+}
+
+fn open_presets(path: &std::path::Path) -> PresetStore {
+    let mut store = PresetStore {
+        file_path: path.to_path_buf(),
+        ..Default::default()
+    };
+    store.load_presets().unwrap();
+    store
+}
+
+#[test]
+fn test_presets_concurrent_saves_are_merged() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join(PRESETS_FILE);
+    let params = history_entry("app", "main").params.unwrap();
+    let mut store = open_presets(&path);
+    store.upsert_preset(&identity("app"), "old", params.clone()).unwrap();
+
+    let mut first = open_presets(&path);
+    let mut second = open_presets(&path);
+    first.upsert_preset(&identity("app"), "sit", params.clone()).unwrap();
+    second.upsert_preset(&identity("app"), "uat", params.clone()).unwrap();
+    second.delete_preset(&identity("app"), "old").unwrap();
+
+    let loaded = open_presets(&path);
+    let mut names: Vec<String> = loaded
+        .sorted_presets(&identity("app"))
+        .into_iter()
+        .map(|preset| preset.name)
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["sit", "uat"]);
+}
@@ -0,0 +1,107 @@
+use jenkins::jenkins::test_report::parse_test_report;
+use serde_json::json;
+
+#[test]
+fn parse_test_report_counts_and_failures() {
+    let json_data = json!({
+        "_class": "hudson.tasks.junit.TestResult",
+        "duration": 1.5,
+        "failCount": 1,
+        "passCount": 2,
+        "skipCount": 1,
+        "suites": [
+            {
+                "name": "com.example.ApiTest",
+                "duration": 1.5,
+                "cases": [
+                    { "className": "com.example.ApiTest", "name": "login", "status": "PASSED", "duration": 0.5 },
+                    { "className": "com.example.ApiTest", "name": "logout", "status": "FIXED", "duration": 0.2 },
+                    {
+                        "className": "com.example.ApiTest",
+                        "name": "refresh",
+                        "status": "REGRESSION",
+                        "duration": 0.8,
+                        "errorDetails": "expected 200 but was 500",
+                        "errorStackTrace": "java.lang.AssertionError: expected 200 but was 500"
+                    },
+                    { "className": "com.example.ApiTest", "name": "admin", "skipped": true }
+                ]
+            }
+        ]
+    });
+
+    let report = parse_test_report(&json_data);
+    assert_eq!(report.passed, 2);
+    assert_eq!(report.failed, 1);
+    assert_eq!(report.skipped, 1);
+    assert_eq!(report.total(), 4);
+
+    let failed = report.failed_cases();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].full_name(), "com.example.ApiTest.refresh");
+    assert_eq!(failed[0].error_details.as_deref(), Some("expected 200 but was 500"));
+    assert!(report.suites[0].cases[3].is_skipped());
+}
+
+#[test]
+fn parse_aggregated_test_report() {
+    let json_data = json!({
+        "_class": "hudson.maven.reporters.SurefireAggregatedReport",
+        "failCount": 1,
+        "skipCount": 0,
+        "totalCount": 3,
+        "childReports": [
+            {
+                "result": {
+                    "failCount": 0,
+                    "passCount": 2,
+                    "skipCount": 0,
+                    "suites": [{ "name": "a", "cases": [{ "className": "A", "name": "one", "status": "PASSED" }] }]
+                }
+            },
+            {
+                "result": {
+                    "failCount": 1,
+                    "passCount": 0,
+                    "skipCount": 0,
+                    "suites": [{ "name": "b", "cases": [{ "className": "B", "name": "two", "status": "FAILED" }] }]
+                }
+            }
+        ]
+    });
+
+    let report = parse_test_report(&json_data);
+    assert_eq!(report.passed, 2);
+    assert_eq!(report.failed, 1);
+    assert_eq!(report.suites.len(), 2);
+    assert_eq!(report.failed_cases()[0].full_name(), "B.two");
+}
+
+#[test]
+fn test_report_to_junit_xml() {
+    let json_data = json!({
+        "failCount": 1,
+        "passCount": 1,
+        "skipCount": 1,
+        "suites": [
+            {
+                "name": "suite<1>",
+                "duration": 0.3,
+                "cases": [
+                    { "className": "S", "name": "ok", "status": "PASSED", "duration": 0.1 },
+                    { "className": "S", "name": "bad", "status": "FAILED", "duration": 0.2, "errorDetails": "a & b" },
+                    { "className": "S", "name": "skip", "status": "SKIPPED" }
+                ]
+            }
+        ]
+    });
+
+    let xml = parse_test_report(&json_data).to_junit_xml();
+    println!("{}", xml);
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+    assert!(xml.contains("<testsuites tests=\"3\" failures=\"1\" skipped=\"1\""));
+    assert!(xml.contains("<testsuite name=\"suite&lt;1&gt;\" tests=\"3\" failures=\"1\" skipped=\"1\""));
+    assert!(xml.contains("<testcase classname=\"S\" name=\"ok\" time=\"0.100\"/>"));
+    assert!(xml.contains("<failure message=\"a &amp; b\"></failure>"));
+    assert!(xml.contains("<skipped/>"));
+}
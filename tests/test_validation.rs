@@ -0,0 +1,119 @@
+use jenkins::constants::{ParamType, DEFAULT_PARAM_VALUE};
+use jenkins::jenkins::validation::{resolve_rules, ParamValidator, RuleViolation};
+use jenkins::jenkins::ParamInfo;
+use jenkins::models::JenkinsConfig;
+use std::collections::HashMap;
+
+fn service() -> JenkinsConfig {
+    toml::from_str(
+        r#"
+name = "PROD"
+url = "https://jenkins.example.com"
+
+[[jobs]]
+job = "^deploy-api$"
+[jobs.rules.VERSION]
+regex = '^\d+\.\d+\.\d+$'
+required = true
+[jobs.rules.ENV]
+allowed = ["staging", "prod"]
+message = "ENV is staging or prod"
+
+[[jobs]]
+job = "^deploy-"
+[jobs.rules.VERSION]
+required = true
+[jobs.rules.TAG]
+git_tag = true
+"#,
+    )
+    .unwrap()
+}
+
+fn validator() -> ParamValidator {
+    let rules = resolve_rules(&service(), "deploy-api", "Deploy API");
+    ParamValidator::with_tags(rules, vec!["v1.2.0".to_string(), "v1.3.0".to_string()])
+}
+
+fn param(value: &str) -> ParamInfo {
+    ParamInfo {
+        value: value.to_string(),
+        r#type: ParamType::String,
+    }
+}
+
+#[test]
+fn first_matching_job_rule_wins() {
+    let rules = resolve_rules(&service(), "deploy-api", "Deploy API");
+    assert_eq!(rules.keys().collect::<Vec<_>>(), vec!["ENV", "TAG", "VERSION"]);
+    assert!(rules["VERSION"].regex.is_some());
+
+    let rules = resolve_rules(&service(), "deploy-web", "Deploy Web");
+    assert_eq!(rules.keys().collect::<Vec<_>>(), vec!["TAG", "VERSION"]);
+    assert!(rules["VERSION"].regex.is_none());
+
+    assert!(resolve_rules(&service(), "test-api", "Test API").is_empty());
+}
+
+#[test]
+fn check_values() {
+    let validator = validator();
+    assert_eq!(validator.check("VERSION", "1.2.3"), Ok(()));
+    assert_eq!(
+        validator.check("VERSION", "1.2"),
+        Err(RuleViolation::Pattern(r"^\d+\.\d+\.\d+$".to_string()))
+    );
+    assert_eq!(validator.check("VERSION", " "), Err(RuleViolation::Required));
+    assert_eq!(validator.check("ENV", "prod"), Ok(()));
+    assert_eq!(
+        validator.check("ENV", "production"),
+        Err(RuleViolation::Custom("ENV is staging or prod".to_string()))
+    );
+    // not required: empty values pass
+    assert_eq!(validator.check("ENV", ""), Ok(()));
+    assert_eq!(validator.check("TAG", "v1.3.0"), Ok(()));
+    assert_eq!(validator.check("TAG", "v1.4.0"), Err(RuleViolation::NotAGitTag));
+    assert_eq!(validator.check("OTHER", "anything"), Ok(()));
+    assert_eq!(validator.check("VERSION", DEFAULT_PARAM_VALUE), Ok(()));
+}
+
+#[test]
+fn invalid_rule_regex_is_reported() {
+    let config: JenkinsConfig = toml::from_str(
+        r#"
+[[jobs]]
+job = ".*"
+rules = { VERSION = { regex = "([0-9]", message = "bad version" } }
+"#,
+    )
+    .unwrap();
+    let validator = ParamValidator::with_tags(resolve_rules(&config, "app", "app"), Vec::new());
+    assert_eq!(
+        validator.check("VERSION", "1"),
+        Err(RuleViolation::InvalidRegex("([0-9]".to_string()))
+    );
+}
+
+#[test]
+fn check_all_reports_missing_required_params() {
+    let validator = validator();
+    let params = HashMap::from([("ENV".to_string(), param("qa")), ("TAG".to_string(), param("v1.2.0"))]);
+    let violations = validator.check_all(&params);
+    assert_eq!(
+        violations,
+        vec![
+            (
+                "ENV".to_string(),
+                RuleViolation::Custom("ENV is staging or prod".to_string())
+            ),
+            ("VERSION".to_string(), RuleViolation::Required),
+        ]
+    );
+
+    let params = HashMap::from([
+        ("ENV".to_string(), param("prod")),
+        ("VERSION".to_string(), param("2.0.1")),
+        ("TAG".to_string(), param("v1.2.0")),
+    ]);
+    assert!(validator.check_all(&params).is_empty());
+}